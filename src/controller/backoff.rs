@@ -0,0 +1,194 @@
+//! Per-object exponential backoff for failed reconciliations.
+//!
+//! The controller's error policy has no memory of its own, so consecutive
+//! failures are tracked here per object (keyed by namespace, name and uid).
+//! Each failure doubles the requeue delay up to a configurable cap, with
+//! jitter so that many objects failing at once don't retry in lockstep.
+//! A successful reconcile resets the counter.
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::Duration;
+
+use kube::ResourceExt;
+
+use crate::crd::MyResource;
+
+/// Default cap on the requeue delay for retryable errors
+pub const DEFAULT_MAX_BACKOFF: Duration = Duration::from_secs(300);
+
+/// Environment variable overriding the backoff cap (in seconds)
+pub const MAX_BACKOFF_ENV: &str = "RECONCILE_MAX_BACKOFF_SECS";
+
+/// Identity of a reconciled object.
+///
+/// The uid is included so a recreated object with the same name starts fresh.
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+pub struct ObjectKey {
+    pub namespace: String,
+    pub name: String,
+    pub uid: String,
+}
+
+impl ObjectKey {
+    /// Build the key for a MyResource
+    pub fn from_resource(resource: &MyResource) -> Self {
+        Self {
+            namespace: resource.namespace().unwrap_or_default(),
+            name: resource.name_any(),
+            uid: resource.uid().unwrap_or_default(),
+        }
+    }
+}
+
+/// Tracks consecutive reconcile failures per object
+#[derive(Debug)]
+pub struct BackoffTracker {
+    /// Upper bound for the computed delay
+    max_delay: Duration,
+    /// Consecutive failure counts per object
+    failures: Mutex<HashMap<ObjectKey, u32>>,
+}
+
+impl Default for BackoffTracker {
+    fn default() -> Self {
+        Self::new(DEFAULT_MAX_BACKOFF)
+    }
+}
+
+impl BackoffTracker {
+    /// Create a tracker with the given delay cap
+    pub fn new(max_delay: Duration) -> Self {
+        Self {
+            max_delay,
+            failures: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Create a tracker with the cap read from `RECONCILE_MAX_BACKOFF_SECS`,
+    /// falling back to `DEFAULT_MAX_BACKOFF` if unset or invalid
+    pub fn from_env() -> Self {
+        let max_delay = std::env::var(MAX_BACKOFF_ENV)
+            .ok()
+            .and_then(|v| v.parse::<u64>().ok())
+            .filter(|secs| *secs > 0)
+            .map(Duration::from_secs)
+            .unwrap_or(DEFAULT_MAX_BACKOFF);
+        Self::new(max_delay)
+    }
+
+    /// Get the configured delay cap
+    pub fn max_delay(&self) -> Duration {
+        self.max_delay
+    }
+
+    /// Record a failure and return the new consecutive failure count
+    pub fn record_failure(&self, key: &ObjectKey) -> u32 {
+        let mut failures = self.lock();
+        let count = failures.entry(key.clone()).or_insert(0);
+        *count = count.saturating_add(1);
+        *count
+    }
+
+    /// Forget all failures for an object
+    pub fn reset(&self, key: &ObjectKey) {
+        self.lock().remove(key);
+    }
+
+    /// Get the current consecutive failure count for an object
+    pub fn failures(&self, key: &ObjectKey) -> u32 {
+        self.lock().get(key).copied().unwrap_or(0)
+    }
+
+    /// Compute a jittered delay for the given failure count
+    pub fn delay(&self, base: Duration, failures: u32) -> Duration {
+        backoff_delay(
+            base,
+            self.max_delay,
+            failures,
+            rand::random_range(0.0..=1.0),
+        )
+    }
+
+    /// Lock the failure map, recovering from a poisoned lock since the counts
+    /// are only advisory
+    fn lock(&self) -> std::sync::MutexGuard<'_, HashMap<ObjectKey, u32>> {
+        self.failures
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+    }
+}
+
+/// Compute an exponential backoff delay with "equal jitter".
+///
+/// The un-jittered delay is `base * 2^(failures - 1)`, capped at `max`. Half of
+/// it is always waited and the other half is scaled by `jitter` (expected in
+/// `0.0..=1.0`), so the result lies in `[delay / 2, delay]`.
+pub fn backoff_delay(base: Duration, max: Duration, failures: u32, jitter: f64) -> Duration {
+    let exponent = failures.saturating_sub(1).min(31);
+    let delay = base.saturating_mul(1u32 << exponent).min(max);
+    let half = delay / 2;
+    half + half.mul_f64(jitter.clamp(0.0, 1.0))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BASE: Duration = Duration::from_secs(5);
+    const MAX: Duration = Duration::from_secs(300);
+
+    #[test]
+    fn test_backoff_grows_exponentially() {
+        assert_eq!(backoff_delay(BASE, MAX, 1, 1.0), Duration::from_secs(5));
+        assert_eq!(backoff_delay(BASE, MAX, 2, 1.0), Duration::from_secs(10));
+        assert_eq!(backoff_delay(BASE, MAX, 3, 1.0), Duration::from_secs(20));
+        assert_eq!(backoff_delay(BASE, MAX, 4, 1.0), Duration::from_secs(40));
+    }
+
+    #[test]
+    fn test_backoff_is_capped() {
+        assert_eq!(backoff_delay(BASE, MAX, 10, 1.0), MAX);
+        assert_eq!(backoff_delay(BASE, MAX, u32::MAX, 1.0), MAX);
+    }
+
+    #[test]
+    fn test_backoff_jitter_bounds() {
+        assert_eq!(backoff_delay(BASE, MAX, 3, 0.0), Duration::from_secs(10));
+        assert_eq!(backoff_delay(BASE, MAX, 3, 0.5), Duration::from_secs(15));
+        // Out-of-range jitter is clamped
+        assert_eq!(backoff_delay(BASE, MAX, 3, 7.0), Duration::from_secs(20));
+    }
+
+    #[test]
+    fn test_tracker_counts_and_resets() {
+        let tracker = BackoffTracker::new(MAX);
+        let key = ObjectKey {
+            namespace: "default".to_string(),
+            name: "test".to_string(),
+            uid: "uid-1".to_string(),
+        };
+        let other = ObjectKey {
+            uid: "uid-2".to_string(),
+            ..key.clone()
+        };
+
+        assert_eq!(tracker.record_failure(&key), 1);
+        assert_eq!(tracker.record_failure(&key), 2);
+        assert_eq!(tracker.failures(&key), 2);
+        assert_eq!(tracker.failures(&other), 0);
+
+        tracker.reset(&key);
+        assert_eq!(tracker.failures(&key), 0);
+    }
+
+    #[test]
+    fn test_tracker_delay_within_bounds() {
+        let tracker = BackoffTracker::new(MAX);
+        for _ in 0..20 {
+            let delay = tracker.delay(BASE, 3);
+            assert!(delay >= Duration::from_secs(10));
+            assert!(delay <= Duration::from_secs(20));
+        }
+    }
+}
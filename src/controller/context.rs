@@ -5,15 +5,50 @@
 
 use std::sync::Arc;
 
+use k8s_openapi::api::apps::v1::{Deployment, StatefulSet};
+use k8s_openapi::api::autoscaling::v2::HorizontalPodAutoscaler;
+use k8s_openapi::api::core::v1::{ConfigMap, PersistentVolumeClaim, Secret, Service};
+use k8s_openapi::api::networking::v1::Ingress;
+use k8s_openapi::api::policy::v1::PodDisruptionBudget;
+use kube::core::PartialObjectMeta;
 use kube::runtime::events::{Event, EventType, Recorder, Reporter};
+use kube::runtime::reflector::Store;
 use kube::{Client, Resource};
 
+use crate::controller::backoff::BackoffTracker;
+use crate::controller::hooks::PreDeleteHook;
 use crate::crd::MyResource;
 use crate::health::HealthState;
+use crate::resources::gateway::HTTPRoute;
 
 /// Field manager name for the operator
 pub const FIELD_MANAGER: &str = "my-operator";
 
+/// Reflector caches of the resources owned by MyResources
+#[derive(Clone)]
+pub struct OwnedStores {
+    /// Cached Deployments
+    pub deployments: Store<Deployment>,
+    /// Cached Services
+    pub services: Store<Service>,
+    /// Cached ConfigMaps
+    pub configmaps: Store<ConfigMap>,
+    /// Cached Secret metadata, of both generated and referenced Secrets
+    pub secrets: Store<PartialObjectMeta<Secret>>,
+    /// Cached Ingresses
+    pub ingresses: Store<Ingress>,
+    /// Cached HTTPRoutes, if the Gateway API is installed
+    pub http_routes: Option<Store<HTTPRoute>>,
+    /// Cached PodDisruptionBudgets
+    pub pod_disruption_budgets: Store<PodDisruptionBudget>,
+    /// Cached HorizontalPodAutoscalers
+    pub horizontal_pod_autoscalers: Store<HorizontalPodAutoscaler>,
+    /// Cached StatefulSets
+    pub statefulsets: Store<StatefulSet>,
+    /// Cached PersistentVolumeClaims
+    pub persistent_volume_claims: Store<PersistentVolumeClaim>,
+}
+
 /// Shared context for the controller
 #[derive(Clone)]
 pub struct Context {
@@ -23,6 +58,12 @@ pub struct Context {
     reporter: Reporter,
     /// Optional health state for metrics and readiness
     pub health_state: Option<Arc<HealthState>>,
+    /// Per-object failure tracking for retry backoff
+    pub backoff: Arc<BackoffTracker>,
+    /// Hooks run before owned resources are deleted
+    pub pre_delete_hooks: Vec<Arc<dyn PreDeleteHook>>,
+    /// Caches of owned resources, used for drift detection and readiness checks
+    pub owned_stores: Option<OwnedStores>,
 }
 
 impl Context {
@@ -35,9 +76,24 @@ impl Context {
                 instance: std::env::var("POD_NAME").ok(),
             },
             health_state,
+            backoff: Arc::new(BackoffTracker::from_env()),
+            pre_delete_hooks: Vec::new(),
+            owned_stores: None,
         }
     }
 
+    /// Use reflector caches for owned resources
+    pub fn with_owned_stores(mut self, stores: OwnedStores) -> Self {
+        self.owned_stores = Some(stores);
+        self
+    }
+
+    /// Register a hook to run before owned resources are deleted
+    pub fn with_pre_delete_hook(mut self, hook: Arc<dyn PreDeleteHook>) -> Self {
+        self.pre_delete_hooks.push(hook);
+        self
+    }
+
     /// Create an event recorder for publishing Kubernetes events
     fn recorder(&self) -> Recorder {
         Recorder::new(self.client.clone(), self.reporter.clone())
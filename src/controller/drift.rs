@@ -0,0 +1,289 @@
+//! Drift detection for owned resources.
+//!
+//! Compares the live state of an owned resource (as seen in the reflector cache)
+//! with the state the operator would apply. Only fields the operator sets are
+//! compared, so defaults filled in by the API server or other controllers don't
+//! count as drift.
+//!
+//! Each applied resource carries a hash of its desired state, so a difference
+//! caused by the operator's own change (a new spec or a rotated ConfigMap or
+//! Secret) is told apart from an out-of-band edit.
+
+use kube::{Resource, ResourceExt};
+use serde::Serialize;
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+
+use crate::controller::error::Error;
+use crate::controller::validation::parse_quantity;
+
+/// Metadata fields owned by the operator that are checked for drift
+const COMPARED_METADATA: [&str; 2] = ["labels", "annotations"];
+
+/// Annotation holding a hash of the desired state last applied to an owned
+/// resource
+pub const APPLIED_HASH_ANNOTATION: &str = "myoperator.example.com/applied-hash";
+
+/// Result of comparing a live owned resource with its desired state
+#[derive(Debug, PartialEq)]
+pub enum DriftCheck {
+    /// The live resource matches the desired state
+    UpToDate,
+    /// The desired state changed since it was last applied
+    Changed,
+    /// The desired state is unchanged, but these live fields were edited
+    Drifted(Vec<String>),
+}
+
+/// Record the hash of a desired state on it, for `check_drift` to compare with
+/// once it is applied
+pub fn with_applied_hash<K: Resource + Serialize + Clone>(desired: &K) -> Result<K, Error> {
+    let hash = format!("{:x}", Sha256::digest(serde_json::to_vec(desired)?));
+    let mut desired = desired.clone();
+    desired
+        .annotations_mut()
+        .insert(APPLIED_HASH_ANNOTATION.to_string(), hash);
+    Ok(desired)
+}
+
+/// Compare a live owned resource with a desired state from `with_applied_hash`.
+///
+/// Differences only count as drift if the live resource was last applied from
+/// the same desired state; otherwise they are the operator's own pending change.
+pub fn check_drift<K: Resource + Serialize>(desired: &K, live: &K) -> Result<DriftCheck, Error> {
+    let drifted = detect_drift(desired, live)?;
+    if drifted.is_empty() {
+        return Ok(DriftCheck::UpToDate);
+    }
+    let hash = desired.annotations().get(APPLIED_HASH_ANNOTATION);
+    if hash.is_none() || hash != live.annotations().get(APPLIED_HASH_ANNOTATION) {
+        return Ok(DriftCheck::Changed);
+    }
+    Ok(DriftCheck::Drifted(drifted))
+}
+
+/// Find the field paths where a live resource drifted from its desired state.
+///
+/// Returns an empty list if every field set in `desired` has the same value in
+/// `live`. Paths use dotted notation, e.g. `spec.replicas` or
+/// `spec.template.spec.containers[0].image`.
+pub fn detect_drift<K: Serialize>(desired: &K, live: &K) -> Result<Vec<String>, Error> {
+    let desired = comparable(serde_json::to_value(desired)?);
+    let live = serde_json::to_value(live)?;
+
+    let mut paths = Vec::new();
+    diff_paths(&desired, &live, "", &mut paths);
+    Ok(paths)
+}
+
+/// Strip the parts of a desired object that are not subject to drift detection
+fn comparable(mut value: Value) -> Value {
+    if let Some(object) = value.as_object_mut() {
+        object.remove("apiVersion");
+        object.remove("kind");
+        object.remove("status");
+        if let Some(metadata) = object.get_mut("metadata").and_then(Value::as_object_mut) {
+            metadata.retain(|key, _| COMPARED_METADATA.contains(&key.as_str()));
+        }
+    }
+    value
+}
+
+/// Recursively collect the paths of fields in `desired` that differ in `live`
+fn diff_paths(desired: &Value, live: &Value, path: &str, out: &mut Vec<String>) {
+    match (desired, live) {
+        (Value::Object(desired), Value::Object(live)) => {
+            for (key, desired_value) in desired {
+                let field = field_path(path, key);
+                match live.get(key) {
+                    Some(live_value) => diff_paths(desired_value, live_value, &field, out),
+                    None if desired_value.is_null() => {}
+                    None => out.push(field),
+                }
+            }
+        }
+        (Value::Array(desired), Value::Array(live)) if desired.len() == live.len() => {
+            for (index, (desired_value, live_value)) in desired.iter().zip(live).enumerate() {
+                diff_paths(
+                    desired_value,
+                    live_value,
+                    &format!("{}[{}]", path, index),
+                    out,
+                );
+            }
+        }
+        // The API server normalizes resource quantities (e.g. 0.5 becomes 500m)
+        (Value::String(desired), Value::String(live))
+            if is_quantity_path(path)
+                && parse_quantity(desired).is_some()
+                && parse_quantity(desired) == parse_quantity(live) => {}
+        _ if desired != live => out.push(path.to_string()),
+        _ => {}
+    }
+}
+
+/// Check whether a field path points at a container resource quantity
+fn is_quantity_path(path: &str) -> bool {
+    path.contains("resources.limits") || path.contains("resources.requests")
+}
+
+/// Append a key to a field path, quoting keys that contain dots or slashes
+/// (such as label keys)
+fn field_path(path: &str, key: &str) -> String {
+    if key.contains(['.', '/']) {
+        format!("{}[\"{}\"]", path, key)
+    } else if path.is_empty() {
+        key.to_string()
+    } else {
+        format!("{}.{}", path, key)
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::indexing_slicing)]
+mod tests {
+    use super::*;
+    use crate::crd::{MyResource, MyResourceSpec};
+    use crate::resources::common::{annotate_secret_hash, generate_configmap, generate_deployment};
+    use k8s_openapi::apimachinery::pkg::apis::meta::v1::ObjectMeta;
+
+    fn create_resource() -> MyResource {
+        MyResource {
+            metadata: ObjectMeta {
+                name: Some("test".to_string()),
+                namespace: Some("default".to_string()),
+                uid: Some("uid-1".to_string()),
+                ..Default::default()
+            },
+            spec: MyResourceSpec {
+                replicas: 3,
+                message: "hello".to_string(),
+                ..Default::default()
+            },
+            status: None,
+        }
+    }
+
+    #[test]
+    fn test_no_drift_for_identical_objects() {
+        let deployment = generate_deployment(&create_resource());
+        assert!(detect_drift(&deployment, &deployment).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_server_defaults_are_not_drift() {
+        let desired = generate_deployment(&create_resource());
+        let mut live = desired.clone();
+        live.metadata.resource_version = Some("42".to_string());
+        live.metadata.generation = Some(3);
+        if let Some(spec) = live.spec.as_mut() {
+            spec.revision_history_limit = Some(10);
+            spec.progress_deadline_seconds = Some(600);
+        }
+
+        assert!(detect_drift(&desired, &live).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_detects_changed_fields() {
+        let desired = generate_deployment(&create_resource());
+        let mut live = desired.clone();
+        if let Some(spec) = live.spec.as_mut() {
+            spec.replicas = Some(5);
+            if let Some(pod) = spec.template.spec.as_mut() {
+                for container in &mut pod.containers {
+                    container.image = Some("nginx:latest".to_string());
+                }
+            }
+        }
+
+        assert_eq!(
+            detect_drift(&desired, &live).unwrap(),
+            vec![
+                "spec.replicas".to_string(),
+                "spec.template.spec.containers[0].image".to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_normalized_quantities_are_not_drift() {
+        let mut resource = create_resource();
+        resource.spec.resources = Some(k8s_openapi::api::core::v1::ResourceRequirements {
+            requests: Some(
+                [(
+                    "cpu".to_string(),
+                    k8s_openapi::apimachinery::pkg::api::resource::Quantity("0.5".to_string()),
+                )]
+                .into(),
+            ),
+            ..Default::default()
+        });
+        let desired = generate_deployment(&resource);
+
+        let mut live = serde_json::to_value(&desired).unwrap();
+        live["spec"]["template"]["spec"]["containers"][0]["resources"]["requests"]["cpu"] =
+            serde_json::json!("500m");
+        let live = serde_json::from_value(live).unwrap();
+
+        assert!(detect_drift(&desired, &live).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_check_drift_tells_own_changes_from_edits() {
+        let applied = with_applied_hash(&generate_deployment(&create_resource())).unwrap();
+        assert_eq!(
+            check_drift(&applied, &applied).unwrap(),
+            DriftCheck::UpToDate
+        );
+
+        // A rotated Secret changes the desired pod template annotation
+        let mut rotated = generate_deployment(&create_resource());
+        if let Some(spec) = rotated.spec.as_mut() {
+            annotate_secret_hash(&mut spec.template, "rotated".to_string());
+        }
+        let desired = with_applied_hash(&rotated).unwrap();
+        assert_eq!(
+            check_drift(&desired, &applied).unwrap(),
+            DriftCheck::Changed
+        );
+
+        // An edit of the live resource with an unchanged desired state is drift
+        let mut edited = applied.clone();
+        if let Some(spec) = edited.spec.as_mut() {
+            spec.replicas = Some(5);
+        }
+        assert_eq!(
+            check_drift(&applied, &edited).unwrap(),
+            DriftCheck::Drifted(vec!["spec.replicas".to_string()])
+        );
+
+        // Resources applied before the hash was recorded are simply re-applied
+        let unmarked = generate_deployment(&create_resource());
+        assert_eq!(
+            check_drift(&applied, &unmarked).unwrap(),
+            DriftCheck::Changed
+        );
+    }
+
+    #[test]
+    fn test_detects_removed_label_and_data() {
+        let desired = generate_configmap(&create_resource());
+        let mut live = desired.clone();
+        if let Some(labels) = live.metadata.labels.as_mut() {
+            labels.remove("app.kubernetes.io/managed-by");
+            labels.insert("extra".to_string(), "ignored".to_string());
+        }
+        if let Some(data) = live.data.as_mut() {
+            data.insert("message".to_string(), "edited".to_string());
+        }
+
+        assert_eq!(
+            detect_drift(&desired, &live).unwrap(),
+            vec![
+                "data.message".to_string(),
+                "metadata.labels[\"app.kubernetes.io/managed-by\"]".to_string(),
+            ]
+        );
+    }
+}
@@ -56,9 +56,11 @@ impl Error {
     }
 
     /// Get the recommended requeue duration for this error
+    ///
+    /// For retryable errors this is the base delay; the controller's error policy
+    /// backs it off exponentially per object on consecutive failures.
     pub fn requeue_after(&self) -> Duration {
         if self.is_retryable() {
-            // Exponential backoff would be better, but use a simple default for now
             Duration::from_secs(30)
         } else {
             // Don't requeue for non-retryable errors
@@ -0,0 +1,298 @@
+//! Finalizer management for MyResource.
+//!
+//! Finalizers are added and removed with JSON patches that only touch our own
+//! entry in `metadata.finalizers`, so finalizers added by other controllers
+//! (backup tools, policy engines, ...) on the same object are preserved. Each
+//! patch starts with a `test` on `metadata.resourceVersion`, making it fail
+//! instead of acting on a stale view of the finalizer list.
+
+use kube::api::{Patch, PatchParams};
+use kube::{Api, ResourceExt};
+use serde_json::json;
+
+use crate::controller::error::Error;
+use crate::controller::reconciler::FINALIZER;
+use crate::crd::MyResource;
+
+/// Check whether our finalizer is present on a resource
+pub fn has_finalizer(resource: &MyResource) -> bool {
+    resource.finalizers().iter().any(|f| f == FINALIZER)
+}
+
+/// Build the JSON patch adding our finalizer.
+///
+/// Returns `None` if the finalizer is already present.
+pub fn add_finalizer_patch(resource: &MyResource) -> Result<Option<json_patch::Patch>, Error> {
+    if has_finalizer(resource) {
+        return Ok(None);
+    }
+
+    let mut ops = resource_version_test(resource);
+    if resource.metadata.finalizers.is_some() {
+        ops.push(json!({
+            "op": "add",
+            "path": "/metadata/finalizers/-",
+            "value": FINALIZER,
+        }));
+    } else {
+        ops.push(json!({
+            "op": "add",
+            "path": "/metadata/finalizers",
+            "value": [FINALIZER],
+        }));
+    }
+
+    Ok(Some(serde_json::from_value(json!(ops))?))
+}
+
+/// Build the JSON patch removing our finalizer.
+///
+/// Returns `None` if the finalizer is not present.
+pub fn remove_finalizer_patch(resource: &MyResource) -> Result<Option<json_patch::Patch>, Error> {
+    let Some(index) = resource.finalizers().iter().position(|f| f == FINALIZER) else {
+        return Ok(None);
+    };
+
+    let path = format!("/metadata/finalizers/{}", index);
+    let mut ops = resource_version_test(resource);
+    // Guard the index too, in case the list changed under the same resourceVersion view
+    ops.push(json!({ "op": "test", "path": path, "value": FINALIZER }));
+    ops.push(json!({ "op": "remove", "path": path }));
+
+    Ok(Some(serde_json::from_value(json!(ops))?))
+}
+
+/// Add our finalizer to a resource, preserving any other finalizers
+pub async fn add_finalizer(api: &Api<MyResource>, resource: &MyResource) -> Result<(), Error> {
+    match add_finalizer_patch(resource)? {
+        Some(patch) => apply(api, resource, patch).await,
+        None => Ok(()),
+    }
+}
+
+/// Remove our finalizer from a resource, preserving any other finalizers
+pub async fn remove_finalizer(api: &Api<MyResource>, resource: &MyResource) -> Result<(), Error> {
+    match remove_finalizer_patch(resource)? {
+        Some(patch) => apply(api, resource, patch).await,
+        None => Ok(()),
+    }
+}
+
+/// Leading `test` operation guarding against a stale resourceVersion
+fn resource_version_test(resource: &MyResource) -> Vec<serde_json::Value> {
+    resource
+        .resource_version()
+        .map(|rv| json!({ "op": "test", "path": "/metadata/resourceVersion", "value": rv }))
+        .into_iter()
+        .collect()
+}
+
+/// Send a finalizer patch, mapping a failed `test` into a retryable error
+async fn apply(
+    api: &Api<MyResource>,
+    resource: &MyResource,
+    patch: json_patch::Patch,
+) -> Result<(), Error> {
+    let name = resource.name_any();
+    match api
+        .patch(&name, &PatchParams::default(), &Patch::Json::<()>(patch))
+        .await
+    {
+        Ok(_) => Ok(()),
+        // A failed test op means the object changed since we read it
+        Err(kube::Error::Api(s)) if s.code == 409 || s.code == 422 => Err(Error::Transient(
+            format!("finalizer patch for {} conflicted: {}", name, s.message),
+        )),
+        Err(e) => Err(Error::Kube(e)),
+    }
+}
+
+#[cfg(test)]
+#[allow(
+    clippy::unwrap_used,
+    clippy::expect_used,
+    clippy::indexing_slicing,
+    clippy::panic
+)]
+mod tests {
+    use super::*;
+    use crate::crd::MyResourceSpec;
+    use http::{Request, Response};
+    use k8s_openapi::apimachinery::pkg::apis::meta::v1::ObjectMeta;
+    use kube::client::Body;
+    use serde_json::Value;
+
+    fn create_resource(finalizers: Option<Vec<&str>>) -> MyResource {
+        MyResource {
+            metadata: ObjectMeta {
+                name: Some("test".to_string()),
+                namespace: Some("default".to_string()),
+                resource_version: Some("42".to_string()),
+                finalizers: finalizers.map(|f| f.into_iter().map(String::from).collect()),
+                ..Default::default()
+            },
+            spec: MyResourceSpec::default(),
+            status: None,
+        }
+    }
+
+    fn patch_json(patch: json_patch::Patch) -> Value {
+        serde_json::to_value(patch).unwrap()
+    }
+
+    #[test]
+    fn test_add_patch_without_existing_finalizers() {
+        let resource = create_resource(None);
+        let patch = add_finalizer_patch(&resource).unwrap().unwrap();
+        assert_eq!(
+            patch_json(patch),
+            json!([
+                { "op": "test", "path": "/metadata/resourceVersion", "value": "42" },
+                { "op": "add", "path": "/metadata/finalizers", "value": [FINALIZER] },
+            ])
+        );
+    }
+
+    #[test]
+    fn test_add_patch_appends_to_existing_finalizers() {
+        let resource = create_resource(Some(vec!["backup.example.com/protect"]));
+        let patch = add_finalizer_patch(&resource).unwrap().unwrap();
+        assert_eq!(
+            patch_json(patch),
+            json!([
+                { "op": "test", "path": "/metadata/resourceVersion", "value": "42" },
+                { "op": "add", "path": "/metadata/finalizers/-", "value": FINALIZER },
+            ])
+        );
+    }
+
+    #[test]
+    fn test_add_patch_noop_when_present() {
+        let resource = create_resource(Some(vec![FINALIZER]));
+        assert!(add_finalizer_patch(&resource).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_remove_patch_targets_only_our_finalizer() {
+        let resource = create_resource(Some(vec!["backup.example.com/protect", FINALIZER]));
+        let patch = remove_finalizer_patch(&resource).unwrap().unwrap();
+        assert_eq!(
+            patch_json(patch),
+            json!([
+                { "op": "test", "path": "/metadata/resourceVersion", "value": "42" },
+                { "op": "test", "path": "/metadata/finalizers/1", "value": FINALIZER },
+                { "op": "remove", "path": "/metadata/finalizers/1" },
+            ])
+        );
+    }
+
+    #[test]
+    fn test_remove_patch_noop_when_absent() {
+        let resource = create_resource(Some(vec!["backup.example.com/protect"]));
+        assert!(remove_finalizer_patch(&resource).unwrap().is_none());
+    }
+
+    /// Serve a single request from a mocked API server.
+    ///
+    /// Applies the received JSON patch to `current` and returns the patched object,
+    /// or answers with `status` if given.
+    async fn serve_patch(
+        mut handle: tower_test::mock::Handle<Request<Body>, Response<Body>>,
+        current: MyResource,
+        status: Option<u16>,
+    ) -> Value {
+        let (request, send) = handle.next_request().await.expect("request expected");
+        assert_eq!(request.method(), http::Method::PATCH);
+        assert_eq!(
+            request.uri().path(),
+            "/apis/myoperator.example.com/v1alpha1/namespaces/default/myresources/test"
+        );
+        assert_eq!(
+            request.headers().get("content-type").unwrap(),
+            "application/json-patch+json"
+        );
+        let body = request.into_body().collect_bytes().await.unwrap();
+        let patch: json_patch::Patch = serde_json::from_slice(&body).unwrap();
+
+        if let Some(code) = status {
+            let status = json!({
+                "kind": "Status",
+                "apiVersion": "v1",
+                "status": "Failure",
+                "message": "the server rejected our request due to an error in our request",
+                "reason": "Invalid",
+                "code": code,
+            });
+            let response = Response::builder()
+                .status(code)
+                .body(Body::from(serde_json::to_vec(&status).unwrap()))
+                .unwrap();
+            send.send_response(response);
+            return serde_json::to_value(patch).unwrap();
+        }
+
+        let mut object = serde_json::to_value(&current).unwrap();
+        object["apiVersion"] = json!("myoperator.example.com/v1alpha1");
+        object["kind"] = json!("MyResource");
+        json_patch::patch(&mut object, &patch).unwrap();
+        send.send_response(
+            Response::builder()
+                .body(Body::from(serde_json::to_vec(&object).unwrap()))
+                .unwrap(),
+        );
+        object
+    }
+
+    #[tokio::test]
+    async fn test_add_finalizer_preserves_other_finalizers() {
+        let (service, handle) = tower_test::mock::pair::<Request<Body>, Response<Body>>();
+        let api: Api<MyResource> =
+            Api::namespaced(kube::Client::new(service, "default"), "default");
+        let resource = create_resource(Some(vec!["backup.example.com/protect"]));
+
+        let server = tokio::spawn(serve_patch(handle, resource.clone(), None));
+        add_finalizer(&api, &resource).await.unwrap();
+        let patched = server.await.unwrap();
+
+        assert_eq!(
+            patched["metadata"]["finalizers"],
+            json!(["backup.example.com/protect", FINALIZER])
+        );
+    }
+
+    #[tokio::test]
+    async fn test_remove_finalizer_preserves_other_finalizers() {
+        let (service, handle) = tower_test::mock::pair::<Request<Body>, Response<Body>>();
+        let api: Api<MyResource> =
+            Api::namespaced(kube::Client::new(service, "default"), "default");
+        let resource = create_resource(Some(vec![
+            "backup.example.com/protect",
+            FINALIZER,
+            "policy.example.com/hold",
+        ]));
+
+        let server = tokio::spawn(serve_patch(handle, resource.clone(), None));
+        remove_finalizer(&api, &resource).await.unwrap();
+        let patched = server.await.unwrap();
+
+        assert_eq!(
+            patched["metadata"]["finalizers"],
+            json!(["backup.example.com/protect", "policy.example.com/hold"])
+        );
+    }
+
+    #[tokio::test]
+    async fn test_failed_test_op_is_retryable() {
+        let (service, handle) = tower_test::mock::pair::<Request<Body>, Response<Body>>();
+        let api: Api<MyResource> =
+            Api::namespaced(kube::Client::new(service, "default"), "default");
+        let resource = create_resource(Some(vec![FINALIZER]));
+
+        let server = tokio::spawn(serve_patch(handle, resource.clone(), Some(422)));
+        let err = remove_finalizer(&api, &resource).await.unwrap_err();
+        server.await.unwrap();
+
+        assert!(matches!(err, Error::Transient(_)));
+        assert!(err.is_retryable());
+    }
+}
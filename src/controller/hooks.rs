@@ -0,0 +1,30 @@
+//! Pluggable hooks for MyResource deletion.
+//!
+//! Pre-delete hooks run while a MyResource is in the `Deleting` phase, before
+//! its owned resources are removed and before the finalizer is released. Use
+//! them to clean up anything that owner references can't reach, such as
+//! external systems or resources in other namespaces.
+
+use std::future::Future;
+use std::pin::Pin;
+
+use kube::Client;
+
+use crate::controller::error::Error;
+use crate::crd::MyResource;
+
+/// Boxed future returned by hooks
+pub type HookFuture<'a> = Pin<Box<dyn Future<Output = Result<(), Error>> + Send + 'a>>;
+
+/// A hook run before the owned resources of a MyResource are deleted.
+///
+/// Hooks are run on every cleanup pass until the owned resources are gone, so
+/// they must be idempotent. Returning an error stops the current pass and the
+/// deletion is retried with backoff.
+pub trait PreDeleteHook: Send + Sync {
+    /// Name of the hook, used in logs and events
+    fn name(&self) -> &str;
+
+    /// Run the hook for a resource that is being deleted
+    fn run<'a>(&'a self, resource: &'a MyResource, client: &'a Client) -> HookFuture<'a>;
+}
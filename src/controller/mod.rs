@@ -1,10 +1,14 @@
 //! Controller module for my-operator.
 //!
-//! Contains the reconciliation loop, state machine, error handling, status management,
-//! and validation logic.
+//! Contains the reconciliation loop, state machine, error handling, retry backoff,
+//! drift detection, status management, and validation logic.
 
+pub mod backoff;
 pub mod context;
+pub mod drift;
 pub mod error;
+pub mod finalizer;
+pub mod hooks;
 pub mod reconciler;
 pub mod state_machine;
 pub mod status;
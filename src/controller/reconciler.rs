@@ -6,30 +6,136 @@
 use std::sync::Arc;
 use std::time::Instant;
 
+use k8s_openapi::api::apps::v1::{Deployment, StatefulSet};
+use k8s_openapi::api::autoscaling::v2::HorizontalPodAutoscaler;
+use k8s_openapi::api::core::v1::{PersistentVolumeClaim, Pod, Secret, Service};
+use k8s_openapi::api::networking::v1::Ingress;
+use k8s_openapi::api::policy::v1::PodDisruptionBudget;
+use k8s_openapi::api::storage::v1::StorageClass;
 use kube::{
-    Api, ResourceExt,
-    api::{Patch, PatchParams},
-    runtime::controller::Action,
+    Api, Resource, ResourceExt,
+    api::{DeleteParams, ListParams, Patch, PatchParams},
+    runtime::{
+        controller::Action,
+        reflector::{ObjectRef, Store},
+    },
 };
 use tracing::{debug, error, info, warn};
 
 use crate::{
-    controller::{context::Context, error::Error, state_machine::ResourceStateMachine},
-    crd::{Condition, MyResource, MyResourceStatus, Phase},
-    resources,
+    controller::{
+        backoff::ObjectKey,
+        context::{Context, OwnedStores},
+        drift::{DriftCheck, check_drift, with_applied_hash},
+        error::Error,
+        finalizer::{add_finalizer, has_finalizer, remove_finalizer},
+        state_machine::{
+            ResourceEvent, ResourceStateMachine, TransitionContext, TransitionResult,
+            determine_event,
+        },
+        status::{
+            ConditionBuilder, is_condition_true, probe_failure_message, push_transition,
+            status_changed,
+        },
+        validation::{parse_quantity, validate_spec},
+    },
+    crd::{
+        DriftPolicy, KnownGoodSpec, MyResource, MyResourceStatus, Phase, PhaseTransition,
+        StorageReclaimPolicy, WorkloadType, condition_types,
+    },
+    resources::{
+        self,
+        gateway::{GATEWAY_API_GROUP, HTTPRoute},
+    },
 };
 
 /// Field manager name for server-side apply
 pub const FIELD_MANAGER: &str = "my-operator";
 
+/// Field manager that keeps `.spec.replicas` of a Deployment while ownership
+/// moves from the operator to the HorizontalPodAutoscaler
+pub const REPLICAS_HANDOVER_MANAGER: &str = "my-operator-replicas-handover";
+
 /// Finalizer name for graceful deletion
 pub const FINALIZER: &str = "myoperator.example.com/finalizer";
 
+/// How long deletion waits for owned resources to disappear before giving up and
+/// leaving the rest to owner-reference garbage collection
+pub const DELETION_CLEANUP_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(300);
+
+/// Annotation that forces a recovery attempt for a resource in the Failed phase.
+///
+/// The value is ignored; the operator removes the annotation once recovery starts.
+pub const RETRY_ANNOTATION: &str = "myoperator.example.com/retry";
+
+/// Annotation that allows changing `spec.workloadType` of an existing resource.
+///
+/// Checked by the immutability webhook policy; the value must be `"true"`.
+pub const WORKLOAD_MIGRATION_ANNOTATION: &str = "myoperator.example.com/allow-workload-migration";
+
 /// Reconcile a MyResource
 ///
 /// This is the main reconciliation function called by the controller.
 /// It handles the full lifecycle: creation, updates, and deletion.
 pub async fn reconcile(obj: Arc<MyResource>, ctx: Arc<Context>) -> Result<Action, Error> {
+    let mut retry_count_written = false;
+    let result = reconcile_resource(&obj, &ctx, &mut retry_count_written).await;
+    let key = ObjectKey::from_resource(&obj);
+
+    match result {
+        // A successful reconcile clears any accumulated retry backoff
+        Ok(_) => {
+            if ctx.backoff.failures(&key) > 0 {
+                ctx.backoff.reset(&key);
+                if let Some(ref health_state) = ctx.health_state {
+                    health_state
+                        .metrics
+                        .set_retry_count(&key.namespace, &key.name, 0);
+                }
+            }
+        }
+        // error_policy counts this failure next. The status apply already
+        // carries the count it will reach, unless the error escaped before it
+        Err(ref e) if !e.is_not_found() && !retry_count_written => {
+            let retries = ctx.backoff.failures(&key).saturating_add(1);
+            if let Err(e) = record_retry_count(&obj, &ctx, retries).await {
+                debug!(name = %obj.name_any(), error = %e, "Failed to record retry count");
+            }
+        }
+        Err(_) => {}
+    }
+
+    result
+}
+
+/// Write the consecutive failure count of a failed reconcile to status, for
+/// errors that escaped before the status was applied
+async fn record_retry_count(obj: &MyResource, ctx: &Context, retries: u32) -> Result<(), Error> {
+    let namespace = obj.namespace().unwrap_or_else(|| "default".to_string());
+    let api: Api<MyResource> = Api::namespaced(ctx.client.clone(), &namespace);
+    let patch = serde_json::json!({
+        "status": {
+            "retryCount": i32::try_from(retries).unwrap_or(i32::MAX)
+        }
+    });
+    let params = PatchParams {
+        field_manager: Some(FIELD_MANAGER.to_string()),
+        ..Default::default()
+    };
+    api.patch_status(&obj.name_any(), &params, &Patch::Merge(&patch))
+        .await?;
+    Ok(())
+}
+
+/// Run a single reconciliation pass for a MyResource.
+///
+/// Sets `retry_count_written` once a status carrying the retry count of a
+/// failed pass has been applied.
+async fn reconcile_resource(
+    obj: &MyResource,
+    ctx: &Context,
+    retry_count_written: &mut bool,
+) -> Result<Action, Error> {
     let start_time = Instant::now();
     let name = obj.name_any();
     let namespace = obj.namespace().unwrap_or_else(|| "default".to_string());
@@ -40,13 +146,13 @@ pub async fn reconcile(obj: Arc<MyResource>, ctx: Arc<Context>) -> Result<Action
 
     // Handle deletion
     if obj.metadata.deletion_timestamp.is_some() {
-        return handle_deletion(&obj, &ctx, &namespace).await;
+        return handle_deletion(obj, ctx, &namespace).await;
     }
 
     // Ensure finalizer is present
-    if !obj.finalizers().iter().any(|f| f == FINALIZER) {
+    if !has_finalizer(obj) {
         info!(name = %name, "Adding finalizer");
-        add_finalizer(&api, &name).await?;
+        add_finalizer(&api, obj).await?;
         return Ok(Action::requeue(std::time::Duration::from_secs(1)));
     }
 
@@ -71,154 +177,148 @@ pub async fn reconcile(obj: Arc<MyResource>, ctx: Arc<Context>) -> Result<Action
         );
     }
 
-    // Initialize state machine for transition validation
-    let state_machine = ResourceStateMachine::new();
+    // Validate the spec on every pass so the ConfigurationValid condition stays
+    // current. A resource that already failed only reports it, and doesn't
+    // recover on replica health alone while the spec stays invalid.
+    let validation = validate_spec(obj);
+    let validation_error = validation.as_ref().err().map(ToString::to_string);
 
-    // Determine next phase based on current state
-    let next_phase = match current_phase {
-        Phase::Pending => {
-            // Validate and start creating
-            if let Err(e) = validate_spec(&obj) {
-                error!(name = %name, error = %e, "Validation failed");
-                ctx.publish_warning_event(
-                    &obj,
-                    "ValidationFailed",
-                    "Validating",
-                    Some(e.to_string()),
-                )
-                .await;
-                update_status(&api, &name, Phase::Failed, 0, Some(&e.to_string())).await?;
-                return Err(e);
-            }
-            ctx.publish_normal_event(
-                &obj,
-                "Creating",
-                "CreateResources",
-                Some("Starting resource creation".to_string()),
-            )
-            .await;
-            Phase::Creating
-        }
-        Phase::Creating => {
-            // Create owned resources
-            create_owned_resources(&obj, &ctx, &namespace).await?;
-
-            // Check if resources are ready
-            let ready_replicas = check_ready_replicas(&obj, &ctx, &namespace).await?;
-            if ready_replicas >= obj.spec.replicas {
-                ctx.publish_normal_event(
-                    &obj,
-                    "Ready",
-                    "Reconciling",
-                    Some(format!(
-                        "Resource is ready with {}/{} replicas",
-                        ready_replicas, obj.spec.replicas
-                    )),
-                )
+    // Perform the work associated with the current phase. Errors surfaced here
+    // are fed into the state machine as a ReconcileError rather than bypassing it.
+    let mut reconcile_error = None;
+    match validation {
+        Err(e) if current_phase != Phase::Failed => {
+            error!(name = %name, error = %e, "Validation failed");
+            ctx.publish_warning_event(obj, "ValidationFailed", "Validating", Some(e.to_string()))
                 .await;
-                Phase::Running
-            } else {
-                Phase::Creating
-            }
+            reconcile_error = Some(e);
         }
-        Phase::Running => {
-            // Check if update needed
-            if spec_changed {
-                ctx.publish_normal_event(
-                    &obj,
-                    "SpecChanged",
-                    "Updating",
-                    Some("Resource spec changed, applying updates".to_string()),
-                )
-                .await;
-                Phase::Updating
-            } else {
-                // Ensure resources are in sync
-                create_owned_resources(&obj, &ctx, &namespace).await?;
-
-                let ready_replicas = check_ready_replicas(&obj, &ctx, &namespace).await?;
-                if ready_replicas < obj.spec.replicas {
-                    ctx.publish_warning_event(
-                        &obj,
-                        "Degraded",
-                        "Reconciling",
-                        Some(format!(
-                            "Resource degraded: {}/{} replicas ready",
-                            ready_replicas, obj.spec.replicas
-                        )),
-                    )
-                    .await;
-                    Phase::Degraded
-                } else {
-                    Phase::Running
-                }
+        _ => match current_phase {
+            Phase::Creating | Phase::Updating => {
+                create_owned_resources(obj, ctx, &namespace, false).await?;
             }
-        }
-        Phase::Updating => {
-            // Apply updates
-            create_owned_resources(&obj, &ctx, &namespace).await?;
-
-            let ready_replicas = check_ready_replicas(&obj, &ctx, &namespace).await?;
-            if ready_replicas >= obj.spec.replicas {
-                ctx.publish_normal_event(
-                    &obj,
-                    "Updated",
-                    "Updating",
-                    Some("Resource update completed successfully".to_string()),
-                )
-                .await;
-                Phase::Running
-            } else {
-                Phase::Updating
+            Phase::Running if !spec_changed => {
+                // Ensure resources are in sync, correcting out-of-band changes
+                create_owned_resources(obj, ctx, &namespace, true).await?;
             }
-        }
-        Phase::Degraded => {
-            // Check if recovered
-            let ready_replicas = check_ready_replicas(&obj, &ctx, &namespace).await?;
-            if ready_replicas >= obj.spec.replicas {
-                ctx.publish_normal_event(
-                    &obj,
-                    "Recovered",
-                    "Reconciling",
-                    Some(format!(
-                        "Resource recovered: {}/{} replicas ready",
-                        ready_replicas, obj.spec.replicas
-                    )),
-                )
-                .await;
-                Phase::Running
-            } else if ready_replicas == 0 {
-                ctx.publish_warning_event(
-                    &obj,
-                    "Failed",
-                    "Reconciling",
-                    Some("Resource failed: no replicas available".to_string()),
-                )
-                .await;
-                Phase::Failed
-            } else {
-                Phase::Degraded
+            Phase::Pending | Phase::Running | Phase::Degraded => {}
+            Phase::Failed => {
+                debug!(name = %name, "Resource in Failed state, checking for recovery");
             }
-        }
-        Phase::Failed => {
-            // Manual intervention required
-            warn!(name = %name, "Resource in Failed state, waiting for intervention");
-            Phase::Failed
-        }
-        Phase::Deleting => {
-            // Should be handled by deletion branch above
-            Phase::Deleting
-        }
+            Phase::Deleting => {
+                // Should be handled by deletion branch above
+            }
+        },
+    }
+
+    let replicas = check_replicas(obj, ctx, &namespace).await?;
+    let ready_replicas = replicas.ready;
+    // A running spec only becomes the known-good one once its rollout is seen
+    // to complete, so keep checking until it has been recorded
+    let known_good_recorded = obj
+        .status
+        .as_ref()
+        .and_then(|s| s.last_known_good.as_ref())
+        .is_some_and(|k| Some(k.generation) == current_gen);
+    let rollout = if matches!(
+        current_phase,
+        Phase::Creating | Phase::Updating | Phase::Failed
+    ) || (matches!(current_phase, Phase::Running | Phase::Degraded)
+        && !known_good_recorded)
+    {
+        Some(check_rollout(obj, ctx, &namespace).await?)
+    } else {
+        None
+    };
+    let rollout_complete = rollout.as_ref().is_some_and(|r| r.complete);
+    let stalled_rollout = rollout.and_then(|r| r.stalled);
+
+    // Compute the next phase via the state machine
+    let retry_count = ctx.backoff.failures(&ObjectKey::from_resource(obj));
+    let mut transition_ctx = TransitionContext::new(ready_replicas, replicas.desired)
+        .with_spec_changed(spec_changed)
+        .with_progress_deadline_exceeded(stalled_rollout.is_some())
+        .with_rollout_complete(rollout_complete)
+        .with_spec_valid(validation_error.is_none())
+        .with_rolled_back(
+            obj.status
+                .as_ref()
+                .is_some_and(|s| is_condition_true(&s.conditions, condition_types::ROLLED_BACK)),
+        );
+    transition_ctx.retry_count = i32::try_from(retry_count).unwrap_or(i32::MAX);
+    if let Some(ref e) = reconcile_error {
+        transition_ctx = transition_ctx.with_error(e.to_string());
+    }
+    let retry_requested = obj.annotations().contains_key(RETRY_ANNOTATION);
+    if retry_requested && current_phase == Phase::Failed {
+        info!(name = %name, "Retry requested via {} annotation", RETRY_ANNOTATION);
+        transition_ctx = transition_ctx.with_recovery_requested(true);
+    }
+    let event = determine_event(&current_phase, &transition_ctx, false);
+    let state_machine = ResourceStateMachine::new();
+    let result = state_machine.transition(&current_phase, event, &transition_ctx);
+    let outcome = apply_transition_result(obj, ctx, &transition_ctx, result).await;
+    let next_phase = outcome.phase;
+
+    // Restore the last known-good resources when an update fails
+    let rolled_back_to = if matches!(current_phase, Phase::Creating | Phase::Updating)
+        && next_phase == Phase::Failed
+    {
+        roll_back(obj, ctx, &namespace).await?
+    } else {
+        None
     };
 
-    // Log state transition (state machine available for advanced validation)
-    let _state_machine = state_machine; // Suppress unused warning, available for enhancement
+    // Append successful transitions to the bounded history in status
+    let mut transitions = obj
+        .status
+        .as_ref()
+        .map(|s| s.transitions.clone())
+        .unwrap_or_default();
+    if let Some(record) = outcome.record {
+        push_transition(&mut transitions, record);
+    }
+
+    // Look for probe failures behind a degraded state
+    let probe_failure = if next_phase == Phase::Degraded {
+        check_probe_failures(obj, ctx, &namespace).await
+    } else {
+        None
+    };
 
     // Update status
-    let ready_replicas = check_ready_replicas(&obj, &ctx, &namespace)
-        .await
-        .unwrap_or(0);
-    update_status(&api, &name, next_phase, ready_replicas, None).await?;
+    let status = build_status(
+        obj,
+        StatusUpdate {
+            phase: next_phase,
+            ready_replicas,
+            desired_replicas: replicas.desired,
+            message: outcome.message.as_deref(),
+            validation: Some(validation_error.as_deref().map_or(Ok(()), Err)),
+            probe_failure,
+            stalled_rollout: stalled_rollout.as_deref(),
+            rollout_complete,
+            rolled_back_to,
+            // Counted after the outcome, matching the retry metric
+            retry_count: if reconcile_error.is_some() {
+                transition_ctx.retry_count.saturating_add(1)
+            } else {
+                0
+            },
+            transitions,
+        },
+    );
+    update_status(ctx, &api, obj, status).await?;
+    *retry_count_written = reconcile_error.is_some();
+
+    // Consume the retry annotation so it doesn't trigger another recovery later
+    if retry_requested && next_phase == Phase::Pending {
+        remove_annotation(&api, &name, RETRY_ANNOTATION).await?;
+    }
+
+    if let Some(e) = reconcile_error {
+        return Err(e);
+    }
 
     // Record metrics
     if let Some(ref health_state) = ctx.health_state {
@@ -229,7 +329,7 @@ pub async fn reconcile(obj: Arc<MyResource>, ctx: Arc<Context>) -> Result<Action
         health_state.metrics.set_resource_replicas(
             &namespace,
             &name,
-            i64::from(obj.spec.replicas),
+            i64::from(replicas.desired),
             i64::from(ready_replicas),
         );
     }
@@ -237,7 +337,7 @@ pub async fn reconcile(obj: Arc<MyResource>, ctx: Arc<Context>) -> Result<Action
     // Determine requeue interval based on state
     let requeue_duration = match next_phase {
         Phase::Running => std::time::Duration::from_secs(60),
-        Phase::Creating | Phase::Updating => std::time::Duration::from_secs(10),
+        Phase::Pending | Phase::Creating | Phase::Updating => std::time::Duration::from_secs(10),
         Phase::Degraded => std::time::Duration::from_secs(30),
         Phase::Failed => std::time::Duration::from_secs(300),
         _ => std::time::Duration::from_secs(30),
@@ -246,214 +346,2500 @@ pub async fn reconcile(obj: Arc<MyResource>, ctx: Arc<Context>) -> Result<Action
     Ok(Action::requeue(requeue_duration))
 }
 
-/// Error policy for the controller
-pub fn error_policy(obj: Arc<MyResource>, error: &Error, ctx: Arc<Context>) -> Action {
+/// Outcome of applying a state machine transition result
+struct TransitionOutcome {
+    /// Phase to record in status
+    phase: Phase,
+    /// Optional message for the status condition
+    message: Option<String>,
+    /// History record for a successful transition
+    record: Option<PhaseTransition>,
+}
+
+/// Act on the outcome of a state machine transition.
+///
+/// Successful transitions are announced with an event. Invalid transitions keep
+/// the current phase, and guard failures are reported as a warning event. Every
+/// outcome is counted in the transition metrics.
+async fn apply_transition_result(
+    obj: &MyResource,
+    ctx: &Context,
+    transition_ctx: &TransitionContext,
+    result: TransitionResult,
+) -> TransitionOutcome {
     let name = obj.name_any();
-    let namespace = obj.namespace().unwrap_or_else(|| "default".to_string());
 
-    // Record error metric
+    match result {
+        TransitionResult::Success {
+            from,
+            to,
+            event,
+            description,
+        } => {
+            record_transition(ctx, from, to, &event, "success");
+            info!(name = %name, from = %from, to = %to, event = %event, "{}", description);
+            let note = Some(format!(
+                "{} ({}/{} replicas ready)",
+                description, transition_ctx.ready_replicas, transition_ctx.desired_replicas
+            ));
+            let reason = transition_reason(to);
+            if matches!(to, Phase::Degraded | Phase::Failed) {
+                ctx.publish_warning_event(obj, reason, "Reconciling", note)
+                    .await;
+            } else {
+                ctx.publish_normal_event(obj, reason, "Reconciling", note)
+                    .await;
+            }
+            TransitionOutcome {
+                phase: to,
+                message: transition_ctx.error_message.clone(),
+                record: Some(PhaseTransition::new(
+                    from,
+                    to,
+                    &event.to_string(),
+                    description,
+                    obj.metadata.generation,
+                )),
+            }
+        }
+        TransitionResult::InvalidTransition { current, event } => {
+            // Most invalid transitions are expected: the observed state maps to an
+            // event that has no transition out of the current phase, so we stay put.
+            record_transition(ctx, current, current, &event, "invalid");
+            debug!(name = %name, phase = %current, event = %event, "No transition for event");
+
+            // An error that cannot be recorded as a transition must not disappear silently
+            if let Some(ref message) = transition_ctx.error_message {
+                ctx.publish_warning_event(
+                    obj,
+                    "InvalidTransition",
+                    "Reconciling",
+                    Some(format!(
+                        "Event {} is not valid in phase {}: {}",
+                        event, current, message
+                    )),
+                )
+                .await;
+            }
+            TransitionOutcome {
+                phase: current,
+                message: transition_ctx.error_message.clone(),
+                record: None,
+            }
+        }
+        TransitionResult::GuardFailed {
+            from,
+            to,
+            event,
+            reason,
+        } => {
+            record_transition(ctx, from, to, &event, "guard_failed");
+            warn!(
+                name = %name,
+                from = %from,
+                to = %to,
+                event = %event,
+                reason = %reason,
+                "Transition guard failed"
+            );
+            let message = format!("Transition {} -> {} blocked: {}", from, to, reason);
+            ctx.publish_warning_event(
+                obj,
+                "TransitionGuardFailed",
+                "Reconciling",
+                Some(message.clone()),
+            )
+            .await;
+            TransitionOutcome {
+                phase: from,
+                message: Some(message),
+                record: None,
+            }
+        }
+    }
+}
+
+/// Event reason published when entering a phase
+fn transition_reason(to: Phase) -> &'static str {
+    match to {
+        Phase::Pending => "RecoveryInitiated",
+        Phase::Creating => "Creating",
+        Phase::Running => "Ready",
+        Phase::Updating => "SpecChanged",
+        Phase::Degraded => "Degraded",
+        Phase::Failed => "Failed",
+        Phase::Deleting => "Deleting",
+    }
+}
+
+/// Record a transition attempt in metrics, if enabled
+fn record_transition(ctx: &Context, from: Phase, to: Phase, event: &ResourceEvent, result: &str) {
     if let Some(ref health_state) = ctx.health_state {
-        health_state.metrics.record_error(&namespace, &name);
+        health_state.metrics.record_transition(
+            &from.to_string(),
+            &to.to_string(),
+            &event.to_string(),
+            result,
+        );
     }
+}
+
+/// Error policy for the controller
+///
+/// Retryable errors are requeued with jittered exponential backoff based on the
+/// number of consecutive failures for the object.
+pub fn error_policy(obj: Arc<MyResource>, error: &Error, ctx: Arc<Context>) -> Action {
+    let name = obj.name_any();
+    let namespace = obj.namespace().unwrap_or_else(|| "default".to_string());
+    let key = ObjectKey::from_resource(&obj);
 
     if error.is_not_found() {
+        if let Some(ref health_state) = ctx.health_state {
+            health_state.metrics.record_error(&namespace, &name);
+        }
         debug!(name = %name, "Resource not found (likely deleted)");
+        ctx.backoff.reset(&key);
         return Action::await_change();
     }
 
+    let retries = ctx.backoff.record_failure(&key);
+
+    // Record error metrics
+    if let Some(ref health_state) = ctx.health_state {
+        health_state.metrics.record_error(&namespace, &name);
+        health_state
+            .metrics
+            .set_retry_count(&namespace, &name, i64::from(retries));
+    }
+
     if error.is_retryable() {
-        warn!(name = %name, error = %error, "Retryable error, will retry");
-        Action::requeue(error.requeue_after())
+        let delay = ctx.backoff.delay(error.requeue_after(), retries);
+        warn!(
+            name = %name,
+            error = %error,
+            retries,
+            delay_secs = delay.as_secs_f64(),
+            "Retryable error, will retry"
+        );
+        Action::requeue(delay)
     } else {
         error!(name = %name, error = %error, "Non-retryable error");
         Action::requeue(std::time::Duration::from_secs(300))
     }
 }
 
-/// Validate the resource spec
-fn validate_spec(obj: &MyResource) -> Result<(), Error> {
-    if obj.spec.replicas < 1 {
-        return Err(Error::Validation("replicas must be at least 1".to_string()));
-    }
-    if obj.spec.replicas > 10 {
-        return Err(Error::Validation("replicas cannot exceed 10".to_string()));
-    }
-    Ok(())
-}
-
 /// Handle deletion of a MyResource
+///
+/// Moves the resource to the Deleting phase, runs the pre-delete hooks, deletes
+/// the owned resources with foreground propagation and waits for them to be
+/// gone before removing the finalizer. If cleanup stalls past
+/// `DELETION_CLEANUP_TIMEOUT`, a warning event is published and the finalizer is
+/// removed anyway, leaving the rest to owner-reference garbage collection.
 async fn handle_deletion(
     obj: &MyResource,
     ctx: &Context,
     namespace: &str,
 ) -> Result<Action, Error> {
     let name = obj.name_any();
+    let api: Api<MyResource> = Api::namespaced(ctx.client.clone(), namespace);
+
+    // Nothing to clean up if our finalizer is already gone
+    if !has_finalizer(obj) {
+        return Ok(Action::await_change());
+    }
+
     info!(name = %name, "Handling deletion");
 
-    // Clean up owned resources (they should be garbage collected via owner references,
-    // but we can do explicit cleanup here if needed)
+    // Record the Deleting phase on the first pass, keeping the updated object so
+    // the finalizer patch below is tested against the latest resourceVersion
+    let mut latest = None;
+    let current_phase = obj
+        .status
+        .as_ref()
+        .map(|s| s.phase)
+        .unwrap_or(Phase::Pending);
+    if current_phase != Phase::Deleting {
+        let replicas = check_replicas(obj, ctx, namespace).await?;
+        let ready_replicas = obj.status.as_ref().map_or(0, |s| s.ready_replicas);
+        let transition_ctx = TransitionContext::new(ready_replicas, replicas.desired);
+        let event = determine_event(&current_phase, &transition_ctx, true);
+        let result = ResourceStateMachine::new().transition(&current_phase, event, &transition_ctx);
+        let outcome = apply_transition_result(obj, ctx, &transition_ctx, result).await;
+
+        let mut transitions = obj
+            .status
+            .as_ref()
+            .map(|s| s.transitions.clone())
+            .unwrap_or_default();
+        if let Some(record) = outcome.record {
+            push_transition(&mut transitions, record);
+        }
+        let status = build_status(
+            obj,
+            StatusUpdate {
+                phase: outcome.phase,
+                ready_replicas,
+                desired_replicas: replicas.desired,
+                message: outcome.message.as_deref(),
+                validation: None,
+                probe_failure: None,
+                stalled_rollout: None,
+                rollout_complete: false,
+                rolled_back_to: None,
+                retry_count: obj.status.as_ref().map_or(0, |s| s.retry_count),
+                transitions,
+            },
+        );
+        latest = Some(update_status(ctx, &api, obj, status).await?);
+    }
+
+    // Release a retained claim first: with foreground deletion the garbage
+    // collector removes dependents while our finalizer is still present
+    release_retained_claim(obj, ctx, namespace).await?;
+
+    let timed_out = obj
+        .metadata
+        .deletion_timestamp
+        .as_ref()
+        .and_then(|ts| jiff::Timestamp::now().duration_since(ts.0).try_into().ok())
+        .is_some_and(|elapsed: std::time::Duration| elapsed > DELETION_CLEANUP_TIMEOUT);
+
+    if timed_out {
+        warn!(name = %name, "Timed out waiting for owned resources to be deleted");
+        ctx.publish_warning_event(
+            obj,
+            "CleanupTimedOut",
+            "Deleting",
+            Some(format!(
+                "Owned resources were not deleted within {}s; leaving them to garbage collection",
+                DELETION_CLEANUP_TIMEOUT.as_secs()
+            )),
+        )
+        .await;
+    } else {
+        // Run pre-delete hooks before touching owned resources
+        for hook in &ctx.pre_delete_hooks {
+            debug!(name = %name, hook = hook.name(), "Running pre-delete hook");
+            if let Err(e) = hook.run(obj, &ctx.client).await {
+                ctx.publish_warning_event(
+                    obj,
+                    "PreDeleteHookFailed",
+                    "Deleting",
+                    Some(format!("Pre-delete hook {} failed: {}", hook.name(), e)),
+                )
+                .await;
+                return Err(e);
+            }
+        }
+
+        if !delete_owned_resources(obj, ctx, namespace).await? {
+            debug!(name = %name, "Waiting for owned resources to be deleted");
+            return Ok(Action::requeue(std::time::Duration::from_secs(5)));
+        }
+
+        ctx.publish_normal_event(
+            obj,
+            "Deleted",
+            "Deleting",
+            Some("Owned resources deleted".to_string()),
+        )
+        .await;
+    }
 
     // Remove finalizer
-    let api: Api<MyResource> = Api::namespaced(ctx.client.clone(), namespace);
-    remove_finalizer(&api, &name).await?;
+    remove_finalizer(&api, latest.as_ref().unwrap_or(obj)).await?;
 
     Ok(Action::await_change())
 }
 
-/// Add finalizer to resource
-async fn add_finalizer(api: &Api<MyResource>, name: &str) -> Result<(), Error> {
-    let patch = serde_json::json!({
-        "metadata": {
-            "finalizers": [FINALIZER]
-        }
-    });
-    api.patch(
-        name,
-        &PatchParams::apply(FIELD_MANAGER),
-        &Patch::Merge(&patch),
-    )
-    .await?;
-    Ok(())
+/// Delete the owned resources with foreground propagation.
+///
+/// Optional resources are only deleted if we own them, so a same-named
+/// resource we never created (or a released claim) is left alone.
+///
+/// Returns `true` once all of them are gone.
+async fn delete_owned_resources(
+    obj: &MyResource,
+    ctx: &Context,
+    namespace: &str,
+) -> Result<bool, Error> {
+    let name = &obj.name_any();
+    let deploy_api: Api<Deployment> = Api::namespaced(ctx.client.clone(), namespace);
+    let svc_api: Api<k8s_openapi::api::core::v1::Service> =
+        Api::namespaced(ctx.client.clone(), namespace);
+    let cm_api: Api<k8s_openapi::api::core::v1::ConfigMap> =
+        Api::namespaced(ctx.client.clone(), namespace);
+
+    let sts_api: Api<StatefulSet> = Api::namespaced(ctx.client.clone(), namespace);
+
+    let deployment_gone = delete_owned(&deploy_api, name).await?;
+    let statefulset_gone = delete_owned(&sts_api, name).await?;
+    let service_gone = delete_owned(&svc_api, name).await?;
+    let headless_gone = delete_owned(&svc_api, &format!("{}-headless", name)).await?;
+    let configmap_gone = delete_owned(&cm_api, name).await?;
+
+    let secret_api: Api<Secret> = Api::namespaced(ctx.client.clone(), namespace);
+    let secret_name = resources::common::generated_secret_name(obj);
+    let secret_gone = delete_if_owned_and_wait(obj, &secret_api, &secret_name).await?;
+
+    let ingress_api: Api<Ingress> = Api::namespaced(ctx.client.clone(), namespace);
+    let ingress_gone = delete_if_owned_and_wait(obj, &ingress_api, name).await?;
+    // Without the Gateway API there is no HTTPRoute to delete
+    let route_gone = if ctx
+        .owned_stores
+        .as_ref()
+        .is_some_and(|s| s.http_routes.is_none())
+    {
+        true
+    } else {
+        let route_api: Api<HTTPRoute> = Api::namespaced(ctx.client.clone(), namespace);
+        delete_if_owned_and_wait(obj, &route_api, name).await?
+    };
+
+    let pdb_api: Api<PodDisruptionBudget> = Api::namespaced(ctx.client.clone(), namespace);
+    let pdb_gone = delete_if_owned_and_wait(obj, &pdb_api, name).await?;
+
+    let hpa_api: Api<HorizontalPodAutoscaler> = Api::namespaced(ctx.client.clone(), namespace);
+    let hpa_gone = delete_if_owned_and_wait(obj, &hpa_api, name).await?;
+
+    // A retained claim has already been released, so it is no longer ours to delete
+    let pvc_api: Api<PersistentVolumeClaim> = Api::namespaced(ctx.client.clone(), namespace);
+    let claim_name = resources::common::storage_claim_name(obj);
+    let claim_gone = delete_if_owned_and_wait(obj, &pvc_api, &claim_name).await?;
+
+    Ok(deployment_gone
+        && statefulset_gone
+        && service_gone
+        && headless_gone
+        && configmap_gone
+        && secret_gone
+        && ingress_gone
+        && route_gone
+        && pdb_gone
+        && hpa_gone
+        && claim_gone)
+}
+
+/// Request foreground deletion of an optional resource if the MyResource owns it.
+///
+/// Returns `true` if no owned resource of that name is left.
+async fn delete_if_owned_and_wait<K>(
+    obj: &MyResource,
+    api: &Api<K>,
+    name: &str,
+) -> Result<bool, Error>
+where
+    K: kube::Resource + Clone + serde::de::DeserializeOwned + std::fmt::Debug,
+{
+    match api.get_opt(name).await? {
+        Some(ref existing) if is_owned_by(obj, existing) => delete_owned(api, name).await,
+        _ => Ok(true),
+    }
+}
+
+/// Check whether a resource has an owner reference to the MyResource
+fn is_owned_by<K: Resource>(obj: &MyResource, resource: &K) -> bool {
+    resource
+        .owner_references()
+        .iter()
+        .any(|r| Some(&r.uid) == obj.metadata.uid.as_ref())
+}
+
+/// Request foreground deletion of a single owned resource.
+///
+/// Returns `true` if the resource no longer exists.
+async fn delete_owned<K>(api: &Api<K>, name: &str) -> Result<bool, Error>
+where
+    K: kube::Resource + Clone + serde::de::DeserializeOwned + std::fmt::Debug,
+{
+    match api.delete(name, &DeleteParams::foreground()).await {
+        // Right means the object was deleted immediately, Left that deletion is in progress
+        Ok(result) => Ok(result.is_right()),
+        Err(kube::Error::Api(s)) if s.is_not_found() => Ok(true),
+        Err(e) => Err(Error::Kube(e)),
+    }
 }
 
-/// Remove finalizer from resource
-async fn remove_finalizer(api: &Api<MyResource>, name: &str) -> Result<(), Error> {
+/// Remove an annotation from resource
+async fn remove_annotation(api: &Api<MyResource>, name: &str, key: &str) -> Result<(), Error> {
     let patch = serde_json::json!({
         "metadata": {
-            "finalizers": null
+            "annotations": {
+                key: null
+            }
         }
     });
-    api.patch(
-        name,
-        &PatchParams::apply(FIELD_MANAGER).force(),
-        &Patch::Merge(&patch),
-    )
-    .await?;
+    api.patch(name, &PatchParams::default(), &Patch::Merge(&patch))
+        .await?;
     Ok(())
 }
 
-/// Create owned resources (Deployment, ConfigMap, Service)
+/// Create or update owned resources (ConfigMap, Secret, PersistentVolumeClaim,
+/// Deployment or StatefulSet, Service, Ingress or HTTPRoute, PodDisruptionBudget,
+/// HorizontalPodAutoscaler)
+///
+/// With `check_drift`, each resource is first compared with its cached live
+/// state: resources that are already up to date are not re-applied, and
+/// drifted ones are reported and corrected according to `spec.driftPolicy`.
 async fn create_owned_resources(
     obj: &MyResource,
     ctx: &Context,
     namespace: &str,
+    check_drift: bool,
 ) -> Result<(), Error> {
     let name = obj.name_any();
+    let stores = ctx.owned_stores.as_ref().filter(|_| check_drift);
 
     // Apply ConfigMap
     let configmap = resources::common::generate_configmap(obj);
     let cm_api: Api<k8s_openapi::api::core::v1::ConfigMap> =
         Api::namespaced(ctx.client.clone(), namespace);
-    cm_api
-        .patch(
-            &name,
-            &PatchParams::apply(FIELD_MANAGER).force(),
-            &Patch::Apply(&configmap),
-        )
-        .await?;
+    apply_owned(obj, ctx, &cm_api, &configmap, stores.map(|s| &s.configmaps)).await?;
 
-    // Apply Deployment
-    let deployment = resources::common::generate_deployment(obj);
-    let deploy_api: Api<k8s_openapi::api::apps::v1::Deployment> =
-        Api::namespaced(ctx.client.clone(), namespace);
-    deploy_api
-        .patch(
-            &name,
-            &PatchParams::apply(FIELD_MANAGER).force(),
-            &Patch::Apply(&deployment),
-        )
-        .await?;
+    // Apply the generated Secret (or remove it once it is no longer wanted) and
+    // hash the Secrets used by the pods. While none of them changed since the
+    // hash recorded on the workload, they aren't read again.
+    let secret_hash = match recorded_secret_hash(obj, namespace, stores) {
+        Some(recorded) => Some(recorded),
+        None => {
+            let generated_secret = apply_generated_secret(obj, ctx, namespace, stores).await?;
+            let mut secrets = referenced_secrets(obj, ctx, namespace).await?;
+            secrets.extend(generated_secret);
+            (!secrets.is_empty()).then(|| SecretHash::of(&secrets))
+        }
+    };
+
+    // Apply the Deployment or StatefulSet, hashing the Secrets it uses into the
+    // pod template
+    // The claim has to exist (and be expanded) before the pods mounting it
+    apply_storage(obj, ctx, namespace, stores).await?;
+    apply_workload(obj, ctx, namespace, stores, secret_hash).await?;
 
     // Apply Service
-    let service = resources::common::generate_service(obj);
-    let svc_api: Api<k8s_openapi::api::core::v1::Service> =
-        Api::namespaced(ctx.client.clone(), namespace);
-    svc_api
-        .patch(
-            &name,
-            &PatchParams::apply(FIELD_MANAGER).force(),
-            &Patch::Apply(&service),
-        )
-        .await?;
+    apply_service(obj, ctx, namespace, stores).await?;
+
+    // Apply the Ingress or HTTPRoute, removing any that are no longer wanted
+    apply_routes(obj, ctx, namespace, stores).await?;
+
+    // Apply the PodDisruptionBudget, or remove it when down to one replica
+    let pdb_api: Api<PodDisruptionBudget> = Api::namespaced(ctx.client.clone(), namespace);
+    match resources::common::generate_pdb(obj) {
+        Some(pdb) => {
+            let cache = stores.map(|s| &s.pod_disruption_budgets);
+            apply_owned(obj, ctx, &pdb_api, &pdb, cache).await?
+        }
+        None => {
+            let cache = ctx.owned_stores.as_ref().map(|s| &s.pod_disruption_budgets);
+            delete_if_owned(obj, &pdb_api, cache, &name).await?
+        }
+    }
+
+    // Apply the HorizontalPodAutoscaler, or remove it when autoscaling is off
+    let hpa_api: Api<HorizontalPodAutoscaler> = Api::namespaced(ctx.client.clone(), namespace);
+    match resources::common::generate_hpa(obj) {
+        Some(hpa) => {
+            let cache = stores.map(|s| &s.horizontal_pod_autoscalers);
+            apply_owned(obj, ctx, &hpa_api, &hpa, cache).await?
+        }
+        None => {
+            let cache = ctx
+                .owned_stores
+                .as_ref()
+                .map(|s| &s.horizontal_pod_autoscalers);
+            delete_if_owned(obj, &hpa_api, cache, &name).await?
+        }
+    }
 
     debug!(name = %name, "Applied owned resources");
     Ok(())
 }
 
-/// Check number of ready replicas
-async fn check_ready_replicas(
+/// Server-side apply a single owned resource.
+///
+/// If a cache is given and holds the live resource, it is compared with the
+/// desired state first. Changes of the desired state since the last apply are
+/// always applied; out-of-band edits are reported and only corrected if the
+/// drift policy allows it. Resources missing from the cache are always applied.
+async fn apply_owned<K>(
+    obj: &MyResource,
+    ctx: &Context,
+    api: &Api<K>,
+    desired: &K,
+    cache: Option<&Store<K>>,
+) -> Result<(), Error>
+where
+    K: Resource<DynamicType = ()>
+        + Clone
+        + serde::Serialize
+        + serde::de::DeserializeOwned
+        + std::fmt::Debug,
+{
+    let live = cache.and_then(|store| {
+        store.get(&ObjectRef::new(&desired.name_any()).within(&obj.namespace().unwrap_or_default()))
+    });
+    apply_owned_live(obj, ctx, api, desired, live.as_deref()).await
+}
+
+/// Server-side apply a single owned resource, comparing it with an already
+/// fetched live state like `apply_owned` does with a cached one
+async fn apply_owned_live<K>(
+    obj: &MyResource,
+    ctx: &Context,
+    api: &Api<K>,
+    desired: &K,
+    live: Option<&K>,
+) -> Result<(), Error>
+where
+    K: Resource<DynamicType = ()>
+        + Clone
+        + serde::Serialize
+        + serde::de::DeserializeOwned
+        + std::fmt::Debug,
+{
+    let name = desired.name_any();
+    let desired = with_applied_hash(desired)?;
+
+    if let Some(live) = live {
+        match check_drift(&desired, live)? {
+            DriftCheck::UpToDate => return Ok(()),
+            DriftCheck::Changed => {}
+            DriftCheck::Drifted(fields) => {
+                let correct = obj.spec.drift_policy == DriftPolicy::Correct;
+                report_drift(obj, ctx, &K::kind(&()), &fields, correct).await;
+                if !correct {
+                    return Ok(());
+                }
+            }
+        }
+    }
+
+    api.patch(
+        &name,
+        &PatchParams::apply(FIELD_MANAGER).force(),
+        &Patch::Apply(&desired),
+    )
+    .await?;
+    Ok(())
+}
+
+/// Apply the PersistentVolumeClaim for `spec.storage`, or remove it once
+/// `spec.storage` is gone.
+///
+/// Growing the claim is only attempted if its StorageClass allows volume
+/// expansion; otherwise the resize could never complete, so it fails with a
+/// permanent error. A removed claim is kept, just no longer owned, if it was
+/// created with the `Retain` reclaim policy.
+async fn apply_storage(
+    obj: &MyResource,
+    ctx: &Context,
+    namespace: &str,
+    stores: Option<&OwnedStores>,
+) -> Result<(), Error> {
+    let pvc_api: Api<PersistentVolumeClaim> = Api::namespaced(ctx.client.clone(), namespace);
+    let name = resources::common::storage_claim_name(obj);
+    let cache = ctx
+        .owned_stores
+        .as_ref()
+        .map(|s| &s.persistent_volume_claims);
+    let live = match cache {
+        Some(store) => store
+            .get(&ObjectRef::new(&name).within(namespace))
+            .map(|c| c.as_ref().clone()),
+        None => pvc_api.get_opt(&name).await?,
+    };
+
+    let Some(desired) = resources::common::generate_pvc(obj) else {
+        return match live {
+            Some(ref claim) if is_retained(claim) => release_claim(obj, &pvc_api, claim).await,
+            Some(_) => delete_if_owned(obj, &pvc_api, cache, &name).await,
+            None => Ok(()),
+        };
+    };
+
+    if let Some(ref claim) = live
+        && requested_storage(&desired) > requested_storage(claim)
+    {
+        check_volume_expansion(ctx, claim).await?;
+    }
+
+    let cache = stores.map(|s| &s.persistent_volume_claims);
+    apply_owned(obj, ctx, &pvc_api, &desired, cache).await
+}
+
+/// Requested size of a claim in bytes
+fn requested_storage(claim: &PersistentVolumeClaim) -> Option<f64> {
+    claim
+        .spec
+        .as_ref()
+        .and_then(|s| s.resources.as_ref())
+        .and_then(|r| r.requests.as_ref())
+        .and_then(|r| r.get("storage"))
+        .and_then(|q| parse_quantity(&q.0))
+}
+
+/// Fail permanently if the StorageClass of a bound claim can't expand volumes.
+///
+/// Claims without a class are left to the API server to judge.
+async fn check_volume_expansion(ctx: &Context, claim: &PersistentVolumeClaim) -> Result<(), Error> {
+    let Some(class) = claim
+        .spec
+        .as_ref()
+        .and_then(|s| s.storage_class_name.as_deref())
+    else {
+        return Ok(());
+    };
+
+    let classes: Api<StorageClass> = Api::all(ctx.client.clone());
+    let expandable = classes
+        .get_opt(class)
+        .await?
+        .is_some_and(|c| c.allow_volume_expansion == Some(true));
+    if !expandable {
+        return Err(Error::Permanent(format!(
+            "StorageClass {} does not allow volume expansion, so PersistentVolumeClaim {} can't \
+             grow to the requested spec.storage.size",
+            class,
+            claim.name_any()
+        )));
+    }
+    Ok(())
+}
+
+/// Check whether a claim was created with the `Retain` reclaim policy
+fn is_retained(claim: &PersistentVolumeClaim) -> bool {
+    claim
+        .annotations()
+        .get(resources::common::RECLAIM_POLICY_ANNOTATION)
+        .is_some_and(|p| *p == StorageReclaimPolicy::Retain.to_string())
+}
+
+/// Release the retained claim of a MyResource that is being deleted, so the
+/// garbage collector leaves it alone
+async fn release_retained_claim(
+    obj: &MyResource,
+    ctx: &Context,
+    namespace: &str,
+) -> Result<(), Error> {
+    let pvc_api: Api<PersistentVolumeClaim> = Api::namespaced(ctx.client.clone(), namespace);
+    match pvc_api
+        .get_opt(&resources::common::storage_claim_name(obj))
+        .await?
+    {
+        Some(ref claim) if is_retained(claim) => release_claim(obj, &pvc_api, claim).await,
+        _ => Ok(()),
+    }
+}
+
+/// Drop our owner reference from a claim, keeping any others
+async fn release_claim(
+    obj: &MyResource,
+    api: &Api<PersistentVolumeClaim>,
+    claim: &PersistentVolumeClaim,
+) -> Result<(), Error> {
+    let owners = claim.owner_references();
+    let remaining: Vec<_> = owners
+        .iter()
+        .filter(|r| Some(&r.uid) != obj.metadata.uid.as_ref())
+        .collect();
+    if remaining.len() == owners.len() {
+        return Ok(());
+    }
+
+    info!(name = %obj.name_any(), claim = %claim.name_any(), "Releasing retained PersistentVolumeClaim");
+    let patch = serde_json::json!({ "metadata": { "ownerReferences": remaining } });
+    api.patch(
+        &claim.name_any(),
+        &PatchParams::default(),
+        &Patch::Merge(&patch),
+    )
+    .await?;
+    Ok(())
+}
+
+/// Apply the Deployment or StatefulSet selected by `spec.workloadType`.
+///
+/// After a workload type migration the previous workload keeps serving until
+/// the new one has its replicas ready, and is deleted only then.
+async fn apply_workload(
     obj: &MyResource,
     ctx: &Context,
     namespace: &str,
-) -> Result<i32, Error> {
+    stores: Option<&OwnedStores>,
+    secret_hash: Option<SecretHash>,
+) -> Result<(), Error> {
     let name = obj.name_any();
-    let deploy_api: Api<k8s_openapi::api::apps::v1::Deployment> =
-        Api::namespaced(ctx.client.clone(), namespace);
+    let deploy_api: Api<Deployment> = Api::namespaced(ctx.client.clone(), namespace);
+    let sts_api: Api<StatefulSet> = Api::namespaced(ctx.client.clone(), namespace);
+    let svc_api: Api<Service> = Api::namespaced(ctx.client.clone(), namespace);
+    let all_stores = ctx.owned_stores.as_ref();
+    let headless_name = resources::common::headless_service_name(obj);
+
+    match obj.spec.workload_type {
+        WorkloadType::Deployment => {
+            let mut deployment = resources::common::generate_deployment(obj);
+            if let (Some(secrets), Some(spec)) = (secret_hash, deployment.spec.as_mut()) {
+                resources::common::annotate_secret_hash(&mut spec.template, secrets.hash);
+                resources::common::annotate_secret_versions(
+                    &mut deployment.metadata,
+                    secrets.versions,
+                );
+            }
+            if obj.spec.autoscaling.is_some() {
+                let cache = all_stores.map(|s| &s.deployments);
+                hand_over_replicas(&deploy_api, cache, &name, namespace, |d| {
+                    d.spec.as_ref().and_then(|s| s.replicas)
+                })
+                .await?;
+            }
+            let cache = stores.map(|s| &s.deployments);
+            apply_owned(obj, ctx, &deploy_api, &deployment, cache).await?;
+
+            if check_replicas(obj, ctx, namespace).await?.ready >= obj.spec.min_replicas() {
+                let cache = all_stores.map(|s| &s.statefulsets);
+                delete_if_owned(obj, &sts_api, cache, &name).await?;
+                let cache = all_stores.map(|s| &s.services);
+                delete_if_owned(obj, &svc_api, cache, &headless_name).await?;
+            }
+        }
+        WorkloadType::StatefulSet => {
+            let headless = resources::common::generate_headless_service(obj);
+            let cache = stores.map(|s| &s.services);
+            apply_owned(obj, ctx, &svc_api, &headless, cache).await?;
 
-    match deploy_api.get(&name).await {
-        Ok(deployment) => {
-            let ready = deployment
-                .status
+            let mut statefulset = resources::common::generate_statefulset(obj);
+            if let (Some(secrets), Some(spec)) = (secret_hash, statefulset.spec.as_mut()) {
+                resources::common::annotate_secret_hash(&mut spec.template, secrets.hash);
+                resources::common::annotate_secret_versions(
+                    &mut statefulset.metadata,
+                    secrets.versions,
+                );
+            }
+            if obj.spec.autoscaling.is_some() {
+                let cache = all_stores.map(|s| &s.statefulsets);
+                hand_over_replicas(&sts_api, cache, &name, namespace, |s| {
+                    s.spec.as_ref().and_then(|s| s.replicas)
+                })
+                .await?;
+            }
+            let cache = stores.map(|s| &s.statefulsets);
+            apply_owned(obj, ctx, &sts_api, &statefulset, cache).await?;
+
+            if check_replicas(obj, ctx, namespace).await?.ready >= obj.spec.min_replicas() {
+                let cache = all_stores.map(|s| &s.deployments);
+                delete_if_owned(obj, &deploy_api, cache, &name).await?;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Hand `.spec.replicas` of a workload over before autoscaling takes it.
+///
+/// A field left out of a server-side apply is removed if no other manager owns
+/// it, which would drop the workload to one replica until the HPA scales it
+/// back up. The live count is first applied under `REPLICAS_HANDOVER_MANAGER`,
+/// so it is kept when the operator stops applying it.
+async fn hand_over_replicas<K>(
+    api: &Api<K>,
+    cache: Option<&Store<K>>,
+    name: &str,
+    namespace: &str,
+    replicas: impl Fn(&K) -> Option<i32>,
+) -> Result<(), Error>
+where
+    K: Resource<DynamicType = ()> + Clone + serde::de::DeserializeOwned + std::fmt::Debug,
+{
+    // The cache may lag behind a recent handover, so confirm against the API
+    // server before applying a possibly stale replica count
+    let cached = match cache {
+        Some(store) => store
+            .get(&ObjectRef::new(name).within(namespace))
+            .is_some_and(|live| applies_replicas(live.meta(), FIELD_MANAGER)),
+        None => true,
+    };
+    if !cached {
+        return Ok(());
+    }
+    let Some(live) = api.get_opt(name).await? else {
+        return Ok(());
+    };
+    if !applies_replicas(live.meta(), FIELD_MANAGER) {
+        return Ok(());
+    }
+    let Some(replicas) = replicas(&live) else {
+        return Ok(());
+    };
+
+    info!(name = %name, kind = %K::kind(&()), replicas, "Handing replicas over to the autoscaler");
+    let patch = serde_json::json!({
+        "apiVersion": K::api_version(&()),
+        "kind": K::kind(&()),
+        "metadata": { "name": name },
+        "spec": { "replicas": replicas },
+    });
+    api.patch(
+        name,
+        &PatchParams::apply(REPLICAS_HANDOVER_MANAGER),
+        &Patch::Apply(&patch),
+    )
+    .await?;
+    Ok(())
+}
+
+/// Check whether a field manager applied `.spec.replicas` of a workload
+fn applies_replicas(
+    metadata: &k8s_openapi::apimachinery::pkg::apis::meta::v1::ObjectMeta,
+    manager: &str,
+) -> bool {
+    metadata.managed_fields.iter().flatten().any(|entry| {
+        entry.manager.as_deref() == Some(manager)
+            && entry.operation.as_deref() == Some("Apply")
+            && entry
+                .fields_v1
                 .as_ref()
-                .and_then(|s| s.ready_replicas)
-                .unwrap_or(0);
-            Ok(ready)
+                .and_then(|fields| fields.0.get("f:spec"))
+                .and_then(|spec| spec.get("f:replicas"))
+                .is_some()
+    })
+}
+
+/// Apply the Service, recreating it when an immutable field has to change.
+///
+/// Switching to or from a headless Service changes `clusterIP`, which can't be
+/// patched. Such a Service is deleted first and recreated once it is gone, so
+/// the apply doesn't fail on every reconcile.
+async fn apply_service(
+    obj: &MyResource,
+    ctx: &Context,
+    namespace: &str,
+    stores: Option<&OwnedStores>,
+) -> Result<(), Error> {
+    let service = resources::common::generate_service(obj);
+    let svc_api: Api<Service> = Api::namespaced(ctx.client.clone(), namespace);
+    let name = obj.name_any();
+
+    // The cache may lag behind a recent recreate, so confirm against the API
+    // server before deleting anything
+    let cached = match ctx.owned_stores {
+        Some(ref stores) => stores
+            .services
+            .get(&ObjectRef::new(&name).within(namespace))
+            .is_some_and(|live| resources::common::service_requires_recreate(&service, &live)),
+        None => true,
+    };
+    if cached
+        && let Some(live) = svc_api.get_opt(&name).await?
+        && resources::common::service_requires_recreate(&service, &live)
+    {
+        delete_service_for_recreate(obj, ctx, &svc_api, "clusterIP changed").await?;
+        return apply_owned(obj, ctx, &svc_api, &service, None).await;
+    }
+
+    match apply_owned(obj, ctx, &svc_api, &service, stores.map(|s| &s.services)).await {
+        Err(Error::Kube(kube::Error::Api(s)))
+            if s.code == 422 && s.message.contains("immutable") =>
+        {
+            delete_service_for_recreate(obj, ctx, &svc_api, &s.message).await?;
+            apply_owned(obj, ctx, &svc_api, &service, None).await
         }
-        Err(kube::Error::Api(s)) if s.is_not_found() => Ok(0),
-        Err(e) => Err(Error::Kube(e)),
+        result => result,
     }
 }
 
-/// Update the status of a MyResource
-async fn update_status(
-    api: &Api<MyResource>,
+/// Delete the Service so it can be recreated with new immutable fields.
+///
+/// Returns a transient error if the Service is still being deleted (e.g. while
+/// a load balancer is cleaned up), so the reconcile is retried until it's gone.
+async fn delete_service_for_recreate(
+    obj: &MyResource,
+    ctx: &Context,
+    svc_api: &Api<Service>,
+    reason: &str,
+) -> Result<(), Error> {
+    let name = obj.name_any();
+    info!(name = %name, reason = %reason, "Recreating Service");
+    ctx.publish_normal_event(
+        obj,
+        "ServiceRecreated",
+        "Reconciling",
+        Some(format!(
+            "Service {} is recreated because an immutable field changed: {}",
+            name, reason
+        )),
+    )
+    .await;
+    if delete_owned(svc_api, &name).await? {
+        return Ok(());
+    }
+    Err(Error::Transient(format!(
+        "waiting for Service {} to be deleted before recreating it",
+        name
+    )))
+}
+
+/// Apply the operator-generated Secret.
+///
+/// The live Secret is read from the API server, since only Secret metadata is
+/// cached and generating a fresh password without the existing one would
+/// overwrite it. If `spec.generatedSecret` was removed, a Secret we own is
+/// deleted. Returns the desired Secret, if any, carrying the resourceVersion
+/// of the live Secret it was generated from.
+async fn apply_generated_secret(
+    obj: &MyResource,
+    ctx: &Context,
+    namespace: &str,
+    stores: Option<&OwnedStores>,
+) -> Result<Option<Secret>, Error> {
+    let secret_api: Api<Secret> = Api::namespaced(ctx.client.clone(), namespace);
+    let name = resources::common::generated_secret_name(obj);
+
+    if obj.spec.generated_secret.is_none() {
+        let owned = match ctx.owned_stores {
+            Some(ref stores) => stores
+                .secrets
+                .get(&ObjectRef::new(&name).within(namespace))
+                .is_some_and(|meta| is_owned_by(obj, meta.as_ref())),
+            None => true,
+        };
+        if owned {
+            delete_if_owned(obj, &secret_api, None, &name).await?;
+        }
+        return Ok(None);
+    }
+
+    let existing = secret_api.get_opt(&name).await?;
+    let Some(secret) = resources::common::generate_secret(obj, existing.as_ref()) else {
+        return Ok(None);
+    };
+    let live = stores.and(existing.as_ref());
+    apply_owned_live(obj, ctx, &secret_api, &secret, live).await?;
+    let mut secret = secret;
+    secret.metadata.resource_version = existing.and_then(|e| e.metadata.resource_version);
+    Ok(Some(secret))
+}
+
+/// Hash of the Secrets used by the pods, and the resourceVersions they were
+/// read at
+struct SecretHash {
+    hash: String,
+    versions: String,
+}
+
+impl SecretHash {
+    /// Hash Secrets read from the API server
+    fn of(secrets: &[Secret]) -> Self {
+        let versions: Vec<_> = secrets
+            .iter()
+            .map(|s| {
+                (
+                    s.metadata.name.as_deref().unwrap_or_default(),
+                    s.metadata.resource_version.as_deref().unwrap_or_default(),
+                )
+            })
+            .collect();
+        Self {
+            hash: resources::common::secret_hash(secrets),
+            versions: resources::common::secret_versions(&versions),
+        }
+    }
+}
+
+/// Reuse the Secret hash recorded on the cached workload if none of the
+/// Secrets it was computed from changed since.
+///
+/// The resourceVersions in the Secret metadata cache are compared with the
+/// ones in `SECRET_VERSIONS_ANNOTATION`, so a pass where nothing changed reads
+/// no Secret from the API server. Returns `None` if the Secrets have to be
+/// read, including when nothing is cached.
+fn recorded_secret_hash(
+    obj: &MyResource,
+    namespace: &str,
+    stores: Option<&OwnedStores>,
+) -> Option<SecretHash> {
+    let stores = stores?;
+    let mut names: Vec<String> = obj
+        .spec
+        .secret_refs
+        .iter()
+        .map(|r| r.name.clone())
+        .collect();
+    if obj.spec.generated_secret.is_some() {
+        names.push(resources::common::generated_secret_name(obj));
+    }
+    let cached = names
+        .iter()
+        .map(|name| {
+            let meta = stores
+                .secrets
+                .get(&ObjectRef::new(name).within(namespace))?;
+            Some((name.as_str(), meta.resource_version()?))
+        })
+        .collect::<Option<Vec<_>>>()?;
+    let cached: Vec<_> = cached.iter().map(|(n, v)| (*n, v.as_str())).collect();
+    let versions = resources::common::secret_versions(&cached);
+
+    let name = obj.name_any();
+    let (annotations, template) = match obj.spec.workload_type {
+        WorkloadType::Deployment => {
+            let deployment = stores
+                .deployments
+                .get(&ObjectRef::new(&name).within(namespace))?;
+            let template = deployment.spec.as_ref()?.template.metadata.clone();
+            (deployment.metadata.annotations.clone(), template)
+        }
+        WorkloadType::StatefulSet => {
+            let statefulset = stores
+                .statefulsets
+                .get(&ObjectRef::new(&name).within(namespace))?;
+            let template = statefulset.spec.as_ref()?.template.metadata.clone();
+            (statefulset.metadata.annotations.clone(), template)
+        }
+    };
+    if annotations?.get(resources::common::SECRET_VERSIONS_ANNOTATION) != Some(&versions) {
+        return None;
+    }
+    let hash = template?
+        .annotations?
+        .remove(resources::common::SECRET_HASH_ANNOTATION)?;
+    Some(SecretHash { hash, versions })
+}
+
+/// Apply the Ingress or HTTPRoute requested by `spec.ingress`, and delete the
+/// ones that are no longer requested
+async fn apply_routes(
+    obj: &MyResource,
+    ctx: &Context,
+    namespace: &str,
+    stores: Option<&OwnedStores>,
+) -> Result<(), Error> {
+    let name = obj.name_any();
+    let all_stores = ctx.owned_stores.as_ref();
+
+    let ingress_api: Api<Ingress> = Api::namespaced(ctx.client.clone(), namespace);
+    match resources::common::generate_ingress(obj) {
+        Some(ingress) => {
+            apply_owned(
+                obj,
+                ctx,
+                &ingress_api,
+                &ingress,
+                stores.map(|s| &s.ingresses),
+            )
+            .await?
+        }
+        None => {
+            let cache = all_stores.map(|s| &s.ingresses);
+            delete_if_owned(obj, &ingress_api, cache, &name).await?
+        }
+    }
+
+    let route_api: Api<HTTPRoute> = Api::namespaced(ctx.client.clone(), namespace);
+    let route_cache = stores.and_then(|s| s.http_routes.as_ref());
+    match resources::common::generate_http_route(obj) {
+        Some(route) => match apply_owned(obj, ctx, &route_api, &route, route_cache).await {
+            Err(e) if e.is_not_found() => {
+                return Err(Error::Permanent(format!(
+                    "spec.ingress.kind is HTTPRoute, but the Gateway API ({}) is not installed",
+                    GATEWAY_API_GROUP
+                )));
+            }
+            result => result?,
+        },
+        // Without the Gateway API there is nothing to clean up
+        None if all_stores.is_some_and(|s| s.http_routes.is_none()) => {}
+        None => {
+            let cache = all_stores.and_then(|s| s.http_routes.as_ref());
+            delete_if_owned(obj, &route_api, cache, &name).await?
+        }
+    }
+    Ok(())
+}
+
+/// Delete a resource if it exists and is owned by the MyResource.
+///
+/// Used for optional resources whose spec block was removed. A resource with
+/// the same name that we don't own is left alone.
+async fn delete_if_owned<K>(
+    obj: &MyResource,
+    api: &Api<K>,
+    cache: Option<&Store<K>>,
     name: &str,
+) -> Result<(), Error>
+where
+    K: Resource<DynamicType = ()> + Clone + serde::de::DeserializeOwned + std::fmt::Debug,
+{
+    let existing = match cache {
+        Some(store) => store
+            .get(&ObjectRef::new(name).within(&obj.namespace().unwrap_or_default()))
+            .map(|o| o.as_ref().clone()),
+        None => api.get_opt(name).await?,
+    };
+    if existing.is_some_and(|o| is_owned_by(obj, &o)) {
+        info!(name = %obj.name_any(), kind = %K::kind(&()), resource = %name, "Deleting resource no longer in spec");
+        delete_owned(api, name).await?;
+    }
+    Ok(())
+}
+
+/// Fetch the Secrets listed in `spec.secretRefs` from the API server.
+///
+/// Only Secret metadata is cached, so the contents to hash are read here.
+/// A missing Secret is a transient error: pods can't start without it, and
+/// the Secret watch triggers a new reconcile once it is created.
+async fn referenced_secrets(
+    obj: &MyResource,
+    ctx: &Context,
+    namespace: &str,
+) -> Result<Vec<Secret>, Error> {
+    let secret_api: Api<Secret> = Api::namespaced(ctx.client.clone(), namespace);
+    let mut secrets = Vec::with_capacity(obj.spec.secret_refs.len());
+
+    for secret_ref in &obj.spec.secret_refs {
+        let Some(secret) = secret_api.get_opt(&secret_ref.name).await? else {
+            return Err(Error::Transient(format!(
+                "Secret {} referenced in spec.secretRefs not found",
+                secret_ref.name
+            )));
+        };
+        secrets.push(secret);
+    }
+    Ok(secrets)
+}
+
+/// Report fields of an owned resource that were changed out-of-band
+async fn report_drift(
+    obj: &MyResource,
+    ctx: &Context,
+    kind: &str,
+    fields: &[String],
+    corrected: bool,
+) {
+    let name = obj.name_any();
+    let namespace = obj.namespace().unwrap_or_default();
+    warn!(
+        name = %name,
+        kind = %kind,
+        fields = ?fields,
+        policy = %obj.spec.drift_policy,
+        "Owned resource drifted from desired state"
+    );
+
+    if let Some(ref health_state) = ctx.health_state {
+        for field in fields {
+            health_state
+                .metrics
+                .record_drift(&namespace, &name, kind, field, corrected);
+        }
+    }
+
+    let action = if corrected {
+        "correcting"
+    } else {
+        "not corrected (driftPolicy: ReportOnly)"
+    };
+    ctx.publish_warning_event(
+        obj,
+        "DriftDetected",
+        "Reconciling",
+        Some(format!(
+            "{} {} changed out-of-band at {}; {}",
+            kind,
+            name,
+            fields.join(", "),
+            action
+        )),
+    )
+    .await;
+}
+
+/// Replica counts of the workload
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct WorkloadReplicas {
+    /// Ready replicas
+    ready: i32,
+    /// Replicas the workload should run
+    desired: i32,
+}
+
+/// Check the replica counts of the workload
+///
+/// Reads the Deployment or StatefulSet from the reflector cache when
+/// available, falling back to a live GET otherwise.
+async fn check_replicas(
+    obj: &MyResource,
+    ctx: &Context,
+    namespace: &str,
+) -> Result<WorkloadReplicas, Error> {
+    let name = obj.name_any();
+
+    let replicas = match (obj.spec.workload_type, ctx.owned_stores.as_ref()) {
+        (WorkloadType::Deployment, Some(stores)) => stores
+            .deployments
+            .get(&ObjectRef::new(&name).within(namespace))
+            .map(|d| deployment_replicas(obj, &d)),
+        (WorkloadType::Deployment, None) => {
+            let deploy_api: Api<Deployment> = Api::namespaced(ctx.client.clone(), namespace);
+            deploy_api
+                .get_opt(&name)
+                .await?
+                .map(|d| deployment_replicas(obj, &d))
+        }
+        (WorkloadType::StatefulSet, Some(stores)) => stores
+            .statefulsets
+            .get(&ObjectRef::new(&name).within(namespace))
+            .map(|s| statefulset_replicas(obj, &s)),
+        (WorkloadType::StatefulSet, None) => {
+            let sts_api: Api<StatefulSet> = Api::namespaced(ctx.client.clone(), namespace);
+            sts_api
+                .get_opt(&name)
+                .await?
+                .map(|s| statefulset_replicas(obj, &s))
+        }
+    };
+    Ok(replicas.unwrap_or(WorkloadReplicas {
+        ready: 0,
+        desired: obj.spec.replicas,
+    }))
+}
+
+/// Replicas a workload should run, given the count in its spec.
+///
+/// With autoscaling the HorizontalPodAutoscaler sets the workload's count,
+/// which may be below `spec.replicas`.
+fn desired_replicas(obj: &MyResource, workload_replicas: Option<i32>) -> i32 {
+    match obj.spec.autoscaling {
+        Some(_) => workload_replicas.unwrap_or(obj.spec.replicas),
+        None => obj.spec.replicas,
+    }
+}
+
+/// Check the pods of a MyResource for containers failing their probes.
+///
+/// This is diagnostic only, so listing errors are logged rather than failing
+/// the reconcile.
+async fn check_probe_failures(obj: &MyResource, ctx: &Context, namespace: &str) -> Option<String> {
+    let name = obj.name_any();
+    let pod_api: Api<Pod> = Api::namespaced(ctx.client.clone(), namespace);
+    let params = ListParams::default().labels(&format!("app.kubernetes.io/name={}", name));
+
+    match pod_api.list(&params).await {
+        Ok(pods) => probe_failure_message(&pods.items),
+        Err(e) => {
+            warn!(name = %name, error = %e, "Failed to list pods for probe status");
+            None
+        }
+    }
+}
+
+/// Replica counts of a Deployment
+fn deployment_replicas(obj: &MyResource, deployment: &Deployment) -> WorkloadReplicas {
+    WorkloadReplicas {
+        ready: deployment
+            .status
+            .as_ref()
+            .and_then(|s| s.ready_replicas)
+            .unwrap_or(0),
+        desired: desired_replicas(obj, deployment.spec.as_ref().and_then(|s| s.replicas)),
+    }
+}
+
+/// Re-apply the owned resources of the last known-good spec after a failed
+/// update, if `spec.rollout.autoRollback` is enabled.
+///
+/// The MyResource keeps its failed spec; only the generated resources go back.
+/// Returns the restored generation.
+async fn roll_back(obj: &MyResource, ctx: &Context, namespace: &str) -> Result<Option<i64>, Error> {
+    if !obj.spec.rollout.as_ref().is_some_and(|r| r.auto_rollback) {
+        return Ok(None);
+    }
+    let Some(known_good) = obj
+        .status
+        .as_ref()
+        .and_then(|s| s.last_known_good.as_ref())
+        .filter(|k| Some(k.generation) != obj.metadata.generation)
+    else {
+        return Ok(None);
+    };
+
+    let restored = MyResource {
+        metadata: obj.metadata.clone(),
+        spec: known_good.spec.clone(),
+        status: obj.status.clone(),
+    };
+    create_owned_resources(&restored, ctx, namespace, false).await?;
+
+    warn!(name = %obj.name_any(), generation = known_good.generation, "Rolled back to last known-good spec");
+    ctx.publish_warning_event(
+        obj,
+        "RolledBack",
+        "RollingBack",
+        Some(format!(
+            "Update to generation {} failed; restored the resources of generation {}",
+            obj.metadata.generation.unwrap_or_default(),
+            known_good.generation
+        )),
+    )
+    .await;
+    Ok(Some(known_good.generation))
+}
+
+/// Progress of the workload's current rollout
+#[derive(Debug, PartialEq)]
+struct RolloutProgress {
+    /// Whether every replica runs the current spec and is available
+    complete: bool,
+    /// Message of a Deployment rollout that exceeded its progress deadline
+    stalled: Option<String>,
+}
+
+/// Check how far the workload's current rollout got.
+///
+/// The workload is read from the API server rather than the cache, which right
+/// after a new pod template is applied could still hold the previous rollout's
+/// state. StatefulSets have no progress deadline, so they never stall.
+async fn check_rollout(
+    obj: &MyResource,
+    ctx: &Context,
+    namespace: &str,
+) -> Result<RolloutProgress, Error> {
+    let name = obj.name_any();
+    let progress = match obj.spec.workload_type {
+        WorkloadType::Deployment => {
+            let deploy_api: Api<Deployment> = Api::namespaced(ctx.client.clone(), namespace);
+            deploy_api.get_opt(&name).await?.map(|d| RolloutProgress {
+                complete: deployment_rollout_complete(&d),
+                stalled: stalled_rollout_message(&d),
+            })
+        }
+        WorkloadType::StatefulSet => {
+            let sts_api: Api<StatefulSet> = Api::namespaced(ctx.client.clone(), namespace);
+            sts_api.get_opt(&name).await?.map(|s| RolloutProgress {
+                complete: statefulset_rollout_complete(&s),
+                stalled: None,
+            })
+        }
+    };
+    Ok(progress.unwrap_or(RolloutProgress {
+        complete: false,
+        stalled: None,
+    }))
+}
+
+/// Check whether a Deployment finished rolling out its latest generation:
+/// every replica is updated and available, and no old pods are left
+fn deployment_rollout_complete(deployment: &Deployment) -> bool {
+    let Some(status) = deployment.status.as_ref() else {
+        return false;
+    };
+    let updated = status.updated_replicas.unwrap_or(0);
+    status.observed_generation == deployment.metadata.generation
+        && updated == status.available_replicas.unwrap_or(0)
+        && updated == status.replicas.unwrap_or(0)
+}
+
+/// Check whether a StatefulSet finished rolling out its latest generation:
+/// every replica is updated and runs the update revision
+fn statefulset_rollout_complete(statefulset: &StatefulSet) -> bool {
+    let Some(status) = statefulset.status.as_ref() else {
+        return false;
+    };
+    status.observed_generation == statefulset.metadata.generation
+        && status.updated_replicas.unwrap_or(0) == status.replicas
+        && status.current_revision == status.update_revision
+}
+
+/// Message of a `ProgressDeadlineExceeded` condition on a Deployment.
+///
+/// Only counts once the Deployment controller has observed the latest
+/// generation, so the condition belongs to the current rollout.
+fn stalled_rollout_message(deployment: &Deployment) -> Option<String> {
+    let status = deployment.status.as_ref()?;
+    if status.observed_generation != deployment.metadata.generation {
+        return None;
+    }
+    let condition = status.conditions.iter().flatten().find(|c| {
+        c.type_ == "Progressing"
+            && c.status == "False"
+            && c.reason.as_deref() == Some("ProgressDeadlineExceeded")
+    })?;
+    Some(format!(
+        "Deployment rollout exceeded its progress deadline: {}",
+        condition.message.as_deref().unwrap_or("no progress")
+    ))
+}
+
+/// Replica counts of a StatefulSet
+fn statefulset_replicas(obj: &MyResource, statefulset: &StatefulSet) -> WorkloadReplicas {
+    WorkloadReplicas {
+        ready: statefulset
+            .status
+            .as_ref()
+            .and_then(|s| s.ready_replicas)
+            .unwrap_or(0),
+        desired: desired_replicas(obj, statefulset.spec.as_ref().and_then(|s| s.replicas)),
+    }
+}
+
+/// Observed state from a reconcile pass, used to build the status
+struct StatusUpdate<'a> {
+    /// Phase to record
     phase: Phase,
+    /// Ready replicas of the workload
     ready_replicas: i32,
-    error_message: Option<&str>,
-) -> Result<(), Error> {
-    let generation = api.get(name).await?.metadata.generation;
+    /// Replicas the workload should run
+    desired_replicas: i32,
+    /// Optional message for the status conditions
+    message: Option<&'a str>,
+    /// Spec validation outcome, or `None` if validation was not run
+    validation: Option<Result<(), &'a str>>,
+    /// Summary of containers failing their probes, if any
+    probe_failure: Option<String>,
+    /// Message of a Deployment rollout that exceeded its progress deadline
+    stalled_rollout: Option<&'a str>,
+    /// Whether the workload was seen to finish rolling out the current spec
+    rollout_complete: bool,
+    /// Generation whose resources were restored by an automatic rollback
+    rolled_back_to: Option<i64>,
+    /// Consecutive failed reconciliations
+    retry_count: i32,
+    /// Bounded phase transition history
+    transitions: Vec<PhaseTransition>,
+}
+
+/// Build the desired status of a MyResource from its current status.
+///
+/// Conditions are merged with the existing ones so `lastTransitionTime` only
+/// moves when a condition's status flips.
+fn build_status(obj: &MyResource, update: StatusUpdate<'_>) -> MyResourceStatus {
+    let generation = obj.metadata.generation;
+    let phase = update.phase;
+    let phase_message = format!("Phase: {}", phase);
+    let message = update.message.unwrap_or(&phase_message);
+    let replicas_message = format!(
+        "{}/{} replicas ready",
+        update.ready_replicas, update.desired_replicas
+    );
+
+    let existing = obj
+        .status
+        .as_ref()
+        .map(|s| s.conditions.clone())
+        .unwrap_or_default();
+    let rolled_back = is_condition_true(&existing, condition_types::ROLLED_BACK);
+    let mut conditions = ConditionBuilder::from_conditions(existing);
+
+    match phase {
+        Phase::Running => {
+            conditions
+                .ready(
+                    true,
+                    "AllReplicasReady",
+                    "All replicas are ready",
+                    generation,
+                )
+                .progressing(false, "ReconcileComplete", &replicas_message, generation)
+                .degraded(false, "AllReplicasReady", &replicas_message, generation);
+        }
+        Phase::Degraded => {
+            conditions
+                .ready(false, "ReplicasNotReady", &replicas_message, generation)
+                .progressing(false, "ReconcileComplete", &replicas_message, generation);
+            match update.probe_failure {
+                Some(ref failure) => conditions.degraded(
+                    true,
+                    "ProbeFailed",
+                    &format!("{}; {}", replicas_message, failure),
+                    generation,
+                ),
+                None => {
+                    conditions.degraded(true, "ReplicasNotReady", &replicas_message, generation)
+                }
+            };
+        }
+        Phase::Failed => {
+            let (reason, message) = match (update.message, update.stalled_rollout) {
+                (None, Some(stalled)) => ("ProgressDeadlineExceeded", stalled),
+                (message, _) => ("ReconciliationFailed", message.unwrap_or("Resource failed")),
+            };
+            conditions
+                .ready(false, reason, message, generation)
+                .progressing(false, reason, message, generation)
+                .degraded(false, reason, message, generation);
+        }
+        Phase::Deleting => {
+            conditions
+                .ready(false, "Deleting", message, generation)
+                .progressing(true, "Deleting", message, generation);
+        }
+        Phase::Pending | Phase::Creating | Phase::Updating => {
+            conditions
+                .ready(false, "Reconciling", message, generation)
+                .progressing(true, "Reconciling", message, generation);
+        }
+    }
 
-    let conditions = if phase == Phase::Running {
-        vec![Condition::ready(
+    // Report an automatic rollback until a spec reaches Running again
+    if let Some(restored) = update.rolled_back_to {
+        conditions.rolled_back(
             true,
-            "AllReplicasReady",
-            "All replicas are ready",
+            "UpdateFailed",
+            &format!(
+                "Generation {} failed; restored the resources of generation {}",
+                generation.unwrap_or_default(),
+                restored
+            ),
             generation,
-        )]
-    } else if phase == Phase::Failed {
-        vec![Condition::ready(
+        );
+    } else if rolled_back && phase == Phase::Running {
+        conditions.rolled_back(
             false,
-            "ReconciliationFailed",
-            error_message.unwrap_or("Resource failed"),
-            generation,
-        )]
-    } else {
-        vec![Condition::progressing(
-            true,
-            "Reconciling",
-            &format!("Phase: {}", phase),
+            "UpdateSucceeded",
+            "The current spec is running",
             generation,
-        )]
-    };
+        );
+    }
+
+    match update.validation {
+        Some(Ok(())) => {
+            conditions.configuration_valid(true, "ValidationPassed", "Spec is valid", generation);
+        }
+        Some(Err(e)) => {
+            conditions.configuration_valid(false, "ValidationFailed", e, generation);
+        }
+        None => {}
+    }
 
-    let status = MyResourceStatus {
+    MyResourceStatus {
         phase,
-        ready_replicas,
+        ready_replicas: update.ready_replicas,
         observed_generation: generation,
-        conditions,
-    };
+        conditions: conditions.build(),
+        retry_count: update.retry_count,
+        transitions: update.transitions,
+        selector: Some(resources::common::pod_selector(obj)),
+        last_known_good: match (phase, generation) {
+            (Phase::Running, Some(generation)) if update.rollout_complete => Some(KnownGoodSpec {
+                generation,
+                spec: obj.spec.clone(),
+            }),
+            _ => obj.status.as_ref().and_then(|s| s.last_known_good.clone()),
+        },
+    }
+}
+
+/// Write the status of a MyResource with server-side apply, returning the
+/// updated object.
+///
+/// The write is skipped if the status is semantically unchanged, in which case
+/// the object is returned as is.
+async fn update_status(
+    ctx: &Context,
+    api: &Api<MyResource>,
+    obj: &MyResource,
+    status: MyResourceStatus,
+) -> Result<MyResource, Error> {
+    let changed = status_changed(obj.status.as_ref(), &status);
+    if let Some(ref health_state) = ctx.health_state {
+        health_state.metrics.record_status_write(changed);
+    }
+    if !changed {
+        debug!(name = %obj.name_any(), "Status unchanged, skipping write");
+        return Ok(obj.clone());
+    }
 
     let patch = serde_json::json!({
-        "status": status
+        "apiVersion": MyResource::api_version(&()),
+        "kind": MyResource::kind(&()),
+        "status": status,
     });
 
-    api.patch_status(
-        name,
-        &PatchParams::apply(FIELD_MANAGER),
-        &Patch::Merge(&patch),
-    )
-    .await?;
+    let updated = api
+        .patch_status(
+            &obj.name_any(),
+            &PatchParams::apply(FIELD_MANAGER).force(),
+            &Patch::Apply(&patch),
+        )
+        .await?;
 
-    Ok(())
+    Ok(updated)
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use super::*;
+    use crate::controller::context::OwnedStores;
+    use crate::controller::status::{get_condition_reason, is_condition_true};
+    use crate::crd::{Condition, MyResourceSpec, condition_types};
+    use http::{Request, Response};
+    use k8s_openapi::api::apps::v1::DeploymentStatus;
+    use k8s_openapi::apimachinery::pkg::apis::meta::v1::ObjectMeta;
+    use kube::client::Body;
+    use kube::runtime::reflector::store::Writer;
+    use kube::runtime::watcher;
+
+    fn create_resource(conditions: Vec<Condition>) -> MyResource {
+        MyResource {
+            metadata: ObjectMeta {
+                name: Some("test".to_string()),
+                namespace: Some("default".to_string()),
+                generation: Some(2),
+                ..Default::default()
+            },
+            spec: MyResourceSpec {
+                replicas: 3,
+                ..Default::default()
+            },
+            status: Some(MyResourceStatus {
+                phase: Phase::Running,
+                conditions,
+                ..Default::default()
+            }),
+        }
+    }
+
+    fn update(phase: Phase, ready_replicas: i32) -> StatusUpdate<'static> {
+        StatusUpdate {
+            phase,
+            ready_replicas,
+            desired_replicas: 3,
+            message: None,
+            validation: Some(Ok(())),
+            probe_failure: None,
+            stalled_rollout: None,
+            rollout_complete: false,
+            rolled_back_to: None,
+            retry_count: 0,
+            transitions: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_build_status_keeps_unchanged_transition_times() {
+        let mut ready =
+            Condition::ready(true, "AllReplicasReady", "All replicas are ready", Some(1));
+        ready.last_transition_time = "2024-01-01T00:00:00Z".to_string();
+        let obj = create_resource(vec![ready]);
+
+        let status = build_status(&obj, update(Phase::Running, 3));
+        let ready = status
+            .conditions
+            .iter()
+            .find(|c| c.r#type == condition_types::READY)
+            .unwrap();
+
+        assert_eq!(ready.last_transition_time, "2024-01-01T00:00:00Z");
+        assert_eq!(ready.observed_generation, Some(2));
+        assert_eq!(status.observed_generation, Some(2));
+    }
+
+    #[test]
+    fn test_build_status_is_stable() {
+        let mut obj = create_resource(Vec::new());
+        let first = build_status(&obj, update(Phase::Running, 3));
+        assert!(status_changed(obj.status.as_ref(), &first));
+
+        obj.status = Some(first);
+        let second = build_status(&obj, update(Phase::Running, 3));
+        assert!(!status_changed(obj.status.as_ref(), &second));
+
+        let third = build_status(&obj, update(Phase::Degraded, 2));
+        assert!(status_changed(obj.status.as_ref(), &third));
+    }
+
+    #[test]
+    fn test_build_status_degraded_conditions() {
+        let obj = create_resource(Vec::new());
+        let status = build_status(&obj, update(Phase::Degraded, 1));
+
+        assert!(is_condition_true(
+            &status.conditions,
+            condition_types::DEGRADED
+        ));
+        assert!(!is_condition_true(
+            &status.conditions,
+            condition_types::READY
+        ));
+        assert!(is_condition_true(
+            &status.conditions,
+            condition_types::CONFIGURATION_VALID
+        ));
+    }
+
+    #[test]
+    fn test_build_status_probe_failure_reason() {
+        let obj = create_resource(Vec::new());
+        let status = build_status(
+            &obj,
+            StatusUpdate {
+                probe_failure: Some("Containers failing probes in 1 pod(s): test-abc: main".into()),
+                ..update(Phase::Degraded, 2)
+            },
+        );
+
+        assert!(is_condition_true(
+            &status.conditions,
+            condition_types::DEGRADED
+        ));
+        assert_eq!(
+            get_condition_reason(&status.conditions, condition_types::DEGRADED),
+            Some("ProbeFailed")
+        );
+    }
+
+    #[test]
+    fn test_build_status_validation_failure() {
+        let obj = create_resource(Vec::new());
+        let status = build_status(
+            &obj,
+            StatusUpdate {
+                validation: Some(Err("replicas cannot exceed 10")),
+                ..update(Phase::Failed, 0)
+            },
+        );
+
+        assert!(!is_condition_true(
+            &status.conditions,
+            condition_types::CONFIGURATION_VALID
+        ));
+        assert_eq!(
+            get_condition_reason(&status.conditions, condition_types::CONFIGURATION_VALID),
+            Some("ValidationFailed")
+        );
+    }
+
+    #[test]
+    fn test_build_status_without_validation_keeps_condition() {
+        let valid = Condition::configuration_valid(false, "ValidationFailed", "bad", Some(1));
+        let obj = create_resource(vec![valid]);
+        let status = build_status(
+            &obj,
+            StatusUpdate {
+                validation: None,
+                ..update(Phase::Deleting, 0)
+            },
+        );
+
+        assert_eq!(
+            get_condition_reason(&status.conditions, condition_types::CONFIGURATION_VALID),
+            Some("ValidationFailed")
+        );
+    }
+
+    #[test]
+    fn test_build_status_stalled_rollout_reason() {
+        let obj = create_resource(Vec::new());
+        let status = build_status(
+            &obj,
+            StatusUpdate {
+                stalled_rollout: Some("Deployment rollout exceeded its progress deadline"),
+                ..update(Phase::Failed, 3)
+            },
+        );
+
+        assert_eq!(
+            get_condition_reason(&status.conditions, condition_types::PROGRESSING),
+            Some("ProgressDeadlineExceeded")
+        );
+        assert!(!is_condition_true(
+            &status.conditions,
+            condition_types::PROGRESSING
+        ));
+    }
+
+    #[test]
+    fn test_build_status_records_last_known_good_after_rollout() {
+        let mut obj = create_resource(Vec::new());
+
+        // Running alone isn't enough; the rollout must be seen to complete
+        let status = build_status(&obj, update(Phase::Running, 3));
+        assert!(status.last_known_good.is_none());
+
+        let completed = || StatusUpdate {
+            rollout_complete: true,
+            ..update(Phase::Running, 3)
+        };
+        let status = build_status(&obj, completed());
+        let known_good = status.last_known_good.clone().unwrap();
+        assert_eq!(known_good.generation, 2);
+        assert_eq!(known_good.spec, obj.spec);
+
+        // A newer generation that isn't rolled out yet doesn't replace it
+        obj.status = Some(status);
+        obj.metadata.generation = Some(3);
+        obj.spec.replicas = 5;
+        for phase in [Phase::Updating, Phase::Running] {
+            let status = build_status(&obj, update(phase, 3));
+            assert_eq!(status.last_known_good, Some(known_good.clone()));
+        }
+        let status = build_status(&obj, completed());
+        assert_eq!(status.last_known_good.map(|k| k.generation), Some(3));
+    }
+
+    #[tokio::test]
+    async fn test_stalled_update_rolls_back_to_previous_generation() {
+        use crate::crd::RolloutSpec;
+        use k8s_openapi::api::apps::v1::{DeploymentCondition, DeploymentSpec};
+        use std::sync::Mutex;
+
+        // Generation 1 ran; generation 2 changed the image and stalled while
+        // the old pods kept serving
+        let mut obj = create_resource(Vec::new());
+        obj.metadata.uid = Some("uid-1".to_string());
+        obj.spec.image = "ghcr.io/example/app:1".to_string();
+        obj.spec.rollout = Some(RolloutSpec {
+            auto_rollback: true,
+            ..Default::default()
+        });
+        let previous = obj.spec.clone();
+        obj.spec.image = "ghcr.io/example/app:2".to_string();
+        obj.status = Some(MyResourceStatus {
+            phase: Phase::Updating,
+            last_known_good: Some(KnownGoodSpec {
+                generation: 1,
+                spec: previous,
+            }),
+            ..Default::default()
+        });
+
+        // An update that is still rolling out doesn't become known-good
+        let status = build_status(&obj, update(Phase::Running, 3));
+        assert_eq!(status.last_known_good.map(|k| k.generation), Some(1));
+
+        let stalled = Deployment {
+            metadata: ObjectMeta {
+                name: Some("test".to_string()),
+                namespace: Some("default".to_string()),
+                generation: Some(2),
+                ..Default::default()
+            },
+            spec: Some(DeploymentSpec {
+                replicas: Some(3),
+                ..Default::default()
+            }),
+            status: Some(DeploymentStatus {
+                observed_generation: Some(2),
+                replicas: Some(4),
+                updated_replicas: Some(1),
+                ready_replicas: Some(3),
+                available_replicas: Some(3),
+                conditions: Some(vec![DeploymentCondition {
+                    type_: "Progressing".to_string(),
+                    status: "False".to_string(),
+                    reason: Some("ProgressDeadlineExceeded".to_string()),
+                    ..Default::default()
+                }]),
+                ..Default::default()
+            }),
+        };
+
+        // Serve the stalled Deployment, echo applied objects and answer
+        // everything else with NotFound, recording the applied Deployments
+        let (service, mut handle) = tower_test::mock::pair::<Request<Body>, Response<Body>>();
+        let applied = Arc::new(Mutex::new(Vec::<Deployment>::new()));
+        let recorded = applied.clone();
+        let stalled_body = serde_json::to_vec(&stalled).unwrap();
+        let server = tokio::spawn(async move {
+            while let Some((request, send)) = handle.next_request().await {
+                let method = request.method().clone();
+                let path = request.uri().path().to_string();
+                let body = request.into_body().collect_bytes().await.unwrap();
+                let is_deployment = path.ends_with("/deployments/test");
+                let response = if method == http::Method::GET && is_deployment {
+                    Response::builder().body(Body::from(stalled_body.clone()))
+                } else if method == http::Method::GET {
+                    Response::builder().status(404).body(Body::from(
+                        serde_json::to_vec(&serde_json::json!({
+                            "kind": "Status",
+                            "apiVersion": "v1",
+                            "status": "Failure",
+                            "reason": "NotFound",
+                            "code": 404,
+                        }))
+                        .unwrap(),
+                    ))
+                } else {
+                    if method == http::Method::PATCH && is_deployment {
+                        recorded
+                            .lock()
+                            .unwrap()
+                            .push(serde_json::from_slice(&body).unwrap());
+                    }
+                    Response::builder().body(Body::from(body.to_vec()))
+                };
+                send.send_response(response.unwrap());
+            }
+        });
+        let ctx = Context::new(kube::Client::new(service, "default"), None);
+
+        let rollout = check_rollout(&obj, &ctx, "default").await.unwrap();
+        assert!(!rollout.complete);
+        let transition_ctx = TransitionContext::new(3, 3)
+            .with_rollout_complete(rollout.complete)
+            .with_progress_deadline_exceeded(rollout.stalled.is_some());
+        let event = determine_event(&Phase::Updating, &transition_ctx, false);
+        assert!(matches!(
+            ResourceStateMachine::new().transition(&Phase::Updating, event, &transition_ctx),
+            TransitionResult::Success {
+                to: Phase::Failed,
+                ..
+            }
+        ));
+
+        assert_eq!(roll_back(&obj, &ctx, "default").await.unwrap(), Some(1));
+        drop(ctx);
+        server.await.unwrap();
+
+        let applied = applied.lock().unwrap();
+        let images: Vec<_> = applied
+            .iter()
+            .filter_map(|d| d.spec.as_ref()?.template.spec.as_ref())
+            .filter_map(|pod| pod.containers.first()?.image.clone())
+            .collect();
+        assert_eq!(images, vec!["ghcr.io/example/app:1".to_string()]);
+    }
+
+    #[test]
+    fn test_build_status_rolled_back_condition() {
+        let mut obj = create_resource(Vec::new());
+        let status = build_status(
+            &obj,
+            StatusUpdate {
+                rolled_back_to: Some(1),
+                ..update(Phase::Failed, 3)
+            },
+        );
+        assert!(is_condition_true(
+            &status.conditions,
+            condition_types::ROLLED_BACK
+        ));
+        let message = status
+            .conditions
+            .iter()
+            .find(|c| c.r#type == condition_types::ROLLED_BACK)
+            .map(|c| c.message.clone())
+            .unwrap();
+        assert!(message.contains("restored the resources of generation 1"));
+
+        // Still reported while Failed, cleared once a spec runs again
+        obj.status = Some(status);
+        let status = build_status(&obj, update(Phase::Failed, 3));
+        assert!(is_condition_true(
+            &status.conditions,
+            condition_types::ROLLED_BACK
+        ));
+        obj.status = Some(status);
+        let status = build_status(&obj, update(Phase::Running, 3));
+        assert_eq!(
+            get_condition_reason(&status.conditions, condition_types::ROLLED_BACK),
+            Some("UpdateSucceeded")
+        );
+    }
+
+    #[test]
+    fn test_stalled_rollout_message_needs_current_generation() {
+        use k8s_openapi::api::apps::v1::{DeploymentCondition, DeploymentStatus};
+
+        let deployment = |generation: i64, observed: i64, reason: &str| Deployment {
+            metadata: ObjectMeta {
+                generation: Some(generation),
+                ..Default::default()
+            },
+            status: Some(DeploymentStatus {
+                observed_generation: Some(observed),
+                conditions: Some(vec![DeploymentCondition {
+                    type_: "Progressing".to_string(),
+                    status: if reason == "ProgressDeadlineExceeded" {
+                        "False"
+                    } else {
+                        "True"
+                    }
+                    .to_string(),
+                    reason: Some(reason.to_string()),
+                    message: Some("ReplicaSet \"test-abc\" has timed out progressing.".to_string()),
+                    ..Default::default()
+                }]),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+
+        assert!(
+            stalled_rollout_message(&deployment(2, 2, "ProgressDeadlineExceeded"))
+                .unwrap()
+                .contains("timed out progressing")
+        );
+        // The condition belongs to the previous rollout
+        assert!(stalled_rollout_message(&deployment(3, 2, "ProgressDeadlineExceeded")).is_none());
+        assert!(stalled_rollout_message(&deployment(2, 2, "NewReplicaSetAvailable")).is_none());
+    }
+
+    #[tokio::test]
+    async fn test_rollout_with_ready_old_pods_stays_updating() {
+        use k8s_openapi::api::apps::v1::DeploymentSpec;
+
+        // A 4-replica rollout with 25% surge: the four old pods are ready, the
+        // one new pod isn't
+        let deployment = Deployment {
+            metadata: ObjectMeta {
+                name: Some("test".to_string()),
+                namespace: Some("default".to_string()),
+                generation: Some(3),
+                ..Default::default()
+            },
+            spec: Some(DeploymentSpec {
+                replicas: Some(4),
+                ..Default::default()
+            }),
+            status: Some(DeploymentStatus {
+                observed_generation: Some(3),
+                replicas: Some(5),
+                updated_replicas: Some(1),
+                ready_replicas: Some(4),
+                available_replicas: Some(4),
+                ..Default::default()
+            }),
+        };
+        let (service, mut handle) = tower_test::mock::pair::<Request<Body>, Response<Body>>();
+        let body = serde_json::to_vec(&deployment).unwrap();
+        let server = tokio::spawn(async move {
+            let (request, send) = handle.next_request().await.unwrap();
+            assert_eq!(
+                request.uri().path(),
+                "/apis/apps/v1/namespaces/default/deployments/test"
+            );
+            send.send_response(Response::builder().body(Body::from(body)).unwrap());
+        });
+        let ctx = Context::new(kube::Client::new(service, "default"), None);
+
+        let mut obj = create_resource(Vec::new());
+        obj.spec.replicas = 4;
+        let rollout = check_rollout(&obj, &ctx, "default").await.unwrap();
+        server.await.unwrap();
+        assert_eq!(
+            rollout,
+            RolloutProgress {
+                complete: false,
+                stalled: None
+            }
+        );
+
+        let replicas = deployment_replicas(&obj, &deployment);
+        let transition_ctx = TransitionContext::new(replicas.ready, replicas.desired)
+            .with_rollout_complete(rollout.complete);
+        assert!(transition_ctx.all_replicas_ready());
+        let event = determine_event(&Phase::Updating, &transition_ctx, false);
+        assert!(matches!(
+            ResourceStateMachine::new().transition(&Phase::Updating, event, &transition_ctx),
+            TransitionResult::InvalidTransition { .. }
+        ));
+
+        // Once the new pods replaced the old ones, the rollout is done
+        let mut rolled_out = deployment.clone();
+        rolled_out.status = Some(DeploymentStatus {
+            observed_generation: Some(3),
+            replicas: Some(4),
+            updated_replicas: Some(4),
+            ready_replicas: Some(4),
+            available_replicas: Some(4),
+            ..Default::default()
+        });
+        assert!(deployment_rollout_complete(&rolled_out));
+        // Status of the previous generation
+        rolled_out.metadata.generation = Some(4);
+        assert!(!deployment_rollout_complete(&rolled_out));
+    }
+
+    #[test]
+    fn test_statefulset_rollout_complete() {
+        use k8s_openapi::api::apps::v1::StatefulSetStatus;
+
+        let statefulset = |updated: i32, current: &str| StatefulSet {
+            metadata: ObjectMeta {
+                generation: Some(2),
+                ..Default::default()
+            },
+            status: Some(StatefulSetStatus {
+                observed_generation: Some(2),
+                replicas: 3,
+                updated_replicas: Some(updated),
+                current_revision: Some(current.to_string()),
+                update_revision: Some("test-2".to_string()),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+
+        assert!(statefulset_rollout_complete(&statefulset(3, "test-2")));
+        assert!(!statefulset_rollout_complete(&statefulset(2, "test-1")));
+        assert!(!statefulset_rollout_complete(&statefulset(3, "test-1")));
+    }
+
+    #[test]
+    fn test_applies_replicas_reads_managed_fields() {
+        use k8s_openapi::apimachinery::pkg::apis::meta::v1::{FieldsV1, ManagedFieldsEntry};
+
+        let entry =
+            |manager: &str, operation: &str, fields: serde_json::Value| ManagedFieldsEntry {
+                manager: Some(manager.to_string()),
+                operation: Some(operation.to_string()),
+                fields_v1: Some(FieldsV1(fields)),
+                ..Default::default()
+            };
+        let deployment = |entries: Vec<ManagedFieldsEntry>| Deployment {
+            metadata: ObjectMeta {
+                managed_fields: Some(entries),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        let owned = deployment(vec![entry(
+            FIELD_MANAGER,
+            "Apply",
+            serde_json::json!({ "f:spec": { "f:replicas": {}, "f:template": {} } }),
+        )]);
+        assert!(applies_replicas(&owned.metadata, FIELD_MANAGER));
+
+        // Handed over: the operator applies the template, the HPA updates replicas
+        let handed_over = deployment(vec![
+            entry(
+                FIELD_MANAGER,
+                "Apply",
+                serde_json::json!({ "f:spec": { "f:template": {} } }),
+            ),
+            entry(
+                "kube-controller-manager",
+                "Update",
+                serde_json::json!({ "f:spec": { "f:replicas": {} } }),
+            ),
+        ]);
+        assert!(!applies_replicas(&handed_over.metadata, FIELD_MANAGER));
+        assert!(!applies_replicas(&ObjectMeta::default(), FIELD_MANAGER));
+    }
+
+    #[tokio::test]
+    async fn test_failed_pass_writes_status_once() {
+        use std::sync::Mutex;
+
+        let mut obj = create_resource(Vec::new());
+        obj.metadata.finalizers = Some(vec![FINALIZER.to_string()]);
+        obj.metadata.resource_version = Some("1".to_string());
+        obj.spec.replicas = -1;
+
+        // Nothing owned exists yet; record every status write
+        let (service, mut handle) = tower_test::mock::pair::<Request<Body>, Response<Body>>();
+        let writes = Arc::new(Mutex::new(Vec::<(String, serde_json::Value)>::new()));
+        let recorded = writes.clone();
+        let resource_body = serde_json::to_vec(&obj).unwrap();
+        let server = tokio::spawn(async move {
+            while let Some((request, send)) = handle.next_request().await {
+                let method = request.method().clone();
+                let path = request.uri().path().to_string();
+                let content_type = request
+                    .headers()
+                    .get(http::header::CONTENT_TYPE)
+                    .and_then(|v| v.to_str().ok())
+                    .unwrap_or_default()
+                    .to_string();
+                let body = request.into_body().collect_bytes().await.unwrap();
+                let response = if method == http::Method::PATCH && path.ends_with("/status") {
+                    recorded
+                        .lock()
+                        .unwrap()
+                        .push((content_type, serde_json::from_slice(&body).unwrap()));
+                    Response::builder().body(Body::from(resource_body.clone()))
+                } else if method == http::Method::GET {
+                    Response::builder().status(404).body(Body::from(
+                        serde_json::to_vec(&serde_json::json!({
+                            "kind": "Status",
+                            "apiVersion": "v1",
+                            "status": "Failure",
+                            "reason": "NotFound",
+                            "code": 404,
+                        }))
+                        .unwrap(),
+                    ))
+                } else {
+                    Response::builder().body(Body::from(body.to_vec()))
+                };
+                send.send_response(response.unwrap());
+            }
+        });
+        let ctx = Arc::new(Context::new(kube::Client::new(service, "default"), None));
+
+        let result = reconcile(Arc::new(obj), ctx.clone()).await;
+        assert!(matches!(result, Err(Error::Validation(_))));
+        drop(ctx);
+        server.await.unwrap();
+
+        // The retry count rides on the single status apply
+        let writes = writes.lock().unwrap();
+        assert_eq!(writes.len(), 1);
+        let (content_type, patch) = writes.first().unwrap();
+        assert_eq!(content_type, "application/apply-patch+yaml");
+        assert_eq!(
+            patch.pointer("/status/retryCount"),
+            Some(&serde_json::json!(1))
+        );
+    }
+
+    /// Hook counting its runs, failing while `fail` is set
+    struct TestHook {
+        name: &'static str,
+        runs: std::sync::atomic::AtomicUsize,
+        fail: std::sync::atomic::AtomicBool,
+    }
+
+    impl TestHook {
+        fn new(name: &'static str, fail: bool) -> Arc<Self> {
+            Arc::new(Self {
+                name,
+                runs: Default::default(),
+                fail: std::sync::atomic::AtomicBool::new(fail),
+            })
+        }
+
+        fn runs(&self) -> usize {
+            self.runs.load(std::sync::atomic::Ordering::SeqCst)
+        }
+    }
+
+    impl crate::controller::hooks::PreDeleteHook for TestHook {
+        fn name(&self) -> &str {
+            self.name
+        }
+
+        fn run<'a>(
+            &'a self,
+            _resource: &'a MyResource,
+            _client: &'a kube::Client,
+        ) -> crate::controller::hooks::HookFuture<'a> {
+            Box::pin(async move {
+                self.runs.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+                if self.fail.load(std::sync::atomic::Ordering::SeqCst) {
+                    Err(Error::Transient("external system unavailable".to_string()))
+                } else {
+                    Ok(())
+                }
+            })
+        }
+    }
+
+    #[tokio::test]
+    async fn test_failing_pre_delete_hook_holds_finalizer() {
+        use std::sync::Mutex;
+
+        let mut obj = create_resource(Vec::new());
+        obj.metadata.uid = Some("uid-1".to_string());
+        obj.metadata.finalizers = Some(vec![FINALIZER.to_string()]);
+        obj.metadata.deletion_timestamp = Some(
+            k8s_openapi::apimachinery::pkg::apis::meta::v1::Time(jiff::Timestamp::now()),
+        );
+        if let Some(status) = obj.status.as_mut() {
+            status.phase = Phase::Deleting;
+        }
+
+        // Nothing else exists; record the finalizer patches and answer them
+        // with the resource
+        let (service, mut handle) = tower_test::mock::pair::<Request<Body>, Response<Body>>();
+        let patched = Arc::new(Mutex::new(Vec::<String>::new()));
+        let recorded = patched.clone();
+        let resource_body = serde_json::to_vec(&obj).unwrap();
+        let server = tokio::spawn(async move {
+            while let Some((request, send)) = handle.next_request().await {
+                let method = request.method().clone();
+                let path = request.uri().path().to_string();
+                let body = request.into_body().collect_bytes().await.unwrap();
+                let response = if method == http::Method::PATCH && path.ends_with("/test") {
+                    recorded.lock().unwrap().push(path);
+                    Response::builder().body(Body::from(resource_body.clone()))
+                } else if matches!(method, http::Method::GET | http::Method::DELETE) {
+                    Response::builder().status(404).body(Body::from(
+                        serde_json::to_vec(&serde_json::json!({
+                            "kind": "Status",
+                            "apiVersion": "v1",
+                            "status": "Failure",
+                            "reason": "NotFound",
+                            "code": 404,
+                        }))
+                        .unwrap(),
+                    ))
+                } else {
+                    Response::builder().body(Body::from(body.to_vec()))
+                };
+                send.send_response(response.unwrap());
+            }
+        });
+
+        let cleanup = TestHook::new("cleanup", false);
+        let external = TestHook::new("external", true);
+        let ctx = Context::new(kube::Client::new(service, "default"), None)
+            .with_pre_delete_hook(cleanup.clone())
+            .with_pre_delete_hook(external.clone());
+
+        // The finalizer is held for as long as a hook keeps failing
+        for pass in 1..=2 {
+            assert!(handle_deletion(&obj, &ctx, "default").await.is_err());
+            assert_eq!(cleanup.runs(), pass);
+            assert_eq!(external.runs(), pass);
+            assert!(patched.lock().unwrap().is_empty());
+        }
+
+        // Once every hook succeeds the owned resources are deleted and the
+        // finalizer is released
+        external
+            .fail
+            .store(false, std::sync::atomic::Ordering::SeqCst);
+        handle_deletion(&obj, &ctx, "default").await.unwrap();
+        assert_eq!(cleanup.runs(), 3);
+        assert_eq!(external.runs(), 3);
+        drop(ctx);
+        server.await.unwrap();
+
+        assert_eq!(
+            *patched.lock().unwrap(),
+            vec!["/apis/myoperator.example.com/v1alpha1/namespaces/default/myresources/test"]
+        );
+    }
+
+    #[test]
+    fn test_recorded_secret_hash_reused_until_a_secret_changes() {
+        let mut obj = create_resource(Vec::new());
+        obj.spec.secret_refs = vec![crate::crd::SecretRef {
+            name: "db".to_string(),
+            projection: Default::default(),
+            keys: Vec::new(),
+        }];
+
+        let secret = |version: &str| Secret {
+            metadata: ObjectMeta {
+                name: Some("db".to_string()),
+                namespace: Some("default".to_string()),
+                resource_version: Some(version.to_string()),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let recorded = SecretHash::of(&[secret("7")]);
+        let mut deployment = resources::common::generate_deployment(&obj);
+        if let Some(spec) = deployment.spec.as_mut() {
+            resources::common::annotate_secret_hash(&mut spec.template, recorded.hash.clone());
+        }
+        resources::common::annotate_secret_versions(&mut deployment.metadata, recorded.versions);
+        let mut deployments = Writer::<Deployment>::default();
+        deployments.apply_watcher_event(&watcher::Event::Apply(deployment));
+
+        let stores = |version: &str| {
+            let mut secrets = Writer::<kube::core::PartialObjectMeta<Secret>>::default();
+            secrets.apply_watcher_event(&watcher::Event::Apply(kube::core::PartialObjectMeta {
+                metadata: secret(version).metadata,
+                ..Default::default()
+            }));
+            OwnedStores {
+                deployments: deployments.as_reader(),
+                services: Writer::default().as_reader(),
+                configmaps: Writer::default().as_reader(),
+                secrets: secrets.as_reader(),
+                ingresses: Writer::default().as_reader(),
+                http_routes: None,
+                pod_disruption_budgets: Writer::default().as_reader(),
+                horizontal_pod_autoscalers: Writer::default().as_reader(),
+                statefulsets: Writer::default().as_reader(),
+                persistent_volume_claims: Writer::default().as_reader(),
+            }
+        };
+
+        let reused = recorded_secret_hash(&obj, "default", Some(&stores("7"))).unwrap();
+        assert_eq!(reused.hash, recorded.hash);
+        assert!(recorded_secret_hash(&obj, "default", Some(&stores("8"))).is_none());
+        assert!(recorded_secret_hash(&obj, "default", None).is_none());
+    }
+
+    #[tokio::test]
+    async fn test_ready_replicas_read_from_cache() {
+        // Dropping the handle makes any API request fail, so this only passes
+        // if the readiness check is served from the cache
+        let (service, handle) = tower_test::mock::pair::<Request<Body>, Response<Body>>();
+        drop(handle);
+        let client = kube::Client::new(service, "default");
+
+        let mut deployments = Writer::<Deployment>::default();
+        deployments.apply_watcher_event(&watcher::Event::Apply(Deployment {
+            metadata: ObjectMeta {
+                name: Some("test".to_string()),
+                namespace: Some("default".to_string()),
+                ..Default::default()
+            },
+            status: Some(DeploymentStatus {
+                ready_replicas: Some(2),
+                ..Default::default()
+            }),
+            ..Default::default()
+        }));
+        let ctx = Context::new(client, None).with_owned_stores(OwnedStores {
+            deployments: deployments.as_reader(),
+            services: Writer::default().as_reader(),
+            configmaps: Writer::default().as_reader(),
+            secrets: Writer::default().as_reader(),
+            ingresses: Writer::default().as_reader(),
+            http_routes: None,
+            pod_disruption_budgets: Writer::default().as_reader(),
+            horizontal_pod_autoscalers: Writer::default().as_reader(),
+            statefulsets: Writer::default().as_reader(),
+            persistent_volume_claims: Writer::default().as_reader(),
+        });
+
+        let obj = create_resource(Vec::new());
+        assert_eq!(
+            check_replicas(&obj, &ctx, "default").await.unwrap().ready,
+            2
+        );
+
+        let mut missing = obj.clone();
+        missing.metadata.name = Some("other".to_string());
+        assert_eq!(
+            check_replicas(&missing, &ctx, "default")
+                .await
+                .unwrap()
+                .ready,
+            0
+        );
+    }
+
+    #[test]
+    fn test_desired_replicas_follow_autoscaler() {
+        use crate::crd::AutoscalingSpec;
+        use k8s_openapi::api::apps::v1::DeploymentSpec;
+
+        // The HPA scaled the Deployment below spec.replicas
+        let deployment = Deployment {
+            spec: Some(DeploymentSpec {
+                replicas: Some(2),
+                ..Default::default()
+            }),
+            status: Some(DeploymentStatus {
+                ready_replicas: Some(2),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+        let mut obj = create_resource(Vec::new());
+        assert_eq!(
+            deployment_replicas(&obj, &deployment),
+            WorkloadReplicas {
+                ready: 2,
+                desired: 3
+            }
+        );
+
+        obj.spec.autoscaling = Some(AutoscalingSpec {
+            min_replicas: Some(2),
+            max_replicas: 6,
+            ..Default::default()
+        });
+        let replicas = deployment_replicas(&obj, &deployment);
+        assert_eq!(replicas.desired, 2);
+        assert!(TransitionContext::new(replicas.ready, replicas.desired).all_replicas_ready());
+    }
 }
@@ -27,6 +27,8 @@ pub enum ResourceEvent {
     RecoveryInitiated,
     /// Resource has fully recovered from degraded state
     FullyRecovered,
+    /// The Deployment rollout made no progress within its deadline
+    ProgressDeadlineExceeded,
 }
 
 impl fmt::Display for ResourceEvent {
@@ -40,6 +42,7 @@ impl fmt::Display for ResourceEvent {
             ResourceEvent::DeletionRequested => write!(f, "DeletionRequested"),
             ResourceEvent::RecoveryInitiated => write!(f, "RecoveryInitiated"),
             ResourceEvent::FullyRecovered => write!(f, "FullyRecovered"),
+            ResourceEvent::ProgressDeadlineExceeded => write!(f, "ProgressDeadlineExceeded"),
         }
     }
 }
@@ -57,6 +60,16 @@ pub struct TransitionContext {
     pub error_message: Option<String>,
     /// Current retry count for backoff
     pub retry_count: i32,
+    /// Whether a recovery attempt was explicitly requested
+    pub recovery_requested: bool,
+    /// Whether the Deployment reported that its rollout stalled
+    pub progress_deadline_exceeded: bool,
+    /// Whether the workload finished rolling out the current spec
+    pub rollout_complete: bool,
+    /// Whether a failed update was rolled back to the last known-good spec
+    pub rolled_back: bool,
+    /// Whether the current spec passes validation
+    pub spec_valid: bool,
 }
 
 impl TransitionContext {
@@ -68,6 +81,11 @@ impl TransitionContext {
             spec_changed: false,
             error_message: None,
             retry_count: 0,
+            recovery_requested: false,
+            progress_deadline_exceeded: false,
+            rollout_complete: true,
+            rolled_back: false,
+            spec_valid: true,
         }
     }
 
@@ -97,6 +115,36 @@ impl TransitionContext {
         self.error_message = Some(message);
         self
     }
+
+    /// Set recovery_requested flag
+    pub fn with_recovery_requested(mut self, requested: bool) -> Self {
+        self.recovery_requested = requested;
+        self
+    }
+
+    /// Set progress_deadline_exceeded flag
+    pub fn with_progress_deadline_exceeded(mut self, exceeded: bool) -> Self {
+        self.progress_deadline_exceeded = exceeded;
+        self
+    }
+
+    /// Set rollout_complete flag
+    pub fn with_rollout_complete(mut self, complete: bool) -> Self {
+        self.rollout_complete = complete;
+        self
+    }
+
+    /// Set rolled_back flag
+    pub fn with_rolled_back(mut self, rolled_back: bool) -> Self {
+        self.rolled_back = rolled_back;
+        self
+    }
+
+    /// Set spec_valid flag
+    pub fn with_spec_valid(mut self, valid: bool) -> Self {
+        self.spec_valid = valid;
+        self
+    }
 }
 
 /// A state transition definition with optional guard
@@ -202,6 +250,12 @@ impl ResourceStateMachine {
                     ResourceEvent::ReconcileError,
                     "Error during resource creation",
                 ),
+                Transition::new(
+                    Phase::Creating,
+                    Phase::Failed,
+                    ResourceEvent::ProgressDeadlineExceeded,
+                    "Initial rollout exceeded its progress deadline",
+                ),
                 Transition::new(
                     Phase::Creating,
                     Phase::Deleting,
@@ -252,6 +306,12 @@ impl ResourceStateMachine {
                     ResourceEvent::ReconcileError,
                     "Error during update",
                 ),
+                Transition::new(
+                    Phase::Updating,
+                    Phase::Failed,
+                    ResourceEvent::ProgressDeadlineExceeded,
+                    "Rollout exceeded its progress deadline",
+                ),
                 Transition::new(
                     Phase::Updating,
                     Phase::Deleting,
@@ -416,11 +476,57 @@ pub fn determine_event(
         return ResourceEvent::DeletionRequested;
     }
 
+    // Errors reported by the reconciler move the resource towards Failed
+    if ctx.error_message.is_some() {
+        return ResourceEvent::ReconcileError;
+    }
+
+    // Pending resources always start by applying their owned resources,
+    // regardless of any replicas left over from a previous incarnation
+    if *current_phase == Phase::Pending {
+        return ResourceEvent::ResourcesApplied;
+    }
+
+    // A failed resource is retried when its spec is fixed or a retry is requested
+    if *current_phase == Phase::Failed && (ctx.spec_changed || ctx.recovery_requested) {
+        return ResourceEvent::RecoveryInitiated;
+    }
+
+    // After a rollback the old pods are healthy again, but recovering would
+    // re-apply the spec that failed; stay Failed until the spec is fixed
+    if *current_phase == Phase::Failed && ctx.rolled_back {
+        return ResourceEvent::ReconcileError;
+    }
+
+    // Healthy pods of an earlier spec don't make an invalid spec recoverable;
+    // recovering would only fail validation again from Pending
+    if *current_phase == Phase::Failed && !ctx.spec_valid {
+        return ResourceEvent::ReconcileError;
+    }
+
     // Check for spec change
     if ctx.spec_changed && matches!(current_phase, Phase::Running | Phase::Degraded) {
         return ResourceEvent::SpecChanged;
     }
 
+    // A stalled rollout won't finish on its own, however many replicas the
+    // old pods still provide, so it also keeps a Failed resource from
+    // recovering until the spec changes or a retry is requested
+    if ctx.progress_deadline_exceeded
+        && matches!(
+            current_phase,
+            Phase::Creating | Phase::Updating | Phase::Failed
+        )
+    {
+        return ResourceEvent::ProgressDeadlineExceeded;
+    }
+
+    // Pods of the previous spec may still be ready while the new ones aren't,
+    // so a rollout only ends once the workload runs the current spec everywhere
+    if !ctx.rollout_complete && matches!(current_phase, Phase::Creating | Phase::Updating) {
+        return ResourceEvent::ResourcesApplied;
+    }
+
     // Determine event based on replica status
     if ctx.all_replicas_ready() {
         match current_phase {
@@ -430,8 +536,6 @@ pub fn determine_event(
         }
     } else if ctx.is_degraded() {
         ResourceEvent::ReplicasDegraded
-    } else if *current_phase == Phase::Pending {
-        ResourceEvent::ResourcesApplied
     } else if ctx.no_replicas_ready() && matches!(current_phase, Phase::Creating | Phase::Updating)
     {
         // During initial bootstrap or updates, having 0 ready replicas
@@ -569,4 +673,170 @@ mod tests {
         let event = determine_event(&Phase::Running, &ctx, false);
         assert_eq!(event, ResourceEvent::ReplicasDegraded);
     }
+
+    #[test]
+    fn test_determine_event_error() {
+        let ctx = TransitionContext::new(3, 3).with_error("invalid spec".to_string());
+        let event = determine_event(&Phase::Pending, &ctx, false);
+        assert_eq!(event, ResourceEvent::ReconcileError);
+
+        let sm = ResourceStateMachine::new();
+        let result = sm.transition(&Phase::Pending, event, &ctx);
+        assert!(matches!(
+            result,
+            TransitionResult::Success {
+                to: Phase::Failed,
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn test_determine_event_pending_with_ready_replicas() {
+        // A Pending resource whose Deployment is already up (e.g. after recovery)
+        // must still go through Creating rather than getting stuck in Pending
+        let ctx = TransitionContext::new(3, 3);
+        let event = determine_event(&Phase::Pending, &ctx, false);
+        assert_eq!(event, ResourceEvent::ResourcesApplied);
+    }
+
+    #[test]
+    fn test_determine_event_failed_recovery() {
+        // Failed stays put while nothing changes
+        let ctx = TransitionContext::new(0, 3);
+        let event = determine_event(&Phase::Failed, &ctx, false);
+        assert_eq!(event, ResourceEvent::ReconcileError);
+
+        // Spec change triggers recovery
+        let ctx = TransitionContext::new(0, 3).with_spec_changed(true);
+        let event = determine_event(&Phase::Failed, &ctx, false);
+        assert_eq!(event, ResourceEvent::RecoveryInitiated);
+
+        // Explicit retry triggers recovery
+        let ctx = TransitionContext::new(0, 3).with_recovery_requested(true);
+        let event = determine_event(&Phase::Failed, &ctx, false);
+        assert_eq!(event, ResourceEvent::RecoveryInitiated);
+
+        // Healthy deployment triggers recovery
+        let ctx = TransitionContext::new(3, 3);
+        let event = determine_event(&Phase::Failed, &ctx, false);
+        assert_eq!(event, ResourceEvent::RecoveryInitiated);
+
+        let sm = ResourceStateMachine::new();
+        let result = sm.transition(&Phase::Failed, event, &ctx);
+        assert!(matches!(
+            result,
+            TransitionResult::Success {
+                to: Phase::Pending,
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn test_progress_deadline_exceeded_fails_rollout() {
+        let sm = ResourceStateMachine::new();
+        // The old pods are still ready, but the new ones never came up
+        let ctx = TransitionContext::new(3, 3).with_progress_deadline_exceeded(true);
+
+        for phase in [Phase::Creating, Phase::Updating] {
+            let event = determine_event(&phase, &ctx, false);
+            assert_eq!(event, ResourceEvent::ProgressDeadlineExceeded);
+            assert!(matches!(
+                sm.transition(&phase, event, &ctx),
+                TransitionResult::Success {
+                    to: Phase::Failed,
+                    ..
+                }
+            ));
+        }
+
+        // Failed doesn't recover just because the old replicas are ready
+        let event = determine_event(&Phase::Failed, &ctx, false);
+        assert!(matches!(
+            sm.transition(&Phase::Failed, event, &ctx),
+            TransitionResult::InvalidTransition { .. }
+        ));
+
+        // A new spec gets another attempt
+        let ctx = ctx.with_spec_changed(true);
+        let event = determine_event(&Phase::Failed, &ctx, false);
+        assert_eq!(event, ResourceEvent::RecoveryInitiated);
+    }
+
+    #[test]
+    fn test_incomplete_rollout_stays_updating() {
+        let sm = ResourceStateMachine::new();
+        // The old pods are ready, the new ones aren't yet
+        let ctx = TransitionContext::new(4, 4).with_rollout_complete(false);
+
+        for phase in [Phase::Creating, Phase::Updating] {
+            let event = determine_event(&phase, &ctx, false);
+            assert!(matches!(
+                sm.transition(&phase, event, &ctx),
+                TransitionResult::InvalidTransition { .. }
+            ));
+        }
+
+        let ctx = ctx.with_rollout_complete(true);
+        let event = determine_event(&Phase::Updating, &ctx, false);
+        assert_eq!(event, ResourceEvent::AllReplicasReady);
+    }
+
+    #[test]
+    fn test_rolled_back_resource_stays_failed() {
+        let sm = ResourceStateMachine::new();
+        let ctx = TransitionContext::new(3, 3).with_rolled_back(true);
+        let event = determine_event(&Phase::Failed, &ctx, false);
+        assert!(matches!(
+            sm.transition(&Phase::Failed, event, &ctx),
+            TransitionResult::InvalidTransition { .. }
+        ));
+
+        let ctx = ctx.with_recovery_requested(true);
+        let event = determine_event(&Phase::Failed, &ctx, false);
+        assert_eq!(event, ResourceEvent::RecoveryInitiated);
+    }
+
+    #[test]
+    fn test_invalid_spec_stays_failed() {
+        let sm = ResourceStateMachine::new();
+        // The pods of an earlier spec are healthy, but the current one is invalid
+        let ctx = TransitionContext::new(3, 3).with_spec_valid(false);
+        let event = determine_event(&Phase::Failed, &ctx, false);
+        assert_eq!(event, ResourceEvent::ReconcileError);
+        assert!(matches!(
+            sm.transition(&Phase::Failed, event, &ctx),
+            TransitionResult::InvalidTransition { .. }
+        ));
+
+        // Fixing the spec gets another attempt
+        let ctx = ctx.with_spec_valid(true).with_spec_changed(true);
+        let event = determine_event(&Phase::Failed, &ctx, false);
+        assert_eq!(event, ResourceEvent::RecoveryInitiated);
+    }
+
+    #[test]
+    fn test_recovery_requested_ignored_outside_failed() {
+        let ctx = TransitionContext::new(3, 3).with_recovery_requested(true);
+        let event = determine_event(&Phase::Running, &ctx, false);
+        assert_eq!(event, ResourceEvent::AllReplicasReady);
+    }
+
+    #[test]
+    fn test_degraded_to_failed_on_zero_replicas() {
+        let sm = ResourceStateMachine::new();
+        let ctx = TransitionContext::new(0, 3);
+        let event = determine_event(&Phase::Degraded, &ctx, false);
+        assert_eq!(event, ResourceEvent::ReconcileError);
+
+        let result = sm.transition(&Phase::Degraded, event, &ctx);
+        assert!(matches!(
+            result,
+            TransitionResult::Success {
+                to: Phase::Failed,
+                ..
+            }
+        ));
+    }
 }
@@ -2,7 +2,10 @@
 //!
 //! Provides helpers for building and updating resource status conditions.
 
-use crate::crd::Condition;
+use k8s_openapi::api::core::v1::Pod;
+use kube::ResourceExt;
+
+use crate::crd::{Condition, MAX_TRANSITION_HISTORY, MyResourceStatus, PhaseTransition};
 
 /// Builder for managing conditions list
 pub struct ConditionBuilder {
@@ -17,15 +20,32 @@ impl ConditionBuilder {
         }
     }
 
+    /// Create a condition builder seeded with existing conditions.
+    ///
+    /// Conditions set on the builder keep their `last_transition_time` from the
+    /// existing list unless their status changes.
+    pub fn from_conditions(conditions: Vec<Condition>) -> Self {
+        Self { conditions }
+    }
+
     /// Add or update a condition
     pub fn set(&mut self, condition: Condition) -> &mut Self {
-        // Find and replace existing condition of same type
+        // Find and replace existing condition of same type, keeping the
+        // transition time if the status did not flip
         if let Some(existing) = self
             .conditions
             .iter_mut()
             .find(|c| c.r#type == condition.r#type)
         {
-            *existing = condition;
+            let last_transition_time = if existing.status == condition.status {
+                std::mem::take(&mut existing.last_transition_time)
+            } else {
+                condition.last_transition_time
+            };
+            *existing = Condition {
+                last_transition_time,
+                ..condition
+            };
         } else {
             self.conditions.push(condition);
         }
@@ -70,6 +90,35 @@ impl ConditionBuilder {
         self.set(Condition::degraded(degraded, reason, message, generation))
     }
 
+    /// Set ConfigurationValid condition
+    pub fn configuration_valid(
+        &mut self,
+        valid: bool,
+        reason: &str,
+        message: &str,
+        generation: Option<i64>,
+    ) -> &mut Self {
+        self.set(Condition::configuration_valid(
+            valid, reason, message, generation,
+        ))
+    }
+
+    /// Set RolledBack condition
+    pub fn rolled_back(
+        &mut self,
+        rolled_back: bool,
+        reason: &str,
+        message: &str,
+        generation: Option<i64>,
+    ) -> &mut Self {
+        self.set(Condition::rolled_back(
+            rolled_back,
+            reason,
+            message,
+            generation,
+        ))
+    }
+
     /// Build the conditions list
     pub fn build(self) -> Vec<Condition> {
         self.conditions
@@ -100,3 +149,63 @@ pub fn get_condition_reason<'a>(
         .find(|c| c.r#type == condition_type)
         .map(|c| c.reason.as_str())
 }
+
+/// Append a transition to a history list, dropping the oldest entries beyond
+/// `MAX_TRANSITION_HISTORY`
+pub fn push_transition(history: &mut Vec<PhaseTransition>, transition: PhaseTransition) {
+    history.push(transition);
+    let excess = history.len().saturating_sub(MAX_TRANSITION_HISTORY);
+    history.drain(..excess);
+}
+
+/// Check whether a computed status differs from the current one.
+///
+/// Conditions built with `ConditionBuilder::from_conditions` keep their
+/// transition time while unchanged, so plain equality only reports semantic
+/// changes.
+pub fn status_changed(current: Option<&MyResourceStatus>, desired: &MyResourceStatus) -> bool {
+    current != Some(desired)
+}
+
+/// Summarize the pods whose containers are running but failing their probes.
+///
+/// A container counts as failing if it is not ready although it has started
+/// (readiness or startup probe failing) or has been restarted (liveness probe
+/// failing). Returns `None` if no pod is affected.
+pub fn probe_failure_message(pods: &[Pod]) -> Option<String> {
+    let failing: Vec<String> = pods
+        .iter()
+        .filter_map(|pod| {
+            let status = pod.status.as_ref()?;
+            if status.phase.as_deref() != Some("Running") {
+                return None;
+            }
+            let containers: Vec<String> = status
+                .container_statuses
+                .iter()
+                .flatten()
+                .filter(|c| !c.ready && (c.started == Some(true) || c.restart_count > 0))
+                .map(|c| {
+                    if c.restart_count > 0 {
+                        format!("{} (restarts: {})", c.name, c.restart_count)
+                    } else {
+                        c.name.clone()
+                    }
+                })
+                .collect();
+            if containers.is_empty() {
+                return None;
+            }
+            Some(format!("{}: {}", pod.name_any(), containers.join(", ")))
+        })
+        .collect();
+
+    if failing.is_empty() {
+        return None;
+    }
+    Some(format!(
+        "Containers failing probes in {} pod(s): {}",
+        failing.len(),
+        failing.join("; ")
+    ))
+}
@@ -2,11 +2,36 @@
 //!
 //! This module provides validation for spec changes, including:
 //! - Replica count validation
+//! - Container settings (image, port, pull secrets, resources, probes, Secrets,
+//!   init and sidecar containers)
+//! - Pod scheduling settings (tolerations, topology spread, priority class)
+//! - ConfigMap file names and size
+//! - Service type, ports and traffic policy
+//! - Ingress / HTTPRoute hosts, paths and kind-specific settings
+//! - PodDisruptionBudget settings that still allow evictions
+//! - Autoscaling bounds and utilization targets
+//! - Rollout strategy and progress deadline
+//! - Workload type and StatefulSet volume claim templates
+//! - Storage size, access modes and mount path
 //! - Spec change detection
 //! - Immutable field changes
 
+use std::collections::BTreeSet;
+
+use k8s_openapi::api::core::v1::{
+    LocalObjectReference, ResourceRequirements, Toleration, TopologySpreadConstraint,
+};
+use k8s_openapi::apimachinery::pkg::util::intstr::IntOrString;
+
 use crate::controller::error::{Error, Result};
-use crate::crd::MyResource;
+use crate::crd::{
+    GeneratedSecretSpec, MyResource, MyResourceSpec, PathType, ProbeSpec, ProbesSpec,
+    RolloutStrategy, RouteKind, SecretProjection, SecretRef, ServiceType, VolumeAccessMode,
+    WorkloadType,
+};
+use crate::resources::common::{
+    CONFIG_MOUNT_PATH, DATA_MOUNT_ROOT, MAIN_CONTAINER_NAME, MESSAGE_KEY, SECRET_MOUNT_ROOT,
+};
 
 /// Minimum number of replicas
 pub const MIN_REPLICAS: i32 = 1;
@@ -14,10 +39,38 @@ pub const MIN_REPLICAS: i32 = 1;
 /// Maximum number of replicas
 pub const MAX_REPLICAS: i32 = 10;
 
+/// Minimum container port
+pub const MIN_PORT: i32 = 1;
+
+/// Maximum container port
+pub const MAX_PORT: i32 = 65535;
+
+/// Maximum total size of the managed ConfigMap data, in bytes
+pub const MAX_CONFIG_BYTES: usize = 1024 * 1024;
+
+/// Maximum length of a ConfigMap or Secret key
+const MAX_CONFIG_KEY_LENGTH: usize = 253;
+
+/// Minimum length of a generated password
+pub const MIN_PASSWORD_LENGTH: u32 = 8;
+
+/// Maximum length of a generated password
+pub const MAX_PASSWORD_LENGTH: u32 = 1024;
+
 /// Validate the resource spec
 pub fn validate_spec(resource: &MyResource) -> Result<()> {
     validate_replicas(resource)?;
     validate_message(resource)?;
+    validate_container(&resource.spec)?;
+    validate_scheduling(&resource.spec)?;
+    validate_config(&resource.spec)?;
+    validate_service(&resource.spec)?;
+    validate_ingress(&resource.spec)?;
+    validate_autoscaling(&resource.spec)?;
+    validate_rollout(&resource.spec)?;
+    validate_disruption_budget(&resource.spec)?;
+    validate_workload(&resource.spec)?;
+    validate_storage(&resource.spec)?;
     Ok(())
 }
 
@@ -53,161 +106,2084 @@ fn validate_message(resource: &MyResource) -> Result<()> {
     Ok(())
 }
 
-/// Result of comparing old and new spec
-#[derive(Debug, Clone, Default)]
-pub struct SpecDiff {
-    /// Number of replicas changed
-    pub replicas_changed: bool,
-    /// Scale direction (positive = up, negative = down)
-    pub replica_delta: i32,
-    /// Message changed
-    pub message_changed: bool,
-    /// Labels changed
-    pub labels_changed: bool,
+/// Validate the container settings (image, port, pull secrets, resources,
+/// probes, Secrets and extra containers)
+pub fn validate_container(spec: &MyResourceSpec) -> Result<()> {
+    validate_image(&spec.image)?;
+    validate_container_port(spec.container_port)?;
+    validate_image_pull_secrets(&spec.image_pull_secrets)?;
+    if let Some(ref resources) = spec.resources {
+        validate_resources(resources)?;
+    }
+    if let Some(ref probes) = spec.probes {
+        validate_probes(probes)?;
+    }
+    validate_secret_refs(&spec.secret_refs)?;
+    if let Some(ref generated) = spec.generated_secret {
+        validate_generated_secret(generated)?;
+    }
+    validate_extra_containers(spec)?;
+    Ok(())
 }
 
-impl SpecDiff {
-    /// Check if any changes require an update to managed resources
-    pub fn requires_update(&self) -> bool {
-        self.replicas_changed || self.message_changed || self.labels_changed
-    }
+/// Validate `spec.initContainers` and `spec.sidecars`.
+///
+/// Container names must be unique within the pod. Sidecars share the pod's
+/// network namespace with the main container, so their ports and port names
+/// must not clash with each other or with `containerPort` / `http`.
+fn validate_extra_containers(spec: &MyResourceSpec) -> Result<()> {
+    let mut names = BTreeSet::from([MAIN_CONTAINER_NAME]);
+    let mut ports = BTreeSet::from([(spec.container_port, "TCP")]);
+    let mut port_names = BTreeSet::from(["http"]);
 
-    /// Check if this is a scale-only operation
-    pub fn is_scale_only(&self) -> bool {
-        self.replicas_changed && !self.message_changed && !self.labels_changed
+    let extra = spec
+        .init_containers
+        .iter()
+        .enumerate()
+        .map(|(i, c)| (format!("spec.initContainers[{}]", i), c, false))
+        .chain(
+            spec.sidecars
+                .iter()
+                .enumerate()
+                .map(|(i, c)| (format!("spec.sidecars[{}]", i), c, true)),
+        );
+    for (field, container, sidecar) in extra {
+        if !is_dns_label(&container.name) {
+            return Err(Error::Validation(format!(
+                "{}.name {:?} must be a DNS label",
+                field, container.name
+            )));
+        }
+        if !names.insert(container.name.as_str()) {
+            return Err(Error::Validation(format!(
+                "{}.name {:?} is already used by another container",
+                field, container.name
+            )));
+        }
+        if container.image.as_deref().is_none_or(str::is_empty) {
+            return Err(Error::Validation(format!("{}.image must be set", field)));
+        }
+
+        // Init containers run one at a time before the others start
+        if !sidecar {
+            continue;
+        }
+        for port in container.ports.iter().flatten() {
+            if !(MIN_PORT..=MAX_PORT).contains(&port.container_port) {
+                return Err(Error::Validation(format!(
+                    "{} port {} must be between {} and {}",
+                    field, port.container_port, MIN_PORT, MAX_PORT
+                )));
+            }
+            let protocol = port.protocol.as_deref().unwrap_or("TCP");
+            if !ports.insert((port.container_port, protocol)) {
+                return Err(Error::Validation(format!(
+                    "{} port {}/{} is already used by another container",
+                    field, port.container_port, protocol
+                )));
+            }
+            if let Some(ref name) = port.name
+                && !port_names.insert(name.as_str())
+            {
+                return Err(Error::Validation(format!(
+                    "{} port name {:?} is already used by another container",
+                    field, name
+                )));
+            }
+        }
     }
+    Ok(())
+}
 
-    /// Check if there are any changes
-    pub fn has_changes(&self) -> bool {
-        self.replicas_changed || self.message_changed || self.labels_changed
+/// Validate the container image reference (basic check)
+fn validate_image(image: &str) -> Result<()> {
+    if image.is_empty() {
+        return Err(Error::Validation(
+            "spec.image must not be empty".to_string(),
+        ));
+    }
+    if image.chars().any(char::is_whitespace) {
+        return Err(Error::Validation(format!(
+            "spec.image {:?} must not contain whitespace",
+            image
+        )));
+    }
+    if image.starts_with([':', '/', '@']) || image.ends_with([':', '/', '@']) {
+        return Err(Error::Validation(format!(
+            "spec.image {:?} is not a valid image reference",
+            image
+        )));
     }
+    Ok(())
+}
 
-    /// Check if this is a scale-up operation
-    pub fn is_scale_up(&self) -> bool {
-        self.replicas_changed && self.replica_delta > 0
+/// Validate the container port
+fn validate_container_port(port: i32) -> Result<()> {
+    if !(MIN_PORT..=MAX_PORT).contains(&port) {
+        return Err(Error::Validation(format!(
+            "spec.containerPort {} must be between {} and {}",
+            port, MIN_PORT, MAX_PORT
+        )));
     }
+    Ok(())
+}
 
-    /// Check if this is a scale-down operation
-    pub fn is_scale_down(&self) -> bool {
-        self.replicas_changed && self.replica_delta < 0
+/// Validate that image pull secrets are named and not repeated
+fn validate_image_pull_secrets(secrets: &[LocalObjectReference]) -> Result<()> {
+    let mut seen = BTreeSet::new();
+    for secret in secrets {
+        if secret.name.is_empty() {
+            return Err(Error::Validation(
+                "spec.imagePullSecrets entries must have a name".to_string(),
+            ));
+        }
+        if !seen.insert(secret.name.as_str()) {
+            return Err(Error::Validation(format!(
+                "spec.imagePullSecrets contains {:?} more than once",
+                secret.name
+            )));
+        }
     }
+    Ok(())
 }
 
-/// Validate spec changes between old and new resource specs
-pub fn validate_spec_change(old: &MyResource, new: &MyResource) -> Result<SpecDiff> {
-    let old_spec = &old.spec;
-    let new_spec = &new.spec;
+/// Validate the Secrets referenced in `spec.secretRefs`
+fn validate_secret_refs(secret_refs: &[SecretRef]) -> Result<()> {
+    let mut names = BTreeSet::new();
+    // MESSAGE is always set from spec.message
+    let mut env_names = BTreeSet::from(["MESSAGE"]);
 
-    // First validate the new spec
-    validate_spec(new)?;
+    for (index, secret_ref) in secret_refs.iter().enumerate() {
+        let field = format!("spec.secretRefs[{}]", index);
+        if secret_ref.name.is_empty() {
+            return Err(Error::Validation(format!(
+                "{}.name must not be empty",
+                field
+            )));
+        }
+        if !names.insert(secret_ref.name.as_str()) {
+            return Err(Error::Validation(format!(
+                "spec.secretRefs contains {:?} more than once",
+                secret_ref.name
+            )));
+        }
 
-    // Calculate replica delta
-    let replica_delta = new_spec.replicas - old_spec.replicas;
+        for (key_index, projection) in secret_ref.keys.iter().enumerate() {
+            let key_field = format!("{}.keys[{}]", field, key_index);
+            if !is_valid_data_key(&projection.key) {
+                return Err(Error::Validation(format!(
+                    "{}.key {:?} is not a valid Secret key",
+                    key_field, projection.key
+                )));
+            }
+            let target = projection.target.as_deref().unwrap_or(&projection.key);
+            match secret_ref.projection {
+                SecretProjection::Env => {
+                    if !is_valid_env_name(target) {
+                        return Err(Error::Validation(format!(
+                            "{} target {:?} is not a valid environment variable name",
+                            key_field, target
+                        )));
+                    }
+                    if !env_names.insert(target) {
+                        return Err(Error::Validation(format!(
+                            "{} sets environment variable {:?} more than once",
+                            key_field, target
+                        )));
+                    }
+                }
+                SecretProjection::File => {
+                    if target.is_empty()
+                        || target.starts_with('/')
+                        || target.split('/').any(|part| part == "..")
+                    {
+                        return Err(Error::Validation(format!(
+                            "{} target {:?} must be a relative path without '..'",
+                            key_field, target
+                        )));
+                    }
+                }
+            }
+        }
+    }
+    Ok(())
+}
 
-    // Validate scale down
-    if replica_delta < 0 {
-        validate_scale_down(old, new)?;
+/// Validate the settings of the generated Secret
+fn validate_generated_secret(generated: &GeneratedSecretSpec) -> Result<()> {
+    if !is_valid_data_key(&generated.key) {
+        return Err(Error::Validation(format!(
+            "spec.generatedSecret.key {:?} is not a valid Secret key",
+            generated.key
+        )));
+    }
+    if !(MIN_PASSWORD_LENGTH..=MAX_PASSWORD_LENGTH).contains(&generated.length) {
+        return Err(Error::Validation(format!(
+            "spec.generatedSecret.length {} must be between {} and {}",
+            generated.length, MIN_PASSWORD_LENGTH, MAX_PASSWORD_LENGTH
+        )));
     }
+    Ok(())
+}
 
-    let diff = SpecDiff {
-        replicas_changed: old_spec.replicas != new_spec.replicas,
-        replica_delta,
-        message_changed: old_spec.message != new_spec.message,
-        labels_changed: old_spec.labels != new_spec.labels,
+/// Check that a string is a valid ConfigMap or Secret key
+fn is_valid_data_key(key: &str) -> bool {
+    !key.is_empty()
+        && key.len() <= MAX_CONFIG_KEY_LENGTH
+        && key != "."
+        && key != ".."
+        && key
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'))
+}
+
+/// Check that a string is a valid environment variable name
+fn is_valid_env_name(name: &str) -> bool {
+    name.chars()
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || matches!(c, '-' | '_' | '.'))
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'))
+}
+
+/// Validate resource quantities and that no request exceeds its limit
+fn validate_resources(resources: &ResourceRequirements) -> Result<()> {
+    let parse = |field: &str, name: &str, quantity: &str| {
+        parse_quantity(quantity).ok_or_else(|| {
+            Error::Validation(format!(
+                "spec.resources.{}.{} has invalid quantity {:?}",
+                field, name, quantity
+            ))
+        })
     };
 
-    Ok(diff)
+    let limits = resources.limits.clone().unwrap_or_default();
+    for (name, quantity) in &limits {
+        parse("limits", name, &quantity.0)?;
+    }
+
+    for (name, quantity) in resources.requests.iter().flatten() {
+        let request = parse("requests", name, &quantity.0)?;
+        if let Some(limit) = limits.get(name)
+            && request > parse("limits", name, &limit.0)?
+        {
+            return Err(Error::Validation(format!(
+                "spec.resources.requests.{} ({}) exceeds the limit ({})",
+                name, quantity.0, limit.0
+            )));
+        }
+    }
+    Ok(())
 }
 
-/// Validate scale down operation
-fn validate_scale_down(old: &MyResource, new: &MyResource) -> Result<()> {
-    let new_replicas = new.spec.replicas;
+/// Validate the container probes
+fn validate_probes(probes: &ProbesSpec) -> Result<()> {
+    let all = [
+        ("liveness", &probes.liveness, false),
+        ("readiness", &probes.readiness, true),
+        ("startup", &probes.startup, false),
+    ];
+    for (name, probe, allows_success_threshold) in all {
+        if let Some(probe) = probe {
+            validate_probe(
+                &format!("spec.probes.{}", name),
+                probe,
+                allows_success_threshold,
+            )?;
+        }
+    }
+    Ok(())
+}
 
-    // Ensure we don't go below minimum
-    if new_replicas < MIN_REPLICAS {
+/// Validate a single probe
+fn validate_probe(field: &str, probe: &ProbeSpec, allows_success_threshold: bool) -> Result<()> {
+    let handlers = [
+        probe.http_get.is_some(),
+        probe.tcp_socket.is_some(),
+        probe.exec.is_some(),
+    ];
+    if handlers.into_iter().filter(|set| *set).count() > 1 {
         return Err(Error::Validation(format!(
-            "cannot scale down to {} replicas, minimum is {}",
-            new_replicas, MIN_REPLICAS
+            "{} must set at most one of httpGet, tcpSocket and exec",
+            field
         )));
     }
 
-    // Warn about aggressive scale down (more than 50% at once)
-    let old_replicas = old.spec.replicas;
-    let delta = old_replicas - new_replicas;
-    if old_replicas > 2 && delta > old_replicas / 2 {
-        tracing::warn!(
-            old_replicas,
-            new_replicas,
-            delta,
-            "Large scale down detected. Consider scaling down gradually."
-        );
+    let port = probe
+        .http_get
+        .as_ref()
+        .and_then(|h| h.port)
+        .or_else(|| probe.tcp_socket.as_ref().and_then(|t| t.port));
+    if let Some(port) = port
+        && !(MIN_PORT..=MAX_PORT).contains(&port)
+    {
+        return Err(Error::Validation(format!(
+            "{} port {} must be between {} and {}",
+            field, port, MIN_PORT, MAX_PORT
+        )));
+    }
+
+    if let Some(ref http) = probe.http_get {
+        if !http.path.starts_with('/') {
+            return Err(Error::Validation(format!(
+                "{}.httpGet.path {:?} must start with /",
+                field, http.path
+            )));
+        }
+        if let Some(ref scheme) = http.scheme
+            && scheme != "HTTP"
+            && scheme != "HTTPS"
+        {
+            return Err(Error::Validation(format!(
+                "{}.httpGet.scheme {:?} must be HTTP or HTTPS",
+                field, scheme
+            )));
+        }
     }
 
+    if probe
+        .exec
+        .as_ref()
+        .is_some_and(|e| e.command.is_empty() || e.command.iter().all(String::is_empty))
+    {
+        return Err(Error::Validation(format!(
+            "{}.exec.command must not be empty",
+            field
+        )));
+    }
+
+    let minimums = [
+        ("initialDelaySeconds", probe.initial_delay_seconds, 0),
+        ("periodSeconds", probe.period_seconds, 1),
+        ("timeoutSeconds", probe.timeout_seconds, 1),
+        ("failureThreshold", probe.failure_threshold, 1),
+        ("successThreshold", probe.success_threshold, 1),
+    ];
+    for (name, value, minimum) in minimums {
+        if let Some(value) = value
+            && value < minimum
+        {
+            return Err(Error::Validation(format!(
+                "{}.{} must be at least {} (got {})",
+                field, name, minimum, value
+            )));
+        }
+    }
+    if !allows_success_threshold && probe.success_threshold.is_some_and(|v| v != 1) {
+        return Err(Error::Validation(format!(
+            "{}.successThreshold must be 1",
+            field
+        )));
+    }
     Ok(())
 }
 
-/// Check if generation has changed (spec update)
-pub fn generation_changed(resource: &MyResource) -> bool {
-    let generation = resource.metadata.generation;
-    let observed = resource.status.as_ref().and_then(|s| s.observed_generation);
+/// Validate the pod scheduling settings
+pub fn validate_scheduling(spec: &MyResourceSpec) -> Result<()> {
+    if spec.node_selector.keys().any(String::is_empty) {
+        return Err(Error::Validation(
+            "spec.nodeSelector keys must not be empty".to_string(),
+        ));
+    }
+    for (index, toleration) in spec.tolerations.iter().enumerate() {
+        validate_toleration(index, toleration)?;
+    }
+    for (index, constraint) in spec.topology_spread_constraints.iter().enumerate() {
+        validate_topology_spread_constraint(index, constraint)?;
+    }
+    if spec
+        .priority_class_name
+        .as_ref()
+        .is_some_and(|name| name.is_empty())
+    {
+        return Err(Error::Validation(
+            "spec.priorityClassName must not be empty".to_string(),
+        ));
+    }
+    Ok(())
+}
 
-    match (generation, observed) {
-        (Some(current_gen), Some(obs)) => current_gen != obs,
-        (Some(_), None) => true, // No observed generation, assume changed
-        _ => false,
+/// Validate the extra ConfigMap files
+pub fn validate_config(spec: &MyResourceSpec) -> Result<()> {
+    for key in spec.config.keys() {
+        if !is_valid_data_key(key) {
+            return Err(Error::Validation(format!(
+                "spec.config key {:?} must be a file name of at most {} characters \
+                 (alphanumerics, '-', '_' or '.')",
+                key, MAX_CONFIG_KEY_LENGTH
+            )));
+        }
+        if key == MESSAGE_KEY {
+            return Err(Error::Validation(format!(
+                "spec.config key {:?} is reserved for spec.message",
+                key
+            )));
+        }
+    }
+
+    let size = spec.message.len()
+        + spec
+            .config
+            .iter()
+            .map(|(key, value)| key.len() + value.len())
+            .sum::<usize>();
+    if size > MAX_CONFIG_BYTES {
+        return Err(Error::Validation(format!(
+            "spec.config and spec.message total {} bytes, exceeding the ConfigMap limit of {}",
+            size, MAX_CONFIG_BYTES
+        )));
     }
+    Ok(())
 }
 
-#[cfg(test)]
-#[allow(clippy::unwrap_used, clippy::expect_used, clippy::indexing_slicing)]
-mod tests {
-    use super::*;
-    use crate::crd::{MyResourceSpec, MyResourceStatus};
-    use k8s_openapi::apimachinery::pkg::apis::meta::v1::ObjectMeta;
-    use std::collections::BTreeMap;
+/// Validate the Service exposure settings
+pub fn validate_service(spec: &MyResourceSpec) -> Result<()> {
+    let Some(ref service) = spec.service else {
+        return Ok(());
+    };
 
-    fn create_test_resource(replicas: i32, message: &str) -> MyResource {
-        MyResource {
-            metadata: ObjectMeta {
-                name: Some("test".to_string()),
-                namespace: Some("default".to_string()),
-                generation: Some(1),
-                ..Default::default()
-            },
-            spec: MyResourceSpec {
-                replicas,
-                message: message.to_string(),
-                labels: BTreeMap::new(),
-            },
-            status: Some(MyResourceStatus::default()),
+    let mut names = BTreeSet::new();
+    for (index, port) in service.ports.iter().enumerate() {
+        let field = format!("spec.service.ports[{}]", index);
+        match port.name.as_deref() {
+            Some(name) => {
+                if !is_dns_label(name) {
+                    return Err(Error::Validation(format!(
+                        "{}.name {:?} must be a lowercase DNS label",
+                        field, name
+                    )));
+                }
+                if !names.insert(name) {
+                    return Err(Error::Validation(format!(
+                        "spec.service.ports contains name {:?} more than once",
+                        name
+                    )));
+                }
+            }
+            None if service.ports.len() > 1 => {
+                return Err(Error::Validation(format!(
+                    "{}.name is required when more than one port is exposed",
+                    field
+                )));
+            }
+            None => {}
+        }
+
+        let ports = [
+            ("port", Some(port.port)),
+            ("targetPort", port.target_port),
+            ("nodePort", port.node_port),
+        ];
+        for (name, value) in ports {
+            if let Some(value) = value
+                && !(MIN_PORT..=MAX_PORT).contains(&value)
+            {
+                return Err(Error::Validation(format!(
+                    "{}.{} {} must be between {} and {}",
+                    field, name, value, MIN_PORT, MAX_PORT
+                )));
+            }
+        }
+        if port.node_port.is_some() && !service.type_.has_node_ports() {
+            return Err(Error::Validation(format!(
+                "{}.nodePort requires a NodePort or LoadBalancer Service, not {}",
+                field, service.type_
+            )));
+        }
+        if let Some(ref protocol) = port.protocol
+            && !matches!(protocol.as_str(), "TCP" | "UDP" | "SCTP")
+        {
+            return Err(Error::Validation(format!(
+                "{}.protocol {:?} must be TCP, UDP or SCTP",
+                field, protocol
+            )));
         }
     }
 
-    #[test]
-    fn test_validate_replicas_valid() {
-        let resource = create_test_resource(3, "test");
-        assert!(validate_spec(&resource).is_ok());
+    if service.external_traffic_policy.is_some() && !service.type_.has_node_ports() {
+        return Err(Error::Validation(format!(
+            "spec.service.externalTrafficPolicy requires a NodePort or LoadBalancer Service, not {}",
+            service.type_
+        )));
+    }
+    if service.type_ == ServiceType::Headless && service.session_affinity.is_some() {
+        return Err(Error::Validation(
+            "spec.service.sessionAffinity is not supported for headless Services".to_string(),
+        ));
     }
+    if service.annotations.keys().any(String::is_empty) {
+        return Err(Error::Validation(
+            "spec.service.annotations keys must not be empty".to_string(),
+        ));
+    }
+    Ok(())
+}
 
-    #[test]
-    fn test_validate_replicas_too_low() {
-        let resource = create_test_resource(0, "test");
-        assert!(validate_spec(&resource).is_err());
+/// Validate the Ingress / HTTPRoute settings
+pub fn validate_ingress(spec: &MyResourceSpec) -> Result<()> {
+    let Some(ref ingress) = spec.ingress else {
+        return Ok(());
+    };
+
+    let mut hosts = BTreeSet::new();
+    for host in &ingress.hosts {
+        let name = host.strip_prefix("*.").unwrap_or(host);
+        if name.len() > 253 || !name.split('.').all(is_dns_label) {
+            return Err(Error::Validation(format!(
+                "spec.ingress.hosts entry {:?} is not a valid host name",
+                host
+            )));
+        }
+        if !hosts.insert(host.as_str()) {
+            return Err(Error::Validation(format!(
+                "spec.ingress.hosts contains {:?} more than once",
+                host
+            )));
+        }
     }
 
-    #[test]
-    fn test_validate_replicas_too_high() {
-        let resource = create_test_resource(100, "test");
-        assert!(validate_spec(&resource).is_err());
+    for (index, path) in ingress.paths.iter().enumerate() {
+        if !path.path.starts_with('/') {
+            return Err(Error::Validation(format!(
+                "spec.ingress.paths[{}].path {:?} must start with /",
+                index, path.path
+            )));
+        }
+        if ingress.kind == RouteKind::HTTPRoute
+            && path.path_type == PathType::ImplementationSpecific
+        {
+            return Err(Error::Validation(format!(
+                "spec.ingress.paths[{}].pathType ImplementationSpecific is not supported for HTTPRoute",
+                index
+            )));
+        }
     }
 
-    #[test]
-    fn test_validate_replicas_at_bounds() {
-        let min_resource = create_test_resource(MIN_REPLICAS, "test");
-        assert!(validate_spec(&min_resource).is_ok());
+    if let Some(port) = ingress.port {
+        let service_ports: Vec<i32> = spec
+            .service
+            .as_ref()
+            .map(|s| s.ports.iter().map(|p| p.port).collect())
+            .filter(|ports: &Vec<i32>| !ports.is_empty())
+            .unwrap_or_else(|| vec![spec.container_port]);
+        if !service_ports.contains(&port) {
+            return Err(Error::Validation(format!(
+                "spec.ingress.port {} is not a port of the Service ({:?})",
+                port, service_ports
+            )));
+        }
+    }
 
-        let max_resource = create_test_resource(MAX_REPLICAS, "test");
-        assert!(validate_spec(&max_resource).is_ok());
+    match ingress.kind {
+        RouteKind::Ingress => {
+            if ingress.gateway.is_some() {
+                return Err(Error::Validation(
+                    "spec.ingress.gateway is only supported for HTTPRoute".to_string(),
+                ));
+            }
+            if ingress
+                .tls_secret_name
+                .as_ref()
+                .is_some_and(String::is_empty)
+                || ingress.class_name.as_ref().is_some_and(String::is_empty)
+            {
+                return Err(Error::Validation(
+                    "spec.ingress.tlsSecretName and className must not be empty".to_string(),
+                ));
+            }
+        }
+        RouteKind::HTTPRoute => {
+            if ingress.gateway.as_ref().is_none_or(|g| g.name.is_empty()) {
+                return Err(Error::Validation(
+                    "spec.ingress.gateway.name is required for HTTPRoute".to_string(),
+                ));
+            }
+            if ingress.tls_secret_name.is_some() || ingress.class_name.is_some() {
+                return Err(Error::Validation(
+                    "spec.ingress.tlsSecretName and className are not supported for HTTPRoute; \
+                     configure them on the Gateway"
+                        .to_string(),
+                ));
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Validate the autoscaling settings.
+///
+/// `maxReplicas` is held to the same bounds as `spec.replicas`, and each
+/// utilization target needs a matching resource request to scale on.
+pub fn validate_autoscaling(spec: &MyResourceSpec) -> Result<()> {
+    let Some(ref autoscaling) = spec.autoscaling else {
+        return Ok(());
+    };
+
+    let max_replicas = autoscaling.max_replicas;
+    if !(MIN_REPLICAS..=MAX_REPLICAS).contains(&max_replicas) {
+        return Err(Error::Validation(format!(
+            "spec.autoscaling.maxReplicas must be between {} and {} (got {})",
+            MIN_REPLICAS, MAX_REPLICAS, max_replicas
+        )));
+    }
+    let min_replicas = spec.min_replicas();
+    if min_replicas < MIN_REPLICAS || min_replicas > max_replicas {
+        return Err(Error::Validation(format!(
+            "spec.autoscaling.minReplicas must be between {} and maxReplicas {} (got {})",
+            MIN_REPLICAS, max_replicas, min_replicas
+        )));
+    }
+
+    let requests = spec
+        .resources
+        .as_ref()
+        .map(|r| r.requests.clone().unwrap_or_default());
+    for (field, resource, target) in [
+        ("targetCpuUtilization", "cpu", autoscaling.cpu_target()),
+        (
+            "targetMemoryUtilization",
+            "memory",
+            autoscaling.target_memory_utilization,
+        ),
+    ] {
+        let Some(target) = target else {
+            continue;
+        };
+        if target <= 0 {
+            return Err(Error::Validation(format!(
+                "spec.autoscaling.{} must be greater than 0 (got {})",
+                field, target
+            )));
+        }
+        // Without spec.resources the default requests cover cpu and memory
+        if let Some(ref requests) = requests
+            && !requests.contains_key(resource)
+        {
+            return Err(Error::Validation(format!(
+                "spec.autoscaling.{} requires a {} request in spec.resources",
+                field, resource
+            )));
+        }
+    }
+
+    Ok(())
+}
+
+/// Validate the workload type and its volume claim templates.
+///
+/// Each claim becomes a pod volume, so its name must be a unique DNS label that
+/// doesn't collide with the ConfigMap or Secret volumes.
+pub fn validate_workload(spec: &MyResourceSpec) -> Result<()> {
+    if spec.volume_claim_templates.is_empty() {
+        return Ok(());
+    }
+    if spec.workload_type != WorkloadType::StatefulSet {
+        return Err(Error::Validation(
+            "spec.volumeClaimTemplates requires spec.workloadType StatefulSet".to_string(),
+        ));
+    }
+
+    let mut names = BTreeSet::new();
+    for (index, claim) in spec.volume_claim_templates.iter().enumerate() {
+        let name = claim.metadata.name.as_deref().unwrap_or_default();
+        if !is_dns_label(name) {
+            return Err(Error::Validation(format!(
+                "spec.volumeClaimTemplates[{}].metadata.name '{}' must be a lowercase DNS label",
+                index, name
+            )));
+        }
+        if ["config", "generated-secret", "storage"].contains(&name) || name.starts_with("secret-")
+        {
+            return Err(Error::Validation(format!(
+                "spec.volumeClaimTemplates[{}].metadata.name '{}' is reserved for the operator's volumes",
+                index, name
+            )));
+        }
+        if !names.insert(name) {
+            return Err(Error::Validation(format!(
+                "spec.volumeClaimTemplates has duplicate name '{}'",
+                name
+            )));
+        }
+        let has_storage = claim
+            .spec
+            .as_ref()
+            .and_then(|s| s.resources.as_ref())
+            .and_then(|r| r.requests.as_ref())
+            .is_some_and(|r| r.contains_key("storage"));
+        if !has_storage {
+            return Err(Error::Validation(format!(
+                "spec.volumeClaimTemplates[{}] must request storage in spec.resources.requests",
+                index
+            )));
+        }
+    }
+
+    Ok(())
+}
+
+/// Validate the operator-managed storage settings.
+///
+/// The mount path must not shadow or sit inside the directories the operator
+/// mounts the ConfigMap, Secrets and StatefulSet volume claims at.
+pub fn validate_storage(spec: &MyResourceSpec) -> Result<()> {
+    let Some(ref storage) = spec.storage else {
+        return Ok(());
+    };
+
+    if !parse_quantity(&storage.size.0).is_some_and(|size| size > 0.0) {
+        return Err(Error::Validation(format!(
+            "spec.storage.size '{}' must be a positive quantity",
+            storage.size.0
+        )));
+    }
+
+    if storage.access_modes.is_empty() {
+        return Err(Error::Validation(
+            "spec.storage.accessModes must not be empty".to_string(),
+        ));
+    }
+    if storage
+        .access_modes
+        .contains(&VolumeAccessMode::ReadWriteOncePod)
+        && spec.max_replicas() > 1
+    {
+        return Err(Error::Validation(
+            "spec.storage.accessModes ReadWriteOncePod allows a single pod, but the workload can \
+             run more than one replica"
+                .to_string(),
+        ));
+    }
+    // The replicas are spread across nodes, and a single-node volume can only
+    // be attached to one of them
+    let multi_node = storage.access_modes.iter().any(|mode| {
+        matches!(
+            mode,
+            VolumeAccessMode::ReadWriteMany | VolumeAccessMode::ReadOnlyMany
+        )
+    });
+    if storage
+        .access_modes
+        .contains(&VolumeAccessMode::ReadWriteOnce)
+        && !multi_node
+        && spec.max_replicas() > 1
+    {
+        return Err(Error::Validation(
+            "spec.storage.accessModes ReadWriteOnce attaches the volume to a single node, but the \
+             workload can run more than one replica; use ReadWriteMany, or a StatefulSet with \
+             spec.volumeClaimTemplates for a volume per replica"
+                .to_string(),
+        ));
+    }
+
+    let mount_path = storage.mount_path.trim_end_matches('/');
+    if !storage.mount_path.starts_with('/') || mount_path.is_empty() {
+        return Err(Error::Validation(format!(
+            "spec.storage.mountPath '{}' must be an absolute path below /",
+            storage.mount_path
+        )));
+    }
+    let mut reserved = vec![CONFIG_MOUNT_PATH, SECRET_MOUNT_ROOT];
+    if !spec.volume_claim_templates.is_empty() {
+        reserved.push(DATA_MOUNT_ROOT);
+    }
+    let overlaps = |a: &str, b: &str| a == b || a.starts_with(&format!("{}/", b));
+    if let Some(path) = reserved
+        .iter()
+        .find(|path| overlaps(mount_path, path) || overlaps(path, mount_path))
+    {
+        return Err(Error::Validation(format!(
+            "spec.storage.mountPath '{}' overlaps the operator-managed mount {}",
+            storage.mount_path, path
+        )));
+    }
+
+    Ok(())
+}
+
+/// Validate the Deployment rollout settings.
+///
+/// Like the Deployment API, `maxSurge` and `maxUnavailable` can't both be
+/// zero, or a rolling update could never replace a pod.
+pub fn validate_rollout(spec: &MyResourceSpec) -> Result<()> {
+    let Some(ref rollout) = spec.rollout else {
+        return Ok(());
+    };
+
+    if spec.workload_type != WorkloadType::Deployment {
+        return Err(Error::Validation(format!(
+            "spec.rollout is only supported with workloadType Deployment, not {}",
+            spec.workload_type
+        )));
+    }
+
+    if rollout.strategy == RolloutStrategy::Recreate
+        && (rollout.max_surge.is_some() || rollout.max_unavailable.is_some())
+    {
+        return Err(Error::Validation(
+            "spec.rollout.maxSurge and maxUnavailable are only allowed with the RollingUpdate \
+             strategy"
+                .to_string(),
+        ));
+    }
+
+    let max_surge = rollout
+        .max_surge
+        .as_ref()
+        .map(|v| rollout_value(v, "maxSurge", None))
+        .transpose()?;
+    let max_unavailable = rollout
+        .max_unavailable
+        .as_ref()
+        .map(|v| rollout_value(v, "maxUnavailable", Some(100)))
+        .transpose()?;
+    if max_surge == Some(0) && max_unavailable == Some(0) {
+        return Err(Error::Validation(
+            "spec.rollout.maxSurge and maxUnavailable must not both be 0".to_string(),
+        ));
+    }
+
+    if let Some(deadline) = rollout.progress_deadline_seconds
+        && deadline <= 0
+    {
+        return Err(Error::Validation(format!(
+            "spec.rollout.progressDeadlineSeconds {} must be positive",
+            deadline
+        )));
+    }
+
+    Ok(())
+}
+
+/// Parse a rollout count or percentage, returning the number it holds
+fn rollout_value(value: &IntOrString, field: &str, max_percent: Option<i32>) -> Result<i32> {
+    let invalid = || {
+        Error::Validation(format!(
+            "spec.rollout.{} must be a non-negative number or percentage{}",
+            field,
+            max_percent.map_or(String::new(), |max| format!(" up to {}%", max))
+        ))
+    };
+    match value {
+        IntOrString::Int(count) if *count >= 0 => Ok(*count),
+        IntOrString::Int(_) => Err(invalid()),
+        IntOrString::String(percent) => percent
+            .strip_suffix('%')
+            .and_then(|p| p.parse::<i32>().ok())
+            .filter(|p| *p >= 0 && max_percent.is_none_or(|max| *p <= max))
+            .ok_or_else(invalid),
+    }
+}
+
+/// Validate the PodDisruptionBudget settings.
+///
+/// A budget that allows no evictions would block node drains forever, so with
+/// more than one replica at least one pod must be evictable. With autoscaling
+/// the budget is checked at `minReplicas`, the smallest scale it applies to.
+pub fn validate_disruption_budget(spec: &MyResourceSpec) -> Result<()> {
+    let Some(ref budget) = spec.pod_disruption_budget else {
+        return Ok(());
+    };
+    // No budget is created for a single replica
+    if spec.max_replicas() <= 1 {
+        return Ok(());
+    }
+    let replicas = spec.min_replicas();
+
+    match (&budget.min_available, &budget.max_unavailable) {
+        (Some(_), Some(_)) => Err(Error::Validation(
+            "spec.podDisruptionBudget may set only one of minAvailable and maxUnavailable"
+                .to_string(),
+        )),
+        (Some(min_available), None) => {
+            let min_available = scaled_budget_value(min_available, replicas, "minAvailable")?;
+            if min_available >= replicas {
+                return Err(Error::Validation(format!(
+                    "spec.podDisruptionBudget.minAvailable ({} of {} replicas) would block all evictions",
+                    min_available, replicas
+                )));
+            }
+            Ok(())
+        }
+        (None, Some(max_unavailable)) => {
+            let max_unavailable = scaled_budget_value(max_unavailable, replicas, "maxUnavailable")?;
+            if max_unavailable < 1 {
+                return Err(Error::Validation(
+                    "spec.podDisruptionBudget.maxUnavailable of 0 would block all evictions"
+                        .to_string(),
+                ));
+            }
+            Ok(())
+        }
+        (None, None) => Ok(()),
+    }
+}
+
+/// Resolve a budget value to a pod count, rounding percentages up like the
+/// disruption controller does
+fn scaled_budget_value(value: &IntOrString, replicas: i32, field: &str) -> Result<i32> {
+    let invalid = || {
+        Error::Validation(format!(
+            "spec.podDisruptionBudget.{} must be a non-negative number or a percentage \
+             between 0% and 100%",
+            field
+        ))
+    };
+    match value {
+        IntOrString::Int(count) if *count >= 0 => Ok(*count),
+        IntOrString::Int(_) => Err(invalid()),
+        IntOrString::String(percent) => {
+            let percent = percent
+                .strip_suffix('%')
+                .and_then(|p| p.parse::<i32>().ok())
+                .filter(|p| (0..=100).contains(p))
+                .ok_or_else(invalid)?;
+            Ok((replicas * percent + 99) / 100)
+        }
+    }
+}
+
+/// Check that a string is a lowercase RFC 1123 DNS label
+fn is_dns_label(name: &str) -> bool {
+    !name.is_empty()
+        && name.len() <= 63
+        && name
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-')
+        && !name.starts_with('-')
+        && !name.ends_with('-')
+}
+
+/// Validate a single toleration
+fn validate_toleration(index: usize, toleration: &Toleration) -> Result<()> {
+    let field = format!("spec.tolerations[{}]", index);
+
+    match toleration.operator.as_deref() {
+        None | Some("Equal") => {}
+        Some("Exists") => {
+            if toleration.value.as_ref().is_some_and(|v| !v.is_empty()) {
+                return Err(Error::Validation(format!(
+                    "{}.value must be empty when operator is Exists",
+                    field
+                )));
+            }
+        }
+        Some(other) => {
+            return Err(Error::Validation(format!(
+                "{}.operator {:?} must be Equal or Exists",
+                field, other
+            )));
+        }
+    }
+
+    match toleration.effect.as_deref() {
+        None | Some("") | Some("NoSchedule") | Some("PreferNoSchedule") | Some("NoExecute") => {}
+        Some(other) => {
+            return Err(Error::Validation(format!(
+                "{}.effect {:?} must be NoSchedule, PreferNoSchedule or NoExecute",
+                field, other
+            )));
+        }
+    }
+
+    if toleration.toleration_seconds.is_some() && toleration.effect.as_deref() != Some("NoExecute")
+    {
+        return Err(Error::Validation(format!(
+            "{}.tolerationSeconds is only allowed with effect NoExecute",
+            field
+        )));
+    }
+    Ok(())
+}
+
+/// Validate a single topology spread constraint
+fn validate_topology_spread_constraint(
+    index: usize,
+    constraint: &TopologySpreadConstraint,
+) -> Result<()> {
+    let field = format!("spec.topologySpreadConstraints[{}]", index);
+
+    if constraint.max_skew < 1 {
+        return Err(Error::Validation(format!(
+            "{}.maxSkew must be at least 1 (got {})",
+            field, constraint.max_skew
+        )));
+    }
+    if constraint.topology_key.is_empty() {
+        return Err(Error::Validation(format!(
+            "{}.topologyKey must not be empty",
+            field
+        )));
+    }
+    if !matches!(
+        constraint.when_unsatisfiable.as_str(),
+        "DoNotSchedule" | "ScheduleAnyway"
+    ) {
+        return Err(Error::Validation(format!(
+            "{}.whenUnsatisfiable {:?} must be DoNotSchedule or ScheduleAnyway",
+            field, constraint.when_unsatisfiable
+        )));
+    }
+    Ok(())
+}
+
+/// Parse a Kubernetes resource quantity (e.g. `500m`, `1.5`, `128Mi`, `1e3`)
+/// into its numeric value.
+///
+/// Returns `None` for malformed or negative quantities.
+pub fn parse_quantity(quantity: &str) -> Option<f64> {
+    const BINARY: [(&str, f64); 6] = [
+        ("Ki", 1024.0),
+        ("Mi", 1_048_576.0),
+        ("Gi", 1_073_741_824.0),
+        ("Ti", 1_099_511_627_776.0),
+        ("Pi", 1_125_899_906_842_624.0),
+        ("Ei", 1_152_921_504_606_846_976.0),
+    ];
+    const DECIMAL: [(char, f64); 9] = [
+        ('n', 1e-9),
+        ('u', 1e-6),
+        ('m', 1e-3),
+        ('k', 1e3),
+        ('M', 1e6),
+        ('G', 1e9),
+        ('T', 1e12),
+        ('P', 1e15),
+        ('E', 1e18),
+    ];
+
+    let number = |s: &str| {
+        // Reject forms f64 accepts but Kubernetes doesn't (inf, NaN, ...)
+        if s.is_empty()
+            || !s.starts_with(|c: char| c.is_ascii_digit() || matches!(c, '.' | '+' | '-'))
+        {
+            return None;
+        }
+        s.parse::<f64>().ok().filter(|v| v.is_finite() && *v >= 0.0)
+    };
+
+    for (suffix, multiplier) in BINARY {
+        if let Some(value) = quantity.strip_suffix(suffix) {
+            return number(value).map(|v| v * multiplier);
+        }
+    }
+    for (suffix, multiplier) in DECIMAL {
+        if let Some(v) = quantity.strip_suffix(suffix).and_then(number) {
+            return Some(v * multiplier);
+        }
+    }
+    // Plain numbers and decimal exponents such as 1e3
+    number(quantity)
+}
+
+/// Result of comparing old and new spec
+#[derive(Debug, Clone, Default)]
+pub struct SpecDiff {
+    /// Number of replicas changed
+    pub replicas_changed: bool,
+    /// Scale direction (positive = up, negative = down)
+    pub replica_delta: i32,
+    /// Message changed
+    pub message_changed: bool,
+    /// Labels changed
+    pub labels_changed: bool,
+    /// Container settings (image, pull policy/secrets, port, resources, probes,
+    /// Secrets) changed
+    pub container_changed: bool,
+    /// Scheduling settings (node selector, tolerations, affinity, spread, priority) changed
+    pub scheduling_changed: bool,
+    /// ConfigMap files changed
+    pub config_changed: bool,
+    /// Service exposure settings changed
+    pub service_changed: bool,
+    /// Ingress / HTTPRoute settings changed
+    pub ingress_changed: bool,
+    /// PodDisruptionBudget settings changed
+    pub disruption_budget_changed: bool,
+    /// Autoscaling settings changed
+    pub autoscaling_changed: bool,
+    /// Workload type or volume claim templates changed
+    pub workload_changed: bool,
+    /// Storage settings changed
+    pub storage_changed: bool,
+    /// Rollout settings changed
+    pub rollout_changed: bool,
+}
+
+impl SpecDiff {
+    /// Check if any changes require an update to managed resources
+    pub fn requires_update(&self) -> bool {
+        self.has_changes()
+    }
+
+    /// Check if this is a scale-only operation
+    pub fn is_scale_only(&self) -> bool {
+        self.replicas_changed
+            && !self.message_changed
+            && !self.labels_changed
+            && !self.container_changed
+            && !self.scheduling_changed
+            && !self.config_changed
+            && !self.service_changed
+            && !self.ingress_changed
+            && !self.disruption_budget_changed
+            && !self.autoscaling_changed
+            && !self.workload_changed
+            && !self.storage_changed
+            && !self.rollout_changed
+    }
+
+    /// Check if there are any changes
+    pub fn has_changes(&self) -> bool {
+        self.replicas_changed
+            || self.message_changed
+            || self.labels_changed
+            || self.container_changed
+            || self.scheduling_changed
+            || self.config_changed
+            || self.service_changed
+            || self.ingress_changed
+            || self.disruption_budget_changed
+            || self.autoscaling_changed
+            || self.workload_changed
+            || self.storage_changed
+            || self.rollout_changed
+    }
+
+    /// Check if this is a scale-up operation
+    pub fn is_scale_up(&self) -> bool {
+        self.replicas_changed && self.replica_delta > 0
+    }
+
+    /// Check if this is a scale-down operation
+    pub fn is_scale_down(&self) -> bool {
+        self.replicas_changed && self.replica_delta < 0
+    }
+}
+
+/// Validate spec changes between old and new resource specs
+pub fn validate_spec_change(old: &MyResource, new: &MyResource) -> Result<SpecDiff> {
+    let old_spec = &old.spec;
+    let new_spec = &new.spec;
+
+    // First validate the new spec
+    validate_spec(new)?;
+
+    // Calculate replica delta
+    let replica_delta = new_spec.replicas - old_spec.replicas;
+
+    // Validate scale down
+    if replica_delta < 0 {
+        validate_scale_down(old, new)?;
+    }
+
+    let diff = SpecDiff {
+        replicas_changed: old_spec.replicas != new_spec.replicas,
+        replica_delta,
+        message_changed: old_spec.message != new_spec.message,
+        labels_changed: old_spec.labels != new_spec.labels,
+        container_changed: old_spec.image != new_spec.image
+            || old_spec.image_pull_policy != new_spec.image_pull_policy
+            || old_spec.image_pull_secrets != new_spec.image_pull_secrets
+            || old_spec.container_port != new_spec.container_port
+            || old_spec.resources != new_spec.resources
+            || old_spec.probes != new_spec.probes
+            || old_spec.init_containers != new_spec.init_containers
+            || old_spec.sidecars != new_spec.sidecars
+            || old_spec.secret_refs != new_spec.secret_refs
+            || old_spec.generated_secret != new_spec.generated_secret,
+        scheduling_changed: old_spec.node_selector != new_spec.node_selector
+            || old_spec.tolerations != new_spec.tolerations
+            || old_spec.affinity != new_spec.affinity
+            || old_spec.topology_spread_constraints != new_spec.topology_spread_constraints
+            || old_spec.priority_class_name != new_spec.priority_class_name,
+        config_changed: old_spec.config != new_spec.config,
+        service_changed: old_spec.service != new_spec.service,
+        ingress_changed: old_spec.ingress != new_spec.ingress,
+        disruption_budget_changed: old_spec.pod_disruption_budget != new_spec.pod_disruption_budget,
+        autoscaling_changed: old_spec.autoscaling != new_spec.autoscaling,
+        workload_changed: old_spec.workload_type != new_spec.workload_type
+            || old_spec.volume_claim_templates != new_spec.volume_claim_templates,
+        storage_changed: old_spec.storage != new_spec.storage,
+        rollout_changed: old_spec.rollout != new_spec.rollout,
+    };
+
+    Ok(diff)
+}
+
+/// Validate scale down operation
+pub fn validate_scale_down(old: &MyResource, new: &MyResource) -> Result<()> {
+    let new_replicas = new.spec.replicas;
+
+    // Ensure we don't go below minimum
+    if new_replicas < MIN_REPLICAS {
+        return Err(Error::Validation(format!(
+            "cannot scale down to {} replicas, minimum is {}",
+            new_replicas, MIN_REPLICAS
+        )));
+    }
+
+    // Warn about aggressive scale down (more than 50% at once)
+    let old_replicas = old.spec.replicas;
+    let delta = old_replicas - new_replicas;
+    if old_replicas > 2 && delta > old_replicas / 2 {
+        tracing::warn!(
+            old_replicas,
+            new_replicas,
+            delta,
+            "Large scale down detected. Consider scaling down gradually."
+        );
+    }
+
+    Ok(())
+}
+
+/// Check if generation has changed (spec update)
+pub fn generation_changed(resource: &MyResource) -> bool {
+    let generation = resource.metadata.generation;
+    let observed = resource.status.as_ref().and_then(|s| s.observed_generation);
+
+    match (generation, observed) {
+        (Some(current_gen), Some(obs)) => current_gen != obs,
+        (Some(_), None) => true, // No observed generation, assume changed
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used, clippy::indexing_slicing)]
+mod tests {
+    use super::*;
+    use crate::crd::{
+        AutoscalingSpec, DisruptionBudgetSpec, ExecProbe, ExternalTrafficPolicy, GatewayRef,
+        HttpGetProbe, IngressPath, IngressSpec, MyResourceSpec, MyResourceStatus, RolloutSpec,
+        SecretKeyProjection, ServiceExposureSpec, ServicePortSpec, StorageSpec, TcpSocketProbe,
+    };
+    use k8s_openapi::apimachinery::pkg::api::resource::Quantity;
+    use k8s_openapi::apimachinery::pkg::apis::meta::v1::ObjectMeta;
+    use std::collections::BTreeMap;
+
+    fn create_test_resource(replicas: i32, message: &str) -> MyResource {
+        MyResource {
+            metadata: ObjectMeta {
+                name: Some("test".to_string()),
+                namespace: Some("default".to_string()),
+                generation: Some(1),
+                ..Default::default()
+            },
+            spec: MyResourceSpec {
+                replicas,
+                message: message.to_string(),
+                labels: BTreeMap::new(),
+                ..Default::default()
+            },
+            status: Some(MyResourceStatus::default()),
+        }
+    }
+
+    #[test]
+    fn test_validate_replicas_valid() {
+        let resource = create_test_resource(3, "test");
+        assert!(validate_spec(&resource).is_ok());
+    }
+
+    #[test]
+    fn test_validate_replicas_too_low() {
+        let resource = create_test_resource(0, "test");
+        assert!(validate_spec(&resource).is_err());
+    }
+
+    #[test]
+    fn test_validate_replicas_too_high() {
+        let resource = create_test_resource(100, "test");
+        assert!(validate_spec(&resource).is_err());
+    }
+
+    #[test]
+    fn test_validate_replicas_at_bounds() {
+        let min_resource = create_test_resource(MIN_REPLICAS, "test");
+        assert!(validate_spec(&min_resource).is_ok());
+
+        let max_resource = create_test_resource(MAX_REPLICAS, "test");
+        assert!(validate_spec(&max_resource).is_ok());
+    }
+
+    fn resources(requests: &[(&str, &str)], limits: &[(&str, &str)]) -> ResourceRequirements {
+        let quantities = |entries: &[(&str, &str)]| {
+            entries
+                .iter()
+                .map(|(k, v)| (k.to_string(), Quantity(v.to_string())))
+                .collect()
+        };
+        ResourceRequirements {
+            requests: Some(quantities(requests)),
+            limits: Some(quantities(limits)),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_validate_image() {
+        let mut resource = create_test_resource(1, "test");
+        resource.spec.image = "registry.example.com:5000/team/app@sha256:abc".to_string();
+        assert!(validate_spec(&resource).is_ok());
+
+        for image in ["", "nginx latest", "nginx:", "/nginx"] {
+            resource.spec.image = image.to_string();
+            assert!(
+                validate_spec(&resource).is_err(),
+                "{:?} should be invalid",
+                image
+            );
+        }
+    }
+
+    #[test]
+    fn test_validate_container_port() {
+        let mut resource = create_test_resource(1, "test");
+        resource.spec.container_port = 8080;
+        assert!(validate_spec(&resource).is_ok());
+
+        resource.spec.container_port = 0;
+        assert!(validate_spec(&resource).is_err());
+
+        resource.spec.container_port = 70000;
+        assert!(validate_spec(&resource).is_err());
+    }
+
+    #[test]
+    fn test_validate_image_pull_secrets() {
+        let mut resource = create_test_resource(1, "test");
+        let secret = |name: &str| LocalObjectReference {
+            name: name.to_string(),
+        };
+        resource.spec.image_pull_secrets = vec![secret("registry"), secret("mirror")];
+        assert!(validate_spec(&resource).is_ok());
+
+        resource.spec.image_pull_secrets = vec![secret("registry"), secret("registry")];
+        assert!(validate_spec(&resource).is_err());
+
+        resource.spec.image_pull_secrets = vec![secret("")];
+        assert!(validate_spec(&resource).is_err());
+    }
+
+    #[test]
+    fn test_validate_extra_containers() {
+        use k8s_openapi::api::core::v1::{Container, ContainerPort};
+
+        let container = |name: &str, port: Option<(i32, &str)>| Container {
+            name: name.to_string(),
+            image: Some("busybox:1.36".to_string()),
+            ports: port.map(|(container_port, name)| {
+                vec![ContainerPort {
+                    container_port,
+                    name: Some(name.to_string()),
+                    ..Default::default()
+                }]
+            }),
+            ..Default::default()
+        };
+
+        let mut resource = create_test_resource(1, "test");
+        resource.spec.init_containers = vec![container("migrate", Some((80, "http")))];
+        resource.spec.sidecars = vec![container("logs", Some((9100, "metrics")))];
+        assert!(validate_spec(&resource).is_ok());
+
+        // Names are unique across the pod, including the main container
+        resource.spec.sidecars = vec![container("main", None)];
+        assert!(validate_spec(&resource).is_err());
+        resource.spec.sidecars = vec![container("migrate", None)];
+        assert!(validate_spec(&resource).is_err());
+        resource.spec.sidecars = vec![container("Logs", None)];
+        assert!(validate_spec(&resource).is_err());
+
+        // Sidecar ports can't clash with the main container or each other
+        resource.spec.sidecars = vec![container("logs", Some((80, "logs")))];
+        assert!(validate_spec(&resource).is_err());
+        resource.spec.sidecars = vec![container("logs", Some((9100, "http")))];
+        assert!(validate_spec(&resource).is_err());
+        resource.spec.sidecars = vec![
+            container("logs", Some((9100, "logs"))),
+            container("proxy", Some((9100, "proxy"))),
+        ];
+        assert!(validate_spec(&resource).is_err());
+
+        resource.spec.sidecars = vec![Container {
+            image: None,
+            ..container("logs", None)
+        }];
+        assert!(validate_spec(&resource).is_err());
+    }
+
+    #[test]
+    fn test_validate_resources() {
+        let mut resource = create_test_resource(1, "test");
+        resource.spec.resources = Some(resources(
+            &[("cpu", "0.5"), ("memory", "256Mi")],
+            &[("cpu", "1"), ("memory", "1Gi")],
+        ));
+        assert!(validate_spec(&resource).is_ok());
+
+        // Request above limit
+        resource.spec.resources = Some(resources(&[("cpu", "2")], &[("cpu", "1500m")]));
+        assert!(validate_spec(&resource).is_err());
+
+        // Malformed quantity
+        resource.spec.resources = Some(resources(&[("memory", "lots")], &[]));
+        assert!(validate_spec(&resource).is_err());
+    }
+
+    #[test]
+    fn test_validate_probes() {
+        let mut resource = create_test_resource(1, "test");
+        let http = ProbeSpec {
+            http_get: Some(HttpGetProbe {
+                path: "/healthz".to_string(),
+                port: Some(8080),
+                scheme: None,
+            }),
+            period_seconds: Some(5),
+            ..Default::default()
+        };
+        resource.spec.probes = Some(ProbesSpec {
+            readiness: Some(http.clone()),
+            liveness: Some(ProbeSpec {
+                exec: Some(ExecProbe {
+                    command: vec!["cat".to_string(), "/tmp/healthy".to_string()],
+                }),
+                ..Default::default()
+            }),
+            startup: Some(ProbeSpec::default()),
+        });
+        assert!(validate_spec(&resource).is_ok());
+
+        // Two handlers
+        let invalid = |probe: ProbeSpec| ProbesSpec {
+            readiness: Some(probe),
+            ..Default::default()
+        };
+        resource.spec.probes = Some(invalid(ProbeSpec {
+            tcp_socket: Some(TcpSocketProbe { port: None }),
+            ..http.clone()
+        }));
+        assert!(validate_spec(&resource).is_err());
+
+        // Relative path
+        resource.spec.probes = Some(invalid(ProbeSpec {
+            http_get: Some(HttpGetProbe {
+                path: "healthz".to_string(),
+                ..Default::default()
+            }),
+            ..Default::default()
+        }));
+        assert!(validate_spec(&resource).is_err());
+
+        // Empty command
+        resource.spec.probes = Some(invalid(ProbeSpec {
+            exec: Some(ExecProbe { command: vec![] }),
+            ..Default::default()
+        }));
+        assert!(validate_spec(&resource).is_err());
+
+        // Zero period
+        resource.spec.probes = Some(invalid(ProbeSpec {
+            period_seconds: Some(0),
+            ..Default::default()
+        }));
+        assert!(validate_spec(&resource).is_err());
+
+        // Liveness requires a success threshold of 1
+        resource.spec.probes = Some(ProbesSpec {
+            liveness: Some(ProbeSpec {
+                success_threshold: Some(2),
+                ..Default::default()
+            }),
+            ..Default::default()
+        });
+        assert!(validate_spec(&resource).is_err());
+    }
+
+    #[test]
+    fn test_validate_tolerations() {
+        let mut resource = create_test_resource(1, "test");
+        resource.spec.tolerations = vec![
+            Toleration {
+                key: Some("dedicated".to_string()),
+                operator: Some("Equal".to_string()),
+                value: Some("batch".to_string()),
+                effect: Some("NoSchedule".to_string()),
+                ..Default::default()
+            },
+            Toleration {
+                key: Some("node.kubernetes.io/unreachable".to_string()),
+                operator: Some("Exists".to_string()),
+                effect: Some("NoExecute".to_string()),
+                toleration_seconds: Some(30),
+                ..Default::default()
+            },
+        ];
+        assert!(validate_spec(&resource).is_ok());
+
+        // Exists with a value
+        resource.spec.tolerations = vec![Toleration {
+            operator: Some("Exists".to_string()),
+            value: Some("batch".to_string()),
+            ..Default::default()
+        }];
+        assert!(validate_spec(&resource).is_err());
+
+        // tolerationSeconds without NoExecute
+        resource.spec.tolerations = vec![Toleration {
+            effect: Some("NoSchedule".to_string()),
+            toleration_seconds: Some(30),
+            ..Default::default()
+        }];
+        assert!(validate_spec(&resource).is_err());
+    }
+
+    #[test]
+    fn test_validate_topology_spread_constraints() {
+        let mut resource = create_test_resource(3, "test");
+        let constraint = TopologySpreadConstraint {
+            max_skew: 1,
+            topology_key: "topology.kubernetes.io/zone".to_string(),
+            when_unsatisfiable: "ScheduleAnyway".to_string(),
+            ..Default::default()
+        };
+        resource.spec.topology_spread_constraints = vec![constraint.clone()];
+        assert!(validate_spec(&resource).is_ok());
+
+        resource.spec.topology_spread_constraints = vec![TopologySpreadConstraint {
+            max_skew: 0,
+            ..constraint.clone()
+        }];
+        assert!(validate_spec(&resource).is_err());
+
+        resource.spec.topology_spread_constraints = vec![TopologySpreadConstraint {
+            when_unsatisfiable: "Sometimes".to_string(),
+            ..constraint
+        }];
+        assert!(validate_spec(&resource).is_err());
+    }
+
+    #[test]
+    fn test_validate_secret_refs() {
+        let mut resource = create_test_resource(1, "test");
+        let key = |key: &str, target: Option<&str>| SecretKeyProjection {
+            key: key.to_string(),
+            target: target.map(String::from),
+        };
+        resource.spec.secret_refs = vec![
+            SecretRef {
+                name: "db".to_string(),
+                projection: SecretProjection::Env,
+                keys: vec![key("password", Some("DB_PASSWORD"))],
+            },
+            SecretRef {
+                name: "tls".to_string(),
+                projection: SecretProjection::File,
+                keys: vec![key("tls.crt", Some("certs/tls.crt"))],
+            },
+            SecretRef {
+                name: "api".to_string(),
+                ..Default::default()
+            },
+        ];
+        resource.spec.generated_secret = Some(GeneratedSecretSpec::default());
+        assert!(validate_spec(&resource).is_ok());
+
+        let invalid = [
+            // Repeated Secret
+            vec![
+                SecretRef {
+                    name: "db".to_string(),
+                    ..Default::default()
+                };
+                2
+            ],
+            // Invalid environment variable name
+            vec![SecretRef {
+                name: "db".to_string(),
+                projection: SecretProjection::Env,
+                keys: vec![key("password", Some("1PASSWORD"))],
+            }],
+            // Shadows MESSAGE
+            vec![SecretRef {
+                name: "db".to_string(),
+                projection: SecretProjection::Env,
+                keys: vec![key("MESSAGE", None)],
+            }],
+            // Escaping file path
+            vec![SecretRef {
+                name: "tls".to_string(),
+                projection: SecretProjection::File,
+                keys: vec![key("tls.crt", Some("../tls.crt"))],
+            }],
+        ];
+        for secret_refs in invalid {
+            resource.spec.secret_refs = secret_refs;
+            assert!(validate_spec(&resource).is_err());
+        }
+
+        resource.spec.secret_refs = Vec::new();
+        resource.spec.generated_secret = Some(GeneratedSecretSpec {
+            length: MIN_PASSWORD_LENGTH - 1,
+            ..Default::default()
+        });
+        assert!(validate_spec(&resource).is_err());
+    }
+
+    #[test]
+    fn test_validate_service() {
+        let mut resource = create_test_resource(1, "test");
+        let port = |name: Option<&str>, port: i32| ServicePortSpec {
+            name: name.map(String::from),
+            port,
+            ..Default::default()
+        };
+        resource.spec.service = Some(ServiceExposureSpec {
+            type_: ServiceType::LoadBalancer,
+            ports: vec![
+                port(Some("http"), 80),
+                ServicePortSpec {
+                    node_port: Some(30443),
+                    target_port: Some(8443),
+                    protocol: Some("TCP".to_string()),
+                    ..port(Some("https"), 443)
+                },
+            ],
+            external_traffic_policy: Some(ExternalTrafficPolicy::Local),
+            ..Default::default()
+        });
+        assert!(validate_spec(&resource).is_ok());
+
+        let invalid = [
+            // Unnamed port among several
+            ServiceExposureSpec {
+                ports: vec![port(Some("http"), 80), port(None, 443)],
+                ..Default::default()
+            },
+            // Repeated name
+            ServiceExposureSpec {
+                ports: vec![port(Some("http"), 80), port(Some("http"), 443)],
+                ..Default::default()
+            },
+            // Port out of range
+            ServiceExposureSpec {
+                ports: vec![port(None, 0)],
+                ..Default::default()
+            },
+            // Node port on a ClusterIP Service
+            ServiceExposureSpec {
+                ports: vec![ServicePortSpec {
+                    node_port: Some(30080),
+                    ..port(None, 80)
+                }],
+                ..Default::default()
+            },
+            // External traffic policy on a headless Service
+            ServiceExposureSpec {
+                type_: ServiceType::Headless,
+                external_traffic_policy: Some(ExternalTrafficPolicy::Cluster),
+                ..Default::default()
+            },
+        ];
+        for service in invalid {
+            resource.spec.service = Some(service);
+            assert!(validate_spec(&resource).is_err());
+        }
+    }
+
+    #[test]
+    fn test_validate_ingress() {
+        let mut resource = create_test_resource(1, "test");
+        let valid = IngressSpec {
+            hosts: vec![
+                "app.example.com".to_string(),
+                "*.apps.example.com".to_string(),
+            ],
+            paths: vec![IngressPath {
+                path: "/api".to_string(),
+                path_type: PathType::Exact,
+            }],
+            tls_secret_name: Some("app-tls".to_string()),
+            class_name: Some("nginx".to_string()),
+            port: Some(80),
+            ..Default::default()
+        };
+        resource.spec.ingress = Some(valid.clone());
+        assert!(validate_spec(&resource).is_ok());
+
+        let route = IngressSpec {
+            kind: RouteKind::HTTPRoute,
+            hosts: valid.hosts.clone(),
+            gateway: Some(GatewayRef {
+                name: "public".to_string(),
+                namespace: Some("gateways".to_string()),
+                section_name: None,
+            }),
+            ..Default::default()
+        };
+        resource.spec.ingress = Some(route.clone());
+        assert!(validate_spec(&resource).is_ok());
+
+        let invalid = [
+            IngressSpec {
+                hosts: vec!["App.Example.com".to_string()],
+                ..Default::default()
+            },
+            IngressSpec {
+                paths: vec![IngressPath {
+                    path: "api".to_string(),
+                    ..Default::default()
+                }],
+                ..Default::default()
+            },
+            IngressSpec {
+                port: Some(8080),
+                ..Default::default()
+            },
+            // HTTPRoute needs a Gateway
+            IngressSpec {
+                kind: RouteKind::HTTPRoute,
+                ..Default::default()
+            },
+            // TLS lives on the Gateway
+            IngressSpec {
+                tls_secret_name: Some("app-tls".to_string()),
+                ..route
+            },
+        ];
+        for ingress in invalid {
+            resource.spec.ingress = Some(ingress);
+            assert!(validate_spec(&resource).is_err());
+        }
+    }
+
+    #[test]
+    fn test_validate_rollout() {
+        let mut resource = create_test_resource(2, "test");
+        let rolling = |max_surge: Option<IntOrString>, max_unavailable: Option<IntOrString>| {
+            Some(RolloutSpec {
+                max_surge,
+                max_unavailable,
+                ..Default::default()
+            })
+        };
+
+        for valid in [
+            rolling(Some(IntOrString::Int(1)), Some(IntOrString::Int(0))),
+            rolling(Some(IntOrString::String("150%".to_string())), None),
+            Some(RolloutSpec {
+                strategy: RolloutStrategy::Recreate,
+                progress_deadline_seconds: Some(120),
+                ..Default::default()
+            }),
+        ] {
+            resource.spec.rollout = valid;
+            assert!(validate_spec(&resource).is_ok());
+        }
+
+        for invalid in [
+            rolling(Some(IntOrString::Int(0)), Some(IntOrString::Int(0))),
+            rolling(None, Some(IntOrString::String("120%".to_string()))),
+            rolling(Some(IntOrString::Int(-1)), None),
+            rolling(Some(IntOrString::String("lots".to_string())), None),
+            Some(RolloutSpec {
+                strategy: RolloutStrategy::Recreate,
+                max_surge: Some(IntOrString::Int(1)),
+                ..Default::default()
+            }),
+            Some(RolloutSpec {
+                progress_deadline_seconds: Some(0),
+                ..Default::default()
+            }),
+        ] {
+            resource.spec.rollout = invalid;
+            assert!(validate_spec(&resource).is_err());
+        }
+
+        // StatefulSets have their own update strategy
+        resource.spec.rollout = Some(RolloutSpec::default());
+        resource.spec.workload_type = WorkloadType::StatefulSet;
+        assert!(validate_spec(&resource).is_err());
+    }
+
+    #[test]
+    fn test_validate_disruption_budget() {
+        let mut resource = create_test_resource(3, "test");
+        let budget = |min: Option<IntOrString>, max: Option<IntOrString>| DisruptionBudgetSpec {
+            min_available: min,
+            max_unavailable: max,
+        };
+        let percent = |p: &str| Some(IntOrString::String(p.to_string()));
+
+        for valid in [
+            budget(Some(IntOrString::Int(2)), None),
+            budget(percent("50%"), None),
+            budget(None, Some(IntOrString::Int(1))),
+            budget(None, percent("10%")),
+        ] {
+            resource.spec.pod_disruption_budget = Some(valid);
+            assert!(validate_spec(&resource).is_ok());
+        }
+
+        for invalid in [
+            budget(Some(IntOrString::Int(1)), Some(IntOrString::Int(1))),
+            // Blocks all evictions
+            budget(Some(IntOrString::Int(3)), None),
+            budget(percent("70%"), None),
+            budget(None, Some(IntOrString::Int(0))),
+            budget(None, percent("0%")),
+            // Malformed
+            budget(percent("150%"), None),
+            budget(percent("half"), None),
+            budget(Some(IntOrString::Int(-1)), None),
+        ] {
+            resource.spec.pod_disruption_budget = Some(invalid);
+            assert!(validate_spec(&resource).is_err());
+        }
+
+        // No budget is created for a single replica
+        resource.spec.replicas = 1;
+        resource.spec.pod_disruption_budget = Some(budget(Some(IntOrString::Int(1)), None));
+        assert!(validate_spec(&resource).is_ok());
+
+        // With autoscaling the budget must allow evictions at minReplicas
+        resource.spec.autoscaling = Some(AutoscalingSpec {
+            max_replicas: 5,
+            ..Default::default()
+        });
+        assert!(validate_spec(&resource).is_err());
+        resource.spec.pod_disruption_budget = Some(budget(None, Some(IntOrString::Int(1))));
+        assert!(validate_spec(&resource).is_ok());
+    }
+
+    #[test]
+    fn test_validate_workload() {
+        use k8s_openapi::api::core::v1::{
+            PersistentVolumeClaim, PersistentVolumeClaimSpec, VolumeResourceRequirements,
+        };
+
+        let claim = |name: &str, storage: Option<&str>| PersistentVolumeClaim {
+            metadata: ObjectMeta {
+                name: Some(name.to_string()),
+                ..Default::default()
+            },
+            spec: Some(PersistentVolumeClaimSpec {
+                resources: storage.map(|size| VolumeResourceRequirements {
+                    requests: Some(
+                        [("storage".to_string(), Quantity(size.to_string()))]
+                            .into_iter()
+                            .collect(),
+                    ),
+                    ..Default::default()
+                }),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+        let mut resource = create_test_resource(3, "test");
+        resource.spec.volume_claim_templates = vec![claim("data", Some("1Gi"))];
+
+        // Volume claims need a StatefulSet
+        assert!(validate_spec(&resource).is_err());
+        resource.spec.workload_type = WorkloadType::StatefulSet;
+        assert!(validate_spec(&resource).is_ok());
+
+        for invalid in [
+            vec![claim("Data", Some("1Gi"))],
+            vec![claim("config", Some("1Gi"))],
+            vec![claim("secret-0", Some("1Gi"))],
+            vec![claim("data", Some("1Gi")), claim("data", Some("2Gi"))],
+            vec![claim("data", None)],
+        ] {
+            resource.spec.volume_claim_templates = invalid;
+            assert!(validate_spec(&resource).is_err());
+        }
+    }
+
+    #[test]
+    fn test_validate_storage() {
+        let mut resource = create_test_resource(1, "test");
+        let storage = |size: &str, mount_path: &str| StorageSpec {
+            size: Quantity(size.to_string()),
+            mount_path: mount_path.to_string(),
+            ..Default::default()
+        };
+
+        for valid in [
+            StorageSpec::default(),
+            storage("10Gi", "/srv/data"),
+            StorageSpec {
+                access_modes: vec![VolumeAccessMode::ReadWriteOncePod],
+                ..Default::default()
+            },
+        ] {
+            resource.spec.storage = Some(valid);
+            assert!(validate_spec(&resource).is_ok());
+        }
+
+        for invalid in [
+            storage("0", "/srv/data"),
+            storage("lots", "/srv/data"),
+            storage("1Gi", "relative"),
+            storage("1Gi", "/"),
+            storage("1Gi", "/etc/config"),
+            storage("1Gi", "/etc/secrets/db"),
+            storage("1Gi", "/etc"),
+            StorageSpec {
+                access_modes: Vec::new(),
+                ..Default::default()
+            },
+        ] {
+            resource.spec.storage = Some(invalid);
+            assert!(validate_spec(&resource).is_err());
+        }
+
+        // Single-pod and single-node volumes can't be shared by several replicas
+        resource.spec.replicas = 2;
+        for access_modes in [
+            vec![VolumeAccessMode::ReadWriteOncePod],
+            vec![VolumeAccessMode::ReadWriteOnce],
+        ] {
+            resource.spec.storage = Some(StorageSpec {
+                access_modes,
+                ..Default::default()
+            });
+            assert!(validate_spec(&resource).is_err());
+        }
+        resource.spec.storage = Some(StorageSpec {
+            access_modes: vec![VolumeAccessMode::ReadWriteMany],
+            ..Default::default()
+        });
+        assert!(validate_spec(&resource).is_ok());
+
+        // Autoscaling beyond one replica counts as well
+        resource.spec.replicas = 1;
+        resource.spec.autoscaling = Some(AutoscalingSpec {
+            min_replicas: Some(1),
+            max_replicas: 3,
+            ..Default::default()
+        });
+        resource.spec.storage = Some(StorageSpec::default());
+        assert!(validate_spec(&resource).is_err());
+    }
+
+    #[test]
+    fn test_validate_autoscaling() {
+        let mut resource = create_test_resource(2, "test");
+        let autoscaling = |min: Option<i32>, max: i32| AutoscalingSpec {
+            min_replicas: min,
+            max_replicas: max,
+            ..Default::default()
+        };
+
+        for valid in [
+            autoscaling(None, 2),
+            autoscaling(Some(1), MAX_REPLICAS),
+            AutoscalingSpec {
+                target_memory_utilization: Some(70),
+                ..autoscaling(None, 4)
+            },
+        ] {
+            resource.spec.autoscaling = Some(valid);
+            assert!(validate_spec(&resource).is_ok());
+        }
+
+        for invalid in [
+            autoscaling(None, MAX_REPLICAS + 1),
+            autoscaling(Some(0), 4),
+            // minReplicas defaults to spec.replicas
+            autoscaling(None, 1),
+            autoscaling(Some(5), 4),
+            AutoscalingSpec {
+                target_cpu_utilization: Some(0),
+                ..autoscaling(None, 4)
+            },
+        ] {
+            resource.spec.autoscaling = Some(invalid);
+            assert!(validate_spec(&resource).is_err());
+        }
+
+        // Utilization targets need a matching request
+        resource.spec.resources = Some(ResourceRequirements {
+            requests: Some(
+                [("cpu".to_string(), Quantity("100m".to_string()))]
+                    .into_iter()
+                    .collect(),
+            ),
+            ..Default::default()
+        });
+        resource.spec.autoscaling = Some(AutoscalingSpec {
+            target_cpu_utilization: Some(60),
+            ..autoscaling(None, 4)
+        });
+        assert!(validate_spec(&resource).is_ok());
+        resource.spec.autoscaling = Some(AutoscalingSpec {
+            target_memory_utilization: Some(60),
+            ..autoscaling(None, 4)
+        });
+        assert!(validate_spec(&resource).is_err());
+    }
+
+    #[test]
+    fn test_validate_config() {
+        let mut resource = create_test_resource(1, "test");
+        resource.spec.config = [
+            ("app.yaml".to_string(), "port: 8080".to_string()),
+            ("feature_flags-v2.json".to_string(), "{}".to_string()),
+        ]
+        .into();
+        assert!(validate_spec(&resource).is_ok());
+
+        for key in ["", "..", "conf/app.yaml", "app yaml", MESSAGE_KEY] {
+            resource.spec.config = [(key.to_string(), String::new())].into();
+            assert!(validate_spec(&resource).is_err(), "key {:?}", key);
+        }
+
+        resource.spec.config = [("big".to_string(), "x".repeat(MAX_CONFIG_BYTES))].into();
+        assert!(validate_spec(&resource).is_err());
+    }
+
+    #[test]
+    fn test_spec_diff_config_change() {
+        let old = create_test_resource(2, "test");
+        let mut new = create_test_resource(2, "test");
+        new.spec.config = [("app.yaml".to_string(), "debug: true".to_string())].into();
+
+        let diff = validate_spec_change(&old, &new).unwrap();
+        assert!(diff.config_changed);
+        assert!(diff.requires_update());
+    }
+
+    #[test]
+    fn test_parse_quantity() {
+        assert_eq!(parse_quantity("500m"), Some(0.5));
+        assert_eq!(parse_quantity("2"), Some(2.0));
+        assert_eq!(parse_quantity("1.5"), Some(1.5));
+        assert_eq!(parse_quantity("1Ki"), Some(1024.0));
+        assert_eq!(parse_quantity("128Mi"), Some(134_217_728.0));
+        assert_eq!(parse_quantity("1k"), Some(1000.0));
+        assert_eq!(parse_quantity("1e3"), Some(1000.0));
+        assert_eq!(parse_quantity("1E"), Some(1e18));
+        assert_eq!(parse_quantity(""), None);
+        assert_eq!(parse_quantity("-1"), None);
+        assert_eq!(parse_quantity("inf"), None);
+        assert_eq!(parse_quantity("10Xi"), None);
+    }
+
+    #[test]
+    fn test_spec_diff_container_change() {
+        let old = create_test_resource(2, "test");
+        let mut new = create_test_resource(3, "test");
+        new.spec.image = "nginx:1.27".to_string();
+
+        let diff = validate_spec_change(&old, &new).unwrap();
+        assert!(diff.container_changed);
+        assert!(diff.requires_update());
+        assert!(!diff.is_scale_only());
     }
 
     #[test]
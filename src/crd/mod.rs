@@ -2,6 +2,14 @@
 //!
 //! This module defines the `MyResource` CRD using kube-rs derive macros.
 
+use std::collections::BTreeMap;
+
+use k8s_openapi::api::core::v1::{
+    Affinity, Container, LocalObjectReference, PersistentVolumeClaim, ResourceRequirements,
+    Toleration, TopologySpreadConstraint,
+};
+use k8s_openapi::apimachinery::pkg::api::resource::Quantity;
+use k8s_openapi::apimachinery::pkg::util::intstr::IntOrString;
 use kube::CustomResource;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
@@ -17,8 +25,10 @@ use serde::{Deserialize, Serialize};
 /// spec:
 ///   replicas: 3
 ///   message: "Hello, World!"
+///   image: nginx:alpine
+///   containerPort: 80
 /// ```
-#[derive(CustomResource, Clone, Debug, Default, Deserialize, Serialize, JsonSchema)]
+#[derive(CustomResource, Clone, Debug, PartialEq, Deserialize, Serialize, JsonSchema)]
 #[kube(
     group = "myoperator.example.com",
     version = "v1alpha1",
@@ -26,6 +36,11 @@ use serde::{Deserialize, Serialize};
     plural = "myresources",
     shortname = "mr",
     status = "MyResourceStatus",
+    scale(
+        spec_replicas_path = ".spec.replicas",
+        status_replicas_path = ".status.readyReplicas",
+        label_selector_path = ".status.selector"
+    ),
     namespaced,
     // Print columns for kubectl get
     printcolumn = r#"{"name":"Phase", "type":"string", "jsonPath":".status.phase"}"#,
@@ -35,25 +50,894 @@ use serde::{Deserialize, Serialize};
 )]
 #[serde(rename_all = "camelCase")]
 pub struct MyResourceSpec {
-    /// Number of replicas for the managed deployment
+    /// Number of replicas for the managed deployment. With `autoscaling`
+    /// the HorizontalPodAutoscaler owns the replica count and this is only
+    /// the default for `autoscaling.minReplicas`.
     #[serde(default = "default_replicas")]
     pub replicas: i32,
 
-    /// A message to be stored in the managed ConfigMap
+    /// A message stored in the managed ConfigMap (as the `message` file)
     #[serde(default)]
     pub message: String,
 
     /// Custom labels to apply to managed resources
     #[serde(default)]
-    pub labels: std::collections::BTreeMap<String, String>,
+    pub labels: BTreeMap<String, String>,
+
+    /// How out-of-band changes to managed resources are handled
+    #[serde(default)]
+    pub drift_policy: DriftPolicy,
+
+    /// Container image to run
+    #[serde(default = "default_image")]
+    pub image: String,
+
+    /// Image pull policy for the container (defaults to the Kubernetes default)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub image_pull_policy: Option<ImagePullPolicy>,
+
+    /// Secrets used to pull the container image
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub image_pull_secrets: Vec<LocalObjectReference>,
+
+    /// Port the container listens on, exposed by the managed Service
+    #[serde(default = "default_container_port")]
+    pub container_port: i32,
+
+    /// Compute resources for the container (defaults to small requests and limits)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub resources: Option<ResourceRequirements>,
+
+    /// Node labels the pods must be scheduled onto
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub node_selector: BTreeMap<String, String>,
+
+    /// Tolerations for node taints
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tolerations: Vec<Toleration>,
+
+    /// Pod scheduling affinity. When unset and `replicas > 1`, pods prefer to be
+    /// spread across nodes.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub affinity: Option<Affinity>,
+
+    /// How pods are spread across topology domains such as zones
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub topology_spread_constraints: Vec<TopologySpreadConstraint>,
+
+    /// Priority class of the pods
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub priority_class_name: Option<String>,
+
+    /// Probes for the container. Unset liveness and readiness probes default
+    /// to a TCP check on `containerPort`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub probes: Option<ProbesSpec>,
+
+    /// Containers run to completion, in order, before the main container
+    /// starts (e.g. database migrations). Unset security context fields get
+    /// the same hardened defaults as the main container.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub init_containers: Vec<Container>,
+
+    /// Containers run next to the main container in every pod (e.g. log
+    /// shippers). Unset security context fields get the same hardened
+    /// defaults as the main container.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub sidecars: Vec<Container>,
+
+    /// Extra files for the managed ConfigMap, keyed by file name. The
+    /// ConfigMap is mounted read-only at `/etc/config` in every pod.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub config: BTreeMap<String, String>,
+
+    /// Existing Secrets whose keys are exposed to the container. Changes to
+    /// these Secrets roll the pods.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub secret_refs: Vec<SecretRef>,
+
+    /// A Secret with a random password, generated once and owned by the
+    /// operator. It is mounted at `/etc/secrets/<name>-generated`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub generated_secret: Option<GeneratedSecretSpec>,
+
+    /// How the managed Service exposes the pods (defaults to a ClusterIP
+    /// Service with a single `http` port on `containerPort`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub service: Option<ServiceExposureSpec>,
+
+    /// Route external HTTP traffic to the Service through an Ingress or a
+    /// Gateway API HTTPRoute. Removing the block deletes the route.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ingress: Option<IngressSpec>,
+
+    /// PodDisruptionBudget created while `replicas > 1` (defaults to
+    /// `maxUnavailable: 1`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pod_disruption_budget: Option<DisruptionBudgetSpec>,
+
+    /// Scale the Deployment with a HorizontalPodAutoscaler instead of a
+    /// fixed `replicas` count
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub autoscaling: Option<AutoscalingSpec>,
+
+    /// How the Deployment rolls out pod template changes, and how long a
+    /// rollout may stall before the resource is marked Failed
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rollout: Option<RolloutSpec>,
+
+    /// Kind of workload running the pods. Changing it on an existing resource
+    /// requires the `myoperator.example.com/allow-workload-migration`
+    /// annotation.
+    #[serde(default)]
+    pub workload_type: WorkloadType,
+
+    /// PersistentVolumeClaims created per replica (StatefulSet only), each
+    /// mounted at `/data/<name>`
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub volume_claim_templates: Vec<PersistentVolumeClaim>,
+
+    /// PersistentVolumeClaim managed by the operator and shared by all
+    /// replicas, mounted at `storage.mountPath`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub storage: Option<StorageSpec>,
+}
+
+impl Default for MyResourceSpec {
+    fn default() -> Self {
+        Self {
+            replicas: default_replicas(),
+            message: String::new(),
+            labels: BTreeMap::new(),
+            drift_policy: DriftPolicy::default(),
+            image: default_image(),
+            image_pull_policy: None,
+            image_pull_secrets: Vec::new(),
+            container_port: default_container_port(),
+            resources: None,
+            node_selector: BTreeMap::new(),
+            tolerations: Vec::new(),
+            affinity: None,
+            topology_spread_constraints: Vec::new(),
+            priority_class_name: None,
+            probes: None,
+            init_containers: Vec::new(),
+            sidecars: Vec::new(),
+            config: BTreeMap::new(),
+            secret_refs: Vec::new(),
+            generated_secret: None,
+            service: None,
+            ingress: None,
+            pod_disruption_budget: None,
+            autoscaling: None,
+            rollout: None,
+            workload_type: WorkloadType::default(),
+            volume_claim_templates: Vec::new(),
+            storage: None,
+        }
+    }
+}
+
+impl MyResourceSpec {
+    /// Lowest replica count the workload can run with
+    pub fn min_replicas(&self) -> i32 {
+        match self.autoscaling {
+            Some(ref autoscaling) => autoscaling.min_replicas.unwrap_or(self.replicas),
+            None => self.replicas,
+        }
+    }
+
+    /// Highest replica count the workload can run with
+    pub fn max_replicas(&self) -> i32 {
+        match self.autoscaling {
+            Some(ref autoscaling) => autoscaling.max_replicas,
+            None => self.replicas,
+        }
+    }
 }
 
 fn default_replicas() -> i32 {
     1
 }
 
+fn default_image() -> String {
+    DEFAULT_IMAGE.to_string()
+}
+
+fn default_container_port() -> i32 {
+    DEFAULT_CONTAINER_PORT
+}
+
+/// Image used when `spec.image` is not set
+pub const DEFAULT_IMAGE: &str = "nginx:alpine";
+
+/// CPU utilization targeted by the HorizontalPodAutoscaler when
+/// `spec.autoscaling` sets no target
+pub const DEFAULT_CPU_UTILIZATION: i32 = 80;
+
+/// Seconds a Deployment rollout may go without progress when
+/// `spec.rollout.progressDeadlineSeconds` is not set (the Kubernetes default)
+pub const DEFAULT_PROGRESS_DEADLINE_SECONDS: i32 = 600;
+
+/// Container port used when `spec.containerPort` is not set
+pub const DEFAULT_CONTAINER_PORT: i32 = 80;
+
+/// Probes for the managed container
+#[derive(Clone, Debug, Default, PartialEq, Deserialize, Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ProbesSpec {
+    /// Restart the container when this probe fails
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub liveness: Option<ProbeSpec>,
+
+    /// Remove the pod from Service endpoints when this probe fails
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub readiness: Option<ProbeSpec>,
+
+    /// Hold off the other probes until this probe succeeds (no default)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub startup: Option<ProbeSpec>,
+}
+
+/// A single container probe.
+///
+/// At most one of `httpGet`, `tcpSocket` and `exec` may be set. If none is set,
+/// a TCP check on `containerPort` is used.
+#[derive(Clone, Debug, Default, PartialEq, Deserialize, Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ProbeSpec {
+    /// Probe with an HTTP GET request
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub http_get: Option<HttpGetProbe>,
+
+    /// Probe by opening a TCP connection
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tcp_socket: Option<TcpSocketProbe>,
+
+    /// Probe by running a command in the container
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub exec: Option<ExecProbe>,
+
+    /// Seconds after the container starts before the probe runs
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub initial_delay_seconds: Option<i32>,
+
+    /// How often the probe runs, in seconds
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub period_seconds: Option<i32>,
+
+    /// Seconds after which the probe times out
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timeout_seconds: Option<i32>,
+
+    /// Consecutive failures before the probe is considered failed
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub failure_threshold: Option<i32>,
+
+    /// Consecutive successes before the probe is considered successful
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub success_threshold: Option<i32>,
+}
+
+/// HTTP GET probe settings
+#[derive(Clone, Debug, Default, PartialEq, Eq, Deserialize, Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct HttpGetProbe {
+    /// Path to request
+    #[serde(default = "default_probe_path")]
+    pub path: String,
+
+    /// Port to request (defaults to `containerPort`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub port: Option<i32>,
+
+    /// Scheme to use (HTTP or HTTPS)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub scheme: Option<String>,
+}
+
+fn default_probe_path() -> String {
+    "/".to_string()
+}
+
+/// TCP socket probe settings
+#[derive(Clone, Debug, Default, PartialEq, Eq, Deserialize, Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct TcpSocketProbe {
+    /// Port to connect to (defaults to `containerPort`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub port: Option<i32>,
+}
+
+/// Exec probe settings
+#[derive(Clone, Debug, Default, PartialEq, Eq, Deserialize, Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ExecProbe {
+    /// Command to run; exit status 0 means success
+    pub command: Vec<String>,
+}
+
+/// Reference to an existing Secret in the same namespace
+#[derive(Clone, Debug, Default, PartialEq, Eq, Deserialize, Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct SecretRef {
+    /// Name of the Secret
+    pub name: String,
+
+    /// How the keys are exposed to the container
+    #[serde(default)]
+    pub projection: SecretProjection,
+
+    /// Keys to expose. If empty, every key of the Secret is exposed.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub keys: Vec<SecretKeyProjection>,
+}
+
+/// A single Secret key exposed to the container
+#[derive(Clone, Debug, Default, PartialEq, Eq, Deserialize, Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct SecretKeyProjection {
+    /// Key in the Secret
+    pub key: String,
+
+    /// Environment variable name or relative file path (defaults to the key)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub target: Option<String>,
+}
+
+/// SecretProjection controls how Secret keys reach the container
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Deserialize, Serialize, JsonSchema)]
+pub enum SecretProjection {
+    /// Expose keys as environment variables
+    #[default]
+    Env,
+    /// Mount keys as files under `/etc/secrets/<secret name>`
+    File,
+}
+
+/// Settings for the operator-generated Secret
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct GeneratedSecretSpec {
+    /// Key the password is stored under
+    #[serde(default = "default_password_key")]
+    pub key: String,
+
+    /// Password length in characters
+    #[serde(default = "default_password_length")]
+    pub length: u32,
+
+    /// Characters the password is drawn from
+    #[serde(default)]
+    pub charset: PasswordCharset,
+}
+
+impl Default for GeneratedSecretSpec {
+    fn default() -> Self {
+        Self {
+            key: default_password_key(),
+            length: default_password_length(),
+            charset: PasswordCharset::default(),
+        }
+    }
+}
+
+fn default_password_key() -> String {
+    "password".to_string()
+}
+
+fn default_password_length() -> u32 {
+    32
+}
+
+/// PasswordCharset selects the characters of a generated password
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Deserialize, Serialize, JsonSchema)]
+pub enum PasswordCharset {
+    /// Letters and digits
+    #[default]
+    Alphanumeric,
+    /// Letters, digits and punctuation
+    Symbols,
+    /// Lowercase hexadecimal digits
+    Hex,
+}
+
+impl PasswordCharset {
+    /// Characters the password is drawn from
+    pub fn chars(&self) -> &'static [u8] {
+        const ALPHANUMERIC: &[u8] =
+            b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789";
+        const SYMBOLS: &[u8] =
+            b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789!#$%&*+-=?@^_~";
+        match self {
+            PasswordCharset::Alphanumeric => ALPHANUMERIC,
+            PasswordCharset::Symbols => SYMBOLS,
+            PasswordCharset::Hex => b"0123456789abcdef",
+        }
+    }
+}
+
+/// Settings for the managed Service
+#[derive(Clone, Debug, Default, PartialEq, Eq, Deserialize, Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ServiceExposureSpec {
+    /// Type of the Service
+    #[serde(default, rename = "type")]
+    pub type_: ServiceType,
+
+    /// Ports exposed by the Service. If empty, a single `http` port on
+    /// `containerPort` is exposed.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub ports: Vec<ServicePortSpec>,
+
+    /// Annotations for the Service, e.g. for cloud load balancer settings
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub annotations: BTreeMap<String, String>,
+
+    /// Session affinity of the Service
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub session_affinity: Option<SessionAffinity>,
+
+    /// Whether external traffic is routed to node-local or cluster-wide
+    /// endpoints (NodePort and LoadBalancer only)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub external_traffic_policy: Option<ExternalTrafficPolicy>,
+}
+
+/// A port exposed by the managed Service
+#[derive(Clone, Debug, Default, PartialEq, Eq, Deserialize, Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ServicePortSpec {
+    /// Name of the port (required when more than one port is exposed)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+
+    /// Port exposed by the Service
+    pub port: i32,
+
+    /// Container port traffic is forwarded to (defaults to `containerPort`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub target_port: Option<i32>,
+
+    /// Node port (NodePort and LoadBalancer only; allocated if unset)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub node_port: Option<i32>,
+
+    /// Protocol of the port (TCP, UDP or SCTP; defaults to TCP)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub protocol: Option<String>,
+}
+
+/// ServiceType selects how the managed Service is exposed
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Deserialize, Serialize, JsonSchema)]
+pub enum ServiceType {
+    /// Virtual IP reachable inside the cluster
+    #[default]
+    ClusterIP,
+    /// ClusterIP plus a port on every node
+    NodePort,
+    /// NodePort plus an external load balancer
+    LoadBalancer,
+    /// Headless Service without a virtual IP; DNS resolves to the pod IPs
+    #[serde(rename = "None")]
+    Headless,
+}
+
+impl ServiceType {
+    /// Whether the Service allocates node ports
+    pub fn has_node_ports(&self) -> bool {
+        matches!(self, ServiceType::NodePort | ServiceType::LoadBalancer)
+    }
+}
+
+impl std::fmt::Display for ServiceType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ServiceType::ClusterIP => write!(f, "ClusterIP"),
+            ServiceType::NodePort => write!(f, "NodePort"),
+            ServiceType::LoadBalancer => write!(f, "LoadBalancer"),
+            ServiceType::Headless => write!(f, "None"),
+        }
+    }
+}
+
+/// SessionAffinity controls whether a client sticks to one pod
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Deserialize, Serialize, JsonSchema)]
+pub enum SessionAffinity {
+    /// Requests are spread across pods
+    None,
+    /// Requests from a client IP go to the same pod
+    ClientIP,
+}
+
+impl std::fmt::Display for SessionAffinity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SessionAffinity::None => write!(f, "None"),
+            SessionAffinity::ClientIP => write!(f, "ClientIP"),
+        }
+    }
+}
+
+/// ExternalTrafficPolicy controls how external traffic reaches the pods
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Deserialize, Serialize, JsonSchema)]
+pub enum ExternalTrafficPolicy {
+    /// Route to pods on any node (obscures the client source IP)
+    Cluster,
+    /// Route only to pods on the receiving node (preserves the client source IP)
+    Local,
+}
+
+impl std::fmt::Display for ExternalTrafficPolicy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ExternalTrafficPolicy::Cluster => write!(f, "Cluster"),
+            ExternalTrafficPolicy::Local => write!(f, "Local"),
+        }
+    }
+}
+
+/// Settings for the generated PodDisruptionBudget. At most one of
+/// `minAvailable` and `maxUnavailable` may be set.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Deserialize, Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct DisruptionBudgetSpec {
+    /// Pods that must stay available during voluntary disruptions (a number
+    /// or a percentage)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min_available: Option<IntOrString>,
+
+    /// Pods that may be unavailable during voluntary disruptions (a number
+    /// or a percentage)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_unavailable: Option<IntOrString>,
+}
+
+/// Rollout settings of the Deployment. Only supported with the Deployment
+/// workload type.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Deserialize, Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct RolloutSpec {
+    /// How old pods are replaced (defaults to RollingUpdate)
+    #[serde(default)]
+    pub strategy: RolloutStrategy,
+
+    /// Pods that may be created above the desired count during a rolling
+    /// update (a number or a percentage, RollingUpdate only)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_surge: Option<IntOrString>,
+
+    /// Pods that may be unavailable during a rolling update (a number or a
+    /// percentage, RollingUpdate only)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_unavailable: Option<IntOrString>,
+
+    /// Seconds a rollout may go without progress before the Deployment
+    /// reports `ProgressDeadlineExceeded` and the resource is marked Failed
+    /// (defaults to 600)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub progress_deadline_seconds: Option<i32>,
+
+    /// Re-apply the resources of the last spec that reached Running when an
+    /// update fails
+    #[serde(default)]
+    pub auto_rollback: bool,
+}
+
+/// RolloutStrategy is how the Deployment replaces its pods
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Deserialize, Serialize, JsonSchema)]
+pub enum RolloutStrategy {
+    /// Replace pods gradually, bounded by `maxSurge` and `maxUnavailable`
+    #[default]
+    RollingUpdate,
+    /// Stop every old pod before starting new ones
+    Recreate,
+}
+
+impl std::fmt::Display for RolloutStrategy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RolloutStrategy::RollingUpdate => write!(f, "RollingUpdate"),
+            RolloutStrategy::Recreate => write!(f, "Recreate"),
+        }
+    }
+}
+
+/// Settings for the generated HorizontalPodAutoscaler. Without a utilization
+/// target the HPA scales on 80% average CPU utilization.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Deserialize, Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct AutoscalingSpec {
+    /// Lower bound for the replica count (defaults to `spec.replicas`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min_replicas: Option<i32>,
+
+    /// Upper bound for the replica count
+    pub max_replicas: i32,
+
+    /// Target average CPU utilization, as a percentage of the CPU request
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub target_cpu_utilization: Option<i32>,
+
+    /// Target average memory utilization, as a percentage of the memory
+    /// request
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub target_memory_utilization: Option<i32>,
+}
+
+impl AutoscalingSpec {
+    /// CPU utilization to scale on, falling back to `DEFAULT_CPU_UTILIZATION`
+    /// when no target is set
+    pub fn cpu_target(&self) -> Option<i32> {
+        match (self.target_cpu_utilization, self.target_memory_utilization) {
+            (None, None) => Some(DEFAULT_CPU_UTILIZATION),
+            (cpu, _) => cpu,
+        }
+    }
+}
+
+/// Settings for the generated Ingress or HTTPRoute
+#[derive(Clone, Debug, Default, PartialEq, Eq, Deserialize, Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct IngressSpec {
+    /// Kind of routing resource to generate
+    #[serde(default)]
+    pub kind: RouteKind,
+
+    /// Host names to route. If empty, all hosts are routed.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub hosts: Vec<String>,
+
+    /// Paths to route. If empty, every path (`/` as a prefix) is routed.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub paths: Vec<IngressPath>,
+
+    /// Secret holding the TLS certificate for the hosts (Ingress only; for an
+    /// HTTPRoute, TLS is configured on the Gateway)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tls_secret_name: Option<String>,
+
+    /// Ingress class (Ingress only)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub class_name: Option<String>,
+
+    /// Gateway the route attaches to (required for an HTTPRoute)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub gateway: Option<GatewayRef>,
+
+    /// Service port to route to (defaults to the first Service port)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub port: Option<i32>,
+
+    /// Annotations for the generated resource
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub annotations: BTreeMap<String, String>,
+}
+
+/// RouteKind selects the resource generated for `spec.ingress`
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Deserialize, Serialize, JsonSchema)]
+pub enum RouteKind {
+    /// A `networking.k8s.io/v1` Ingress
+    #[default]
+    Ingress,
+    /// A Gateway API `gateway.networking.k8s.io/v1` HTTPRoute
+    HTTPRoute,
+}
+
+impl std::fmt::Display for RouteKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RouteKind::Ingress => write!(f, "Ingress"),
+            RouteKind::HTTPRoute => write!(f, "HTTPRoute"),
+        }
+    }
+}
+
+/// A routed path
+#[derive(Clone, Debug, Default, PartialEq, Eq, Deserialize, Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct IngressPath {
+    /// Path to match, starting with `/`
+    pub path: String,
+
+    /// How the path is matched
+    #[serde(default)]
+    pub path_type: PathType,
+}
+
+/// PathType controls how a routed path is matched
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Deserialize, Serialize, JsonSchema)]
+pub enum PathType {
+    /// Match the path and everything below it
+    #[default]
+    Prefix,
+    /// Match the path exactly
+    Exact,
+    /// Leave matching to the Ingress controller (Ingress only)
+    ImplementationSpecific,
+}
+
+impl std::fmt::Display for PathType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PathType::Prefix => write!(f, "Prefix"),
+            PathType::Exact => write!(f, "Exact"),
+            PathType::ImplementationSpecific => write!(f, "ImplementationSpecific"),
+        }
+    }
+}
+
+/// Reference to the Gateway an HTTPRoute attaches to
+#[derive(Clone, Debug, Default, PartialEq, Eq, Deserialize, Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct GatewayRef {
+    /// Name of the Gateway
+    pub name: String,
+
+    /// Namespace of the Gateway (defaults to the MyResource namespace)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub namespace: Option<String>,
+
+    /// Listener of the Gateway to attach to (defaults to all listeners)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub section_name: Option<String>,
+}
+
+/// Settings for the operator-managed PersistentVolumeClaim
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct StorageSpec {
+    /// Requested size. It can grow (if the StorageClass allows volume
+    /// expansion) but never shrink.
+    pub size: Quantity,
+
+    /// StorageClass of the claim (defaults to the cluster default). Immutable.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub storage_class_name: Option<String>,
+
+    /// Access modes of the claim. Immutable.
+    #[serde(default = "default_access_modes")]
+    pub access_modes: Vec<VolumeAccessMode>,
+
+    /// Directory the volume is mounted at in the container (defaults to
+    /// `/var/lib/data`)
+    #[serde(default = "default_storage_mount_path")]
+    pub mount_path: String,
+
+    /// What happens to the claim when the MyResource is deleted
+    #[serde(default)]
+    pub reclaim_policy: StorageReclaimPolicy,
+}
+
+impl Default for StorageSpec {
+    fn default() -> Self {
+        Self {
+            size: Quantity("1Gi".to_string()),
+            storage_class_name: None,
+            access_modes: default_access_modes(),
+            mount_path: default_storage_mount_path(),
+            reclaim_policy: StorageReclaimPolicy::default(),
+        }
+    }
+}
+
+fn default_access_modes() -> Vec<VolumeAccessMode> {
+    vec![VolumeAccessMode::ReadWriteOnce]
+}
+
+fn default_storage_mount_path() -> String {
+    "/var/lib/data".to_string()
+}
+
+/// VolumeAccessMode is how the volume can be mounted by nodes and pods
+// Variant names match the Kubernetes access modes
+#[allow(clippy::enum_variant_names)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Deserialize, Serialize, JsonSchema)]
+pub enum VolumeAccessMode {
+    /// Read-write by pods on a single node
+    ReadWriteOnce,
+    /// Read-only by pods on many nodes
+    ReadOnlyMany,
+    /// Read-write by pods on many nodes
+    ReadWriteMany,
+    /// Read-write by a single pod
+    ReadWriteOncePod,
+}
+
+impl std::fmt::Display for VolumeAccessMode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            VolumeAccessMode::ReadWriteOnce => write!(f, "ReadWriteOnce"),
+            VolumeAccessMode::ReadOnlyMany => write!(f, "ReadOnlyMany"),
+            VolumeAccessMode::ReadWriteMany => write!(f, "ReadWriteMany"),
+            VolumeAccessMode::ReadWriteOncePod => write!(f, "ReadWriteOncePod"),
+        }
+    }
+}
+
+/// StorageReclaimPolicy controls whether the claim outlives the MyResource
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Deserialize, Serialize, JsonSchema)]
+pub enum StorageReclaimPolicy {
+    /// Delete the claim (and, depending on the StorageClass, its volume)
+    #[default]
+    Delete,
+    /// Keep the claim when the MyResource is deleted or `spec.storage` is
+    /// removed
+    Retain,
+}
+
+impl std::fmt::Display for StorageReclaimPolicy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            StorageReclaimPolicy::Delete => write!(f, "Delete"),
+            StorageReclaimPolicy::Retain => write!(f, "Retain"),
+        }
+    }
+}
+
+/// WorkloadType selects the controller that runs the pods
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Deserialize, Serialize, JsonSchema)]
+pub enum WorkloadType {
+    /// Interchangeable pods managed by a Deployment
+    #[default]
+    Deployment,
+    /// Pods with stable identities and per-replica volumes managed by a
+    /// StatefulSet
+    StatefulSet,
+}
+
+impl std::fmt::Display for WorkloadType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            WorkloadType::Deployment => write!(f, "Deployment"),
+            WorkloadType::StatefulSet => write!(f, "StatefulSet"),
+        }
+    }
+}
+
+/// ImagePullPolicy controls when the kubelet pulls the container image
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Deserialize, Serialize, JsonSchema)]
+pub enum ImagePullPolicy {
+    /// Always pull the image
+    Always,
+    /// Pull the image only if it is not present on the node
+    IfNotPresent,
+    /// Never pull the image
+    Never,
+}
+
+impl std::fmt::Display for ImagePullPolicy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ImagePullPolicy::Always => write!(f, "Always"),
+            ImagePullPolicy::IfNotPresent => write!(f, "IfNotPresent"),
+            ImagePullPolicy::Never => write!(f, "Never"),
+        }
+    }
+}
+
+/// DriftPolicy controls how the operator reacts to managed resources that were
+/// changed outside of the operator
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Deserialize, Serialize, JsonSchema)]
+pub enum DriftPolicy {
+    /// Report drift and re-apply the desired state
+    #[default]
+    Correct,
+    /// Report drift but leave the live resources untouched
+    ReportOnly,
+}
+
+impl std::fmt::Display for DriftPolicy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DriftPolicy::Correct => write!(f, "Correct"),
+            DriftPolicy::ReportOnly => write!(f, "ReportOnly"),
+        }
+    }
+}
+
 /// Status of a MyResource
-#[derive(Clone, Debug, Default, Deserialize, Serialize, JsonSchema)]
+#[derive(Clone, Debug, Default, PartialEq, Deserialize, Serialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct MyResourceStatus {
     /// Current phase of the resource
@@ -71,6 +955,75 @@ pub struct MyResourceStatus {
     /// Conditions describing the current state
     #[serde(default)]
     pub conditions: Vec<Condition>,
+
+    /// Number of consecutive failed reconciliations (reset on success)
+    #[serde(default)]
+    pub retry_count: i32,
+
+    /// Recent phase transitions, oldest first (bounded to `MAX_TRANSITION_HISTORY`)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub transitions: Vec<PhaseTransition>,
+
+    /// Label selector of the pods, used by the scale subresource
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub selector: Option<String>,
+
+    /// The last spec that reached the Running phase, restored by
+    /// `spec.rollout.autoRollback`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_known_good: Option<KnownGoodSpec>,
+}
+
+/// A spec that brought the resource to the Running phase
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct KnownGoodSpec {
+    /// Generation of the MyResource the spec was taken from
+    pub generation: i64,
+    /// The spec itself
+    pub spec: MyResourceSpec,
+}
+
+/// Maximum number of phase transitions retained in status
+pub const MAX_TRANSITION_HISTORY: usize = 10;
+
+/// A recorded transition between two lifecycle phases
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct PhaseTransition {
+    /// Phase the resource transitioned from
+    pub from: Phase,
+    /// Phase the resource transitioned to
+    pub to: Phase,
+    /// State machine event that triggered the transition
+    pub event: String,
+    /// Human-readable description of the transition
+    pub description: String,
+    /// Time the transition occurred
+    pub timestamp: String,
+    /// The generation of the resource when the transition occurred
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub observed_generation: Option<i64>,
+}
+
+impl PhaseTransition {
+    /// Create a new transition record timestamped now
+    pub fn new(
+        from: Phase,
+        to: Phase,
+        event: &str,
+        description: &str,
+        generation: Option<i64>,
+    ) -> Self {
+        Self {
+            from,
+            to,
+            event: event.to_string(),
+            description: description.to_string(),
+            timestamp: jiff::Timestamp::now().to_string(),
+            observed_generation: generation,
+        }
+    }
 }
 
 /// Phase represents the current lifecycle phase of a MyResource
@@ -108,7 +1061,7 @@ impl std::fmt::Display for Phase {
 }
 
 /// Condition describes the state of a resource at a certain point
-#[derive(Clone, Debug, Deserialize, Serialize, JsonSchema)]
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct Condition {
     /// Type of condition (e.g., "Ready", "Progressing", "Degraded")
@@ -168,6 +1121,26 @@ impl Condition {
     pub fn degraded(degraded: bool, reason: &str, message: &str, generation: Option<i64>) -> Self {
         Self::new("Degraded", degraded, reason, message, generation)
     }
+
+    /// Create a "ConfigurationValid" condition
+    pub fn configuration_valid(
+        valid: bool,
+        reason: &str,
+        message: &str,
+        generation: Option<i64>,
+    ) -> Self {
+        Self::new("ConfigurationValid", valid, reason, message, generation)
+    }
+
+    /// Create a "RolledBack" condition
+    pub fn rolled_back(
+        rolled_back: bool,
+        reason: &str,
+        message: &str,
+        generation: Option<i64>,
+    ) -> Self {
+        Self::new("RolledBack", rolled_back, reason, message, generation)
+    }
 }
 
 /// Standard condition types
@@ -180,4 +1153,7 @@ pub mod condition_types {
     pub const DEGRADED: &str = "Degraded";
     /// ConfigurationValid indicates the spec passed validation
     pub const CONFIGURATION_VALID: &str = "ConfigurationValid";
+    /// RolledBack indicates a failed update was rolled back to the last
+    /// known-good spec
+    pub const ROLLED_BACK: &str = "RolledBack";
 }
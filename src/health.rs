@@ -53,6 +53,59 @@ impl EncodeLabelSet for PhaseLabels {
     }
 }
 
+/// Labels for phase transition metrics
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+pub struct TransitionLabels {
+    pub from: String,
+    pub to: String,
+    pub event: String,
+    pub result: String,
+}
+
+impl EncodeLabelSet for TransitionLabels {
+    fn encode(&self, encoder: &mut LabelSetEncoder<'_>) -> Result<(), std::fmt::Error> {
+        ("from", self.from.as_str()).encode(encoder.encode_label())?;
+        ("to", self.to.as_str()).encode(encoder.encode_label())?;
+        ("event", self.event.as_str()).encode(encoder.encode_label())?;
+        ("result", self.result.as_str()).encode(encoder.encode_label())?;
+        Ok(())
+    }
+}
+
+/// Labels for drift metrics
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+pub struct DriftLabels {
+    pub namespace: String,
+    pub name: String,
+    pub kind: String,
+    pub field: String,
+    pub action: String,
+}
+
+impl EncodeLabelSet for DriftLabels {
+    fn encode(&self, encoder: &mut LabelSetEncoder<'_>) -> Result<(), std::fmt::Error> {
+        ("namespace", self.namespace.as_str()).encode(encoder.encode_label())?;
+        ("name", self.name.as_str()).encode(encoder.encode_label())?;
+        ("kind", self.kind.as_str()).encode(encoder.encode_label())?;
+        ("field", self.field.as_str()).encode(encoder.encode_label())?;
+        ("action", self.action.as_str()).encode(encoder.encode_label())?;
+        Ok(())
+    }
+}
+
+/// Labels for status write metrics
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+pub struct StatusWriteLabels {
+    pub result: String,
+}
+
+impl EncodeLabelSet for StatusWriteLabels {
+    fn encode(&self, encoder: &mut LabelSetEncoder<'_>) -> Result<(), std::fmt::Error> {
+        ("result", self.result.as_str()).encode(encoder.encode_label())?;
+        Ok(())
+    }
+}
+
 /// Shared metrics for the operator
 pub struct Metrics {
     /// Total reconciliations counter
@@ -67,6 +120,14 @@ pub struct Metrics {
     pub resource_replicas_desired: Family<ReconcileLabels, Gauge>,
     /// Ready replicas per resource
     pub resource_replicas_ready: Family<ReconcileLabels, Gauge>,
+    /// Consecutive failed reconciliations per resource
+    pub reconcile_retries: Family<ReconcileLabels, Gauge>,
+    /// State machine transition attempts by outcome
+    pub phase_transitions_total: Family<TransitionLabels, Counter>,
+    /// Out-of-band changes to owned resources by field path
+    pub drift_corrections_total: Family<DriftLabels, Counter>,
+    /// Status writes by outcome (applied or skipped)
+    pub status_writes_total: Family<StatusWriteLabels, Counter>,
     /// Prometheus registry
     registry: Registry,
 }
@@ -127,6 +188,34 @@ impl Metrics {
             resource_replicas_ready.clone(),
         );
 
+        let reconcile_retries = Family::<ReconcileLabels, Gauge>::default();
+        registry.register(
+            "myoperator_reconcile_retries",
+            "Number of consecutive failed reconciliations for each resource",
+            reconcile_retries.clone(),
+        );
+
+        let phase_transitions_total = Family::<TransitionLabels, Counter>::default();
+        registry.register(
+            "myoperator_phase_transitions",
+            "Total number of state machine transition attempts by outcome",
+            phase_transitions_total.clone(),
+        );
+
+        let drift_corrections_total = Family::<DriftLabels, Counter>::default();
+        registry.register(
+            "myoperator_drift_corrections",
+            "Total number of drifted fields detected on owned resources, by action taken",
+            drift_corrections_total.clone(),
+        );
+
+        let status_writes_total = Family::<StatusWriteLabels, Counter>::default();
+        registry.register(
+            "myoperator_status_writes",
+            "Total number of status writes, applied or skipped as unchanged",
+            status_writes_total.clone(),
+        );
+
         Self {
             reconciliations_total,
             reconciliation_errors_total,
@@ -134,6 +223,10 @@ impl Metrics {
             resources_total,
             resource_replicas_desired,
             resource_replicas_ready,
+            reconcile_retries,
+            phase_transitions_total,
+            drift_corrections_total,
+            status_writes_total,
             registry,
         }
     }
@@ -183,6 +276,57 @@ impl Metrics {
             .set(ready);
     }
 
+    /// Update the consecutive failure count for a resource
+    pub fn set_retry_count(&self, namespace: &str, name: &str, retries: i64) {
+        let labels = ReconcileLabels {
+            namespace: namespace.to_string(),
+            name: name.to_string(),
+        };
+        self.reconcile_retries.get_or_create(&labels).set(retries);
+    }
+
+    /// Record a state machine transition attempt
+    ///
+    /// `result` is one of `success`, `invalid` or `guard_failed`.
+    pub fn record_transition(&self, from: &str, to: &str, event: &str, result: &str) {
+        let labels = TransitionLabels {
+            from: from.to_string(),
+            to: to.to_string(),
+            event: event.to_string(),
+            result: result.to_string(),
+        };
+        self.phase_transitions_total.get_or_create(&labels).inc();
+    }
+
+    /// Record a drifted field on an owned resource
+    ///
+    /// `corrected` is false if the drift was only reported.
+    pub fn record_drift(
+        &self,
+        namespace: &str,
+        name: &str,
+        kind: &str,
+        field: &str,
+        corrected: bool,
+    ) {
+        let labels = DriftLabels {
+            namespace: namespace.to_string(),
+            name: name.to_string(),
+            kind: kind.to_string(),
+            field: field.to_string(),
+            action: if corrected { "corrected" } else { "reported" }.to_string(),
+        };
+        self.drift_corrections_total.get_or_create(&labels).inc();
+    }
+
+    /// Record a status write, or one skipped because nothing changed
+    pub fn record_status_write(&self, applied: bool) {
+        let labels = StatusWriteLabels {
+            result: if applied { "applied" } else { "skipped" }.to_string(),
+        };
+        self.status_writes_total.get_or_create(&labels).inc();
+    }
+
     /// Encode metrics to Prometheus text format
     pub fn encode(&self) -> String {
         let mut buffer = String::new();
@@ -328,6 +472,50 @@ mod tests {
         assert!(encoded.contains("myoperator_resource_replicas_ready"));
     }
 
+    #[test]
+    fn test_retry_metrics() {
+        let metrics = Metrics::new();
+        metrics.set_retry_count("default", "flaky-app", 3);
+
+        let encoded = metrics.encode();
+        assert!(encoded.contains("myoperator_reconcile_retries"));
+    }
+
+    #[test]
+    fn test_transition_metrics() {
+        let metrics = Metrics::new();
+        metrics.record_transition("Pending", "Creating", "ResourcesApplied", "success");
+        metrics.record_transition("Running", "Running", "ResourcesApplied", "invalid");
+
+        let encoded = metrics.encode();
+        assert!(encoded.contains("myoperator_phase_transitions"));
+        assert!(encoded.contains("result=\"invalid\""));
+    }
+
+    #[test]
+    fn test_drift_metrics() {
+        let metrics = Metrics::new();
+        metrics.record_drift("default", "app", "Deployment", "spec.replicas", true);
+        metrics.record_drift("default", "app", "ConfigMap", "data.message", false);
+
+        let encoded = metrics.encode();
+        assert!(encoded.contains("myoperator_drift_corrections_total"));
+        assert!(encoded.contains("field=\"spec.replicas\",action=\"corrected\""));
+        assert!(encoded.contains("field=\"data.message\",action=\"reported\""));
+    }
+
+    #[test]
+    fn test_status_write_metrics() {
+        let metrics = Metrics::new();
+        metrics.record_status_write(true);
+        metrics.record_status_write(false);
+        metrics.record_status_write(false);
+
+        let encoded = metrics.encode();
+        assert!(encoded.contains("myoperator_status_writes_total{result=\"applied\"} 1"));
+        assert!(encoded.contains("myoperator_status_writes_total{result=\"skipped\"} 2"));
+    }
+
     #[tokio::test]
     async fn test_health_state() {
         let state = HealthState::new();
@@ -16,16 +16,28 @@ pub use webhooks::{
 use std::sync::Arc;
 
 use futures::{Stream, StreamExt};
-use k8s_openapi::api::apps::v1::Deployment;
-use k8s_openapi::api::core::v1::{ConfigMap, Service};
+use k8s_openapi::api::apps::v1::{Deployment, StatefulSet};
+use k8s_openapi::api::autoscaling::v2::HorizontalPodAutoscaler;
+use k8s_openapi::api::core::v1::{ConfigMap, PersistentVolumeClaim, Secret, Service};
+use k8s_openapi::api::networking::v1::Ingress;
+use k8s_openapi::api::policy::v1::PodDisruptionBudget;
+use kube::core::{GroupVersionKind, PartialObjectMeta};
+use kube::runtime::reflector::ObjectRef;
 use kube::runtime::watcher::Config as WatcherConfig;
-use kube::runtime::{Controller, WatchStreamExt, metadata_watcher, predicates, reflector, watcher};
-use kube::{Api, Client, Resource};
+use kube::runtime::{
+    Controller, Predicate, WatchStreamExt, metadata_watcher, predicates, reflector, watcher,
+};
+use kube::{Api, Client, Resource, ResourceExt};
 use serde::de::DeserializeOwned;
 use tracing::{debug, error, info};
 
-use controller::{context::Context, reconciler::reconcile};
+use controller::{
+    context::{Context, OwnedStores},
+    hooks::PreDeleteHook,
+    reconciler::reconcile,
+};
 use crd::MyResource;
+use resources::gateway::{GATEWAY_API_GROUP, HTTPRoute};
 
 /// Create namespaced or cluster-wide API based on scope
 pub fn scoped_api<T>(client: Client, namespace: Option<&str>) -> Api<T>
@@ -54,7 +66,8 @@ fn default_watcher_config() -> WatcherConfig {
 /// - Maintains an in-memory cache via reflector
 /// - Uses automatic retry with exponential backoff on errors
 /// - Converts watch events to objects (Added/Modified only)
-/// - Filters out status-only updates via generation predicate
+/// - Filters out status-only updates via generation and annotation predicates
+///   (annotations carry operator triggers such as the retry annotation)
 ///
 /// Returns the reflector store (for cache lookups) and the filtered stream.
 fn create_filtered_stream<K>(
@@ -72,10 +85,110 @@ where
     let stream = reflector(writer, watcher(api, watcher_config))
         .default_backoff()
         .applied_objects()
-        .predicate_filter(predicates::generation, Default::default());
+        .predicate_filter(
+            predicates::generation.combine(predicates::annotations),
+            Default::default(),
+        );
+    (reader, stream)
+}
+
+/// Create the watcher configuration for owned resources.
+///
+/// Only resources carrying the operator's managed-by label are watched, so the
+/// caches don't hold every Deployment, Service, etc. in the cluster.
+fn owned_watcher_config() -> WatcherConfig {
+    default_watcher_config().labels(&format!(
+        "{}={}",
+        resources::common::MANAGED_BY_LABEL,
+        resources::common::MANAGED_BY
+    ))
+}
+
+/// Create a reflector-backed stream for an owned resource type.
+///
+/// Unlike the MyResource stream, no predicates are applied: any change to an
+/// owned resource may be drift that needs a reconcile.
+///
+/// Returns the reflector store (for drift detection) and the stream.
+fn create_owned_stream<K>(
+    api: Api<K>,
+    watcher_config: WatcherConfig,
+) -> (
+    reflector::Store<K>,
+    impl Stream<Item = Result<K, watcher::Error>>,
+)
+where
+    K: Resource + Clone + DeserializeOwned + std::fmt::Debug + Send + 'static,
+    K::DynamicType: Default + Eq + std::hash::Hash + Clone,
+{
+    let (reader, writer) = reflector::store();
+    let stream = reflector(writer, watcher(api, watcher_config))
+        .default_backoff()
+        .touched_objects();
+    (reader, stream)
+}
+
+/// Create a reflector-backed stream of object metadata.
+///
+/// Used for Secrets, which are watched cluster-wide (referenced Secrets carry
+/// no operator label) and whose contents shouldn't be held in memory.
+///
+/// Returns the metadata store and the stream.
+fn create_metadata_stream<K>(
+    api: Api<K>,
+    watcher_config: WatcherConfig,
+) -> (
+    reflector::Store<PartialObjectMeta<K>>,
+    impl Stream<Item = Result<PartialObjectMeta<K>, watcher::Error>>,
+)
+where
+    K: Resource + Clone + DeserializeOwned + std::fmt::Debug + Send + 'static,
+    K::DynamicType: Default + Eq + std::hash::Hash + Clone,
+{
+    let (reader, writer) = reflector::store();
+    let stream = reflector(writer, metadata_watcher(api, watcher_config))
+        .default_backoff()
+        .touched_objects();
     (reader, stream)
 }
 
+/// Check whether the Gateway API HTTPRoute kind is served by the cluster.
+///
+/// Watching a kind whose CRD is not installed fails forever, so HTTPRoutes are
+/// only watched if it is. Installing the Gateway API later requires an
+/// operator restart.
+async fn http_route_available(client: &Client) -> bool {
+    let gvk = GroupVersionKind::gvk(GATEWAY_API_GROUP, "v1", "HTTPRoute");
+    match kube::discovery::pinned_kind(client, &gvk).await {
+        Ok(_) => true,
+        Err(e) => {
+            info!(
+                "Gateway API HTTPRoute not available, not watching HTTPRoutes: {}",
+                e
+            );
+            false
+        }
+    }
+}
+
+/// Find the MyResources that reference or own a Secret.
+///
+/// Referenced Secrets have no owner reference pointing back at the MyResource,
+/// so they are mapped through the cached MyResources instead.
+fn secret_dependents(
+    resources: &reflector::Store<MyResource>,
+    secret: &PartialObjectMeta<Secret>,
+) -> Vec<ObjectRef<MyResource>> {
+    let namespace = secret.namespace();
+    let name = secret.name_any();
+    resources
+        .state()
+        .iter()
+        .filter(|r| r.namespace() == namespace && resources::common::uses_secret(r, &name))
+        .map(|r| ObjectRef::from_obj(r.as_ref()))
+        .collect()
+}
+
 /// Run the operator controller (cluster-wide).
 ///
 /// This is the main controller loop that watches MyResource resources
@@ -84,7 +197,7 @@ where
 ///
 /// If health_state is provided, metrics will be recorded for reconciliations.
 pub async fn run_controller(client: Client, health_state: Option<Arc<HealthState>>) {
-    run_controller_scoped(client, health_state, None).await
+    run_controller_scoped(client, health_state, None, Vec::new()).await
 }
 
 /// Run the operator controller with optional namespace scoping.
@@ -93,10 +206,14 @@ pub async fn run_controller(client: Client, health_state: Option<Arc<HealthState
 /// When `namespace` is `None`, watches resources cluster-wide.
 ///
 /// Use the scoped version for integration tests to enable parallel test execution.
+///
+/// `pre_delete_hooks` run, in order, before the owned resources of a deleted
+/// MyResource are removed (see [`PreDeleteHook`]).
 pub async fn run_controller_scoped(
     client: Client,
     health_state: Option<Arc<HealthState>>,
     namespace: Option<&str>,
+    pre_delete_hooks: Vec<Arc<dyn PreDeleteHook>>,
 ) {
     let scope_msg = namespace.unwrap_or("cluster-wide");
     info!(
@@ -109,29 +226,85 @@ pub async fn run_controller_scoped(
         state.set_ready(true).await;
     }
 
-    let ctx = Arc::new(Context::new(client.clone(), health_state));
-
     // Set up APIs for the controller (namespaced or cluster-wide)
     let myresources: Api<MyResource> = scoped_api(client.clone(), namespace);
     let deployments: Api<Deployment> = scoped_api(client.clone(), namespace);
+    let statefulsets: Api<StatefulSet> = scoped_api(client.clone(), namespace);
     let services: Api<Service> = scoped_api(client.clone(), namespace);
     let configmaps: Api<ConfigMap> = scoped_api(client.clone(), namespace);
+    let secrets: Api<Secret> = scoped_api(client.clone(), namespace);
+    let ingresses: Api<Ingress> = scoped_api(client.clone(), namespace);
+    let pdbs: Api<PodDisruptionBudget> = scoped_api(client.clone(), namespace);
+    let hpas: Api<HorizontalPodAutoscaler> = scoped_api(client.clone(), namespace);
+    let pvcs: Api<PersistentVolumeClaim> = scoped_api(client.clone(), namespace);
 
     // Use consistent watcher configuration across all controllers
     let watcher_config = default_watcher_config();
 
-    // Create filtered stream with standard optimizations (reflector, backoff, generation predicate)
+    // Create filtered stream with standard optimizations (reflector, backoff, predicates)
     let (reader, resource_stream) = create_filtered_stream(myresources, watcher_config.clone());
 
+    // Owned resources are cached in full (not just metadata) so the reconciler can
+    // compare them with the desired state and detect out-of-band changes
+    let owned_config = owned_watcher_config();
+    let (deployment_store, deployment_stream) =
+        create_owned_stream(deployments, owned_config.clone());
+    let (statefulset_store, statefulset_stream) =
+        create_owned_stream(statefulsets, owned_config.clone());
+    let (service_store, service_stream) = create_owned_stream(services, owned_config.clone());
+    let (configmap_store, configmap_stream) = create_owned_stream(configmaps, owned_config.clone());
+    // Only Secret metadata is watched: a new resourceVersion is enough to trigger
+    // the reconcile that reads and hashes the referenced Secrets
+    let (secret_store, secret_stream) = create_metadata_stream(secrets, watcher_config);
+    let secret_resources = reader.clone();
+    let (ingress_store, ingress_stream) = create_owned_stream(ingresses, owned_config.clone());
+    let (pdb_store, pdb_stream) = create_owned_stream(pdbs, owned_config.clone());
+    let (hpa_store, hpa_stream) = create_owned_stream(hpas, owned_config.clone());
+    let (pvc_store, pvc_stream) = create_owned_stream(pvcs, owned_config.clone());
+    let (http_route_store, http_route_stream) = if http_route_available(&client).await {
+        let http_routes: Api<HTTPRoute> = scoped_api(client.clone(), namespace);
+        let (store, stream) = create_owned_stream(http_routes, owned_config);
+        (Some(store), Some(stream))
+    } else {
+        (None, None)
+    };
+
+    let ctx = Context::new(client.clone(), health_state).with_owned_stores(OwnedStores {
+        deployments: deployment_store,
+        services: service_store,
+        configmaps: configmap_store,
+        secrets: secret_store,
+        ingresses: ingress_store,
+        http_routes: http_route_store,
+        pod_disruption_budgets: pdb_store,
+        horizontal_pod_autoscalers: hpa_store,
+        statefulsets: statefulset_store,
+        persistent_volume_claims: pvc_store,
+    });
+    let ctx = Arc::new(
+        pre_delete_hooks
+            .into_iter()
+            .fold(ctx, Context::with_pre_delete_hook),
+    );
+
     // Create and run the controller using for_stream with the pre-filtered stream
-    // Memory optimization: Use metadata_watcher for owned resources where we only need to know
-    // they exist/changed (Services, ConfigMaps). Keep full watcher for Deployment
-    // since we read .status.readyReplicas. metadata_watcher returns PartialObjectMeta which only
-    // contains TypeMeta + ObjectMeta, reducing memory and IO.
-    Controller::for_stream(resource_stream, reader)
-        .owns(deployments, watcher_config.clone())
-        .owns_stream(metadata_watcher(services, watcher_config.clone()).touched_objects())
-        .owns_stream(metadata_watcher(configmaps, watcher_config).touched_objects())
+    let mut controller = Controller::for_stream(resource_stream, reader)
+        .owns_stream(deployment_stream)
+        .owns_stream(statefulset_stream)
+        .owns_stream(service_stream)
+        .owns_stream(configmap_stream)
+        .owns_stream(ingress_stream)
+        .owns_stream(pdb_stream)
+        .owns_stream(hpa_stream)
+        .owns_stream(pvc_stream)
+        .watches_stream(secret_stream, move |secret| {
+            secret_dependents(&secret_resources, &secret)
+        });
+    if let Some(stream) = http_route_stream {
+        controller = controller.owns_stream(stream);
+    }
+
+    controller
         .run(reconcile, controller::reconciler::error_policy, ctx)
         .for_each(|result| async move {
             match result {
@@ -3,24 +3,101 @@
 //! Provides functions for creating standard Kubernetes resources with proper
 //! labels, owner references, and configurations.
 
+use k8s_openapi::ByteString;
 use k8s_openapi::api::{
-    apps::v1::Deployment,
-    core::v1::{ConfigMap, Service},
+    apps::v1::{
+        Deployment, DeploymentStrategy, RollingUpdateDeployment, StatefulSet, StatefulSetSpec,
+    },
+    autoscaling::v2::{
+        CrossVersionObjectReference, HorizontalPodAutoscaler, HorizontalPodAutoscalerSpec,
+        MetricSpec, MetricTarget, ResourceMetricSource,
+    },
+    core::v1::{
+        Affinity, Capabilities, ConfigMap, Container, EnvFromSource, EnvVar, EnvVarSource,
+        ExecAction, HTTPGetAction, KeyToPath, PersistentVolumeClaim, PersistentVolumeClaimSpec,
+        PersistentVolumeClaimVolumeSource, PodAffinityTerm, PodAntiAffinity, Probe,
+        ResourceRequirements, Secret, SecretEnvSource, SecretKeySelector, SecretVolumeSource,
+        SecurityContext, Service, ServicePort, TCPSocketAction, Volume, VolumeMount,
+        VolumeResourceRequirements, WeightedPodAffinityTerm,
+    },
+    networking::v1::{
+        HTTPIngressPath, HTTPIngressRuleValue, Ingress, IngressBackend, IngressRule,
+        IngressServiceBackend, IngressSpec as KubeIngressSpec, IngressTLS, ServiceBackendPort,
+    },
+    policy::v1::{PodDisruptionBudget, PodDisruptionBudgetSpec},
 };
-use k8s_openapi::apimachinery::pkg::apis::meta::v1::OwnerReference;
+use k8s_openapi::apimachinery::pkg::api::resource::Quantity;
+use k8s_openapi::apimachinery::pkg::apis::meta::v1::{LabelSelector, OwnerReference};
+use k8s_openapi::apimachinery::pkg::util::intstr::IntOrString;
 use kube::ResourceExt;
+use sha2::{Digest, Sha256};
 use std::collections::BTreeMap;
 
-use crate::crd::MyResource;
+use crate::crd::{
+    DEFAULT_PROGRESS_DEADLINE_SECONDS, IngressPath, MyResource, PasswordCharset, PathType,
+    ProbeSpec, RolloutSpec, RolloutStrategy, RouteKind, SecretProjection, ServiceType,
+};
+use crate::resources::gateway::{
+    HTTPBackendRef, HTTPPathMatch, HTTPRoute, HTTPRouteMatch, HTTPRouteRule, HTTPRouteSpec,
+    ParentReference,
+};
+
+/// Name of the container running `spec.image`
+pub const MAIN_CONTAINER_NAME: &str = "main";
+
+/// Directory the managed ConfigMap is mounted at in every pod
+pub const CONFIG_MOUNT_PATH: &str = "/etc/config";
+
+/// Pod template annotation holding a hash of the ConfigMap contents.
+///
+/// Changing the ConfigMap changes the hash, which rolls the pods.
+pub const CONFIG_HASH_ANNOTATION: &str = "myoperator.example.com/config-hash";
+
+/// ConfigMap key holding `spec.message`
+pub const MESSAGE_KEY: &str = "message";
+
+/// Directory under which Secrets are mounted, one subdirectory per Secret
+pub const SECRET_MOUNT_ROOT: &str = "/etc/secrets";
+
+/// Pod template annotation holding a hash of the Secrets used by the pods.
+///
+/// Only set when the resource uses Secrets. Changing a referenced Secret
+/// changes the hash, which rolls the pods.
+pub const SECRET_HASH_ANNOTATION: &str = "myoperator.example.com/secret-hash";
+
+/// Workload annotation listing the resourceVersions of the Secrets hashed into
+/// `SECRET_HASH_ANNOTATION`.
+///
+/// While the cached Secret metadata still has these versions, the recorded hash
+/// is reused without reading the Secrets again.
+pub const SECRET_VERSIONS_ANNOTATION: &str = "myoperator.example.com/secret-versions";
+
+/// Directory under which StatefulSet volume claims are mounted, one
+/// subdirectory per claim
+pub const DATA_MOUNT_ROOT: &str = "/data";
+
+/// PersistentVolumeClaim annotation recording `spec.storage.reclaimPolicy`.
+///
+/// Kept on the claim so the policy is still known after `spec.storage` is
+/// removed.
+pub const RECLAIM_POLICY_ANNOTATION: &str = "myoperator.example.com/reclaim-policy";
+
+/// Label selector of the managed pods, in the string form used by the scale
+/// subresource
+pub fn pod_selector(resource: &MyResource) -> String {
+    format!("app.kubernetes.io/name={}", resource.name_any())
+}
+
+/// Label identifying the resources managed by the operator
+pub const MANAGED_BY_LABEL: &str = "app.kubernetes.io/managed-by";
+
+/// Value of `MANAGED_BY_LABEL` on managed resources
+pub const MANAGED_BY: &str = "my-operator";
 
 /// Standard labels applied to all managed resources
 pub fn standard_labels(resource: &MyResource) -> BTreeMap<String, String> {
     let mut labels = BTreeMap::new();
     labels.insert("app.kubernetes.io/name".to_string(), resource.name_any());
-    labels.insert(
-        "app.kubernetes.io/managed-by".to_string(),
-        "my-operator".to_string(),
-    );
     labels.insert(
         "app.kubernetes.io/component".to_string(),
         "myresource".to_string(),
@@ -31,6 +108,9 @@ pub fn standard_labels(resource: &MyResource) -> BTreeMap<String, String> {
         labels.insert(key.clone(), value.clone());
     }
 
+    // Owned resources are only watched with this label, so it can't be overridden
+    labels.insert(MANAGED_BY_LABEL.to_string(), MANAGED_BY.to_string());
+
     labels
 }
 
@@ -46,6 +126,133 @@ pub fn owner_reference(resource: &MyResource) -> OwnerReference {
     }
 }
 
+/// Compute resources used when `spec.resources` is not set
+pub fn default_resources() -> ResourceRequirements {
+    let quantities = |cpu: &str, memory: &str| {
+        BTreeMap::from([
+            ("cpu".to_string(), Quantity(cpu.to_string())),
+            ("memory".to_string(), Quantity(memory.to_string())),
+        ])
+    };
+    ResourceRequirements {
+        limits: Some(quantities("100m", "128Mi")),
+        requests: Some(quantities("50m", "64Mi")),
+        ..Default::default()
+    }
+}
+
+/// Hardened security context of the main container
+pub fn default_security_context() -> SecurityContext {
+    SecurityContext {
+        allow_privilege_escalation: Some(false),
+        read_only_root_filesystem: Some(true),
+        run_as_non_root: Some(true),
+        capabilities: Some(Capabilities {
+            drop: Some(vec!["ALL".to_string()]),
+            ..Default::default()
+        }),
+        ..Default::default()
+    }
+}
+
+/// Copy of a user-supplied init or sidecar container with the unset fields of
+/// its security context filled in from `default_security_context`.
+///
+/// `capabilities` is defaulted as a whole, so a container that adds
+/// capabilities decides for itself what to drop. A privileged container keeps
+/// privilege escalation, which the API server requires.
+pub fn with_security_defaults(container: &Container) -> Container {
+    let defaults = default_security_context();
+    let mut container = container.clone();
+    let context = container
+        .security_context
+        .get_or_insert_with(SecurityContext::default);
+
+    if context.privileged != Some(true) {
+        context.allow_privilege_escalation = context
+            .allow_privilege_escalation
+            .or(defaults.allow_privilege_escalation);
+    }
+    context.read_only_root_filesystem = context
+        .read_only_root_filesystem
+        .or(defaults.read_only_root_filesystem);
+    context.run_as_non_root = context.run_as_non_root.or(defaults.run_as_non_root);
+    if context.capabilities.is_none() {
+        context.capabilities = defaults.capabilities;
+    }
+    container
+}
+
+/// Affinity used when `spec.affinity` is not set.
+///
+/// With more than one replica, pods prefer (but don't require) to run on
+/// different nodes, so a single node failure doesn't take out every replica.
+pub fn default_affinity(resource: &MyResource) -> Option<Affinity> {
+    if resource.spec.max_replicas() <= 1 {
+        return None;
+    }
+
+    Some(Affinity {
+        pod_anti_affinity: Some(PodAntiAffinity {
+            preferred_during_scheduling_ignored_during_execution: Some(vec![
+                WeightedPodAffinityTerm {
+                    weight: 100,
+                    pod_affinity_term: PodAffinityTerm {
+                        label_selector: Some(LabelSelector {
+                            match_labels: Some(BTreeMap::from([(
+                                "app.kubernetes.io/name".to_string(),
+                                resource.name_any(),
+                            )])),
+                            ..Default::default()
+                        }),
+                        topology_key: "kubernetes.io/hostname".to_string(),
+                        ..Default::default()
+                    },
+                },
+            ]),
+            ..Default::default()
+        }),
+        ..Default::default()
+    })
+}
+
+/// Build a container probe from a probe spec.
+///
+/// Ports default to `container_port`, and a probe without a handler (or no
+/// probe spec at all) becomes a TCP check on that port.
+pub fn container_probe(probe: Option<&ProbeSpec>, container_port: i32) -> Probe {
+    let default_probe = ProbeSpec::default();
+    let probe = probe.unwrap_or(&default_probe);
+    let port = |port: Option<i32>| IntOrString::Int(port.unwrap_or(container_port));
+
+    let mut result = Probe {
+        initial_delay_seconds: probe.initial_delay_seconds,
+        period_seconds: probe.period_seconds,
+        timeout_seconds: probe.timeout_seconds,
+        failure_threshold: probe.failure_threshold,
+        success_threshold: probe.success_threshold,
+        ..Default::default()
+    };
+    if let Some(ref http) = probe.http_get {
+        result.http_get = Some(HTTPGetAction {
+            path: Some(http.path.clone()),
+            port: port(http.port),
+            scheme: http.scheme.clone(),
+            ..Default::default()
+        });
+    } else if let Some(ref exec) = probe.exec {
+        result.exec = Some(ExecAction {
+            command: Some(exec.command.clone()),
+        });
+    } else {
+        result.tcp_socket = Some(TCPSocketAction {
+            port: port(probe.tcp_socket.as_ref().and_then(|t| t.port)),
+            ..Default::default()
+        });
+    }
+    result
+}
+
 /// Generate a ConfigMap for a MyResource
 pub fn generate_configmap(resource: &MyResource) -> ConfigMap {
     let name = resource.name_any();
@@ -59,154 +266,893 @@ pub fn generate_configmap(resource: &MyResource) -> ConfigMap {
             owner_references: Some(vec![owner_reference(resource)]),
             ..Default::default()
         },
-        data: Some({
-            let mut data = BTreeMap::new();
-            data.insert("message".to_string(), resource.spec.message.clone());
-            data.insert("replicas".to_string(), resource.spec.replicas.to_string());
-            data
-        }),
+        data: Some(configmap_data(resource)),
         ..Default::default()
     }
 }
 
-/// Generate a Deployment for a MyResource
-pub fn generate_deployment(resource: &MyResource) -> Deployment {
-    let name = resource.name_any();
-    let labels = standard_labels(resource);
-    let replicas = resource.spec.replicas;
+/// Files written to the managed ConfigMap: `spec.config` plus the message
+pub fn configmap_data(resource: &MyResource) -> BTreeMap<String, String> {
+    let mut data = resource.spec.config.clone();
+    data.insert(MESSAGE_KEY.to_string(), resource.spec.message.clone());
+    data
+}
 
-    Deployment {
+/// Hash the ConfigMap contents for the pod template annotation
+pub fn config_hash(data: &BTreeMap<String, String>) -> String {
+    hash_parts(
+        data.iter()
+            .flat_map(|(key, value)| [key.as_bytes(), value.as_bytes()]),
+    )
+}
+
+/// Hash the data of the Secrets used by the pods, in the given order
+pub fn secret_hash(secrets: &[Secret]) -> String {
+    hash_parts(secrets.iter().flat_map(|secret| {
+        let name = secret.metadata.name.as_deref().unwrap_or_default();
+        secret
+            .data
+            .iter()
+            .flatten()
+            .flat_map(move |(key, value)| [name.as_bytes(), key.as_bytes(), value.0.as_slice()])
+    }))
+}
+
+/// List Secret names and resourceVersions, in the given order, for
+/// `SECRET_VERSIONS_ANNOTATION`
+pub fn secret_versions(versions: &[(&str, &str)]) -> String {
+    versions
+        .iter()
+        .map(|(name, version)| format!("{}={}", name, version))
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+/// SHA-256 of a sequence of byte strings, as lowercase hex
+fn hash_parts<'a>(parts: impl Iterator<Item = &'a [u8]>) -> String {
+    let mut hasher = Sha256::new();
+    for part in parts {
+        // Length-prefix each part so distinct inputs can't hash the same
+        hasher.update((part.len() as u64).to_be_bytes());
+        hasher.update(part);
+    }
+    format!("{:x}", hasher.finalize())
+}
+
+/// Name of the operator-generated Secret
+pub fn generated_secret_name(resource: &MyResource) -> String {
+    format!("{}-generated", resource.name_any())
+}
+
+/// Check whether a MyResource references or owns the named Secret
+pub fn uses_secret(resource: &MyResource, secret_name: &str) -> bool {
+    resource
+        .spec
+        .secret_refs
+        .iter()
+        .any(|r| r.name == secret_name)
+        || (resource.spec.generated_secret.is_some()
+            && generated_secret_name(resource) == secret_name)
+}
+
+/// Generate a random password from the given character set
+pub fn generate_password(length: u32, charset: PasswordCharset) -> String {
+    let chars = charset.chars();
+    (0..length)
+        .filter_map(|_| chars.get(rand::random_range(0..chars.len())))
+        .map(|&c| char::from(c))
+        .collect()
+}
+
+/// Generate the operator-owned Secret for a MyResource.
+///
+/// The password is generated once: if `existing` already holds the key, its
+/// value is kept, so the Secret is stable across reconciles. Delete the key
+/// (or the Secret) to rotate it. Returns `None` if `spec.generatedSecret` is
+/// not set.
+pub fn generate_secret(resource: &MyResource, existing: Option<&Secret>) -> Option<Secret> {
+    let spec = resource.spec.generated_secret.as_ref()?;
+    let password = existing
+        .and_then(|secret| secret.data.as_ref())
+        .and_then(|data| data.get(&spec.key))
+        .cloned()
+        .unwrap_or_else(|| ByteString(generate_password(spec.length, spec.charset).into_bytes()));
+
+    Some(Secret {
         metadata: k8s_openapi::apimachinery::pkg::apis::meta::v1::ObjectMeta {
-            name: Some(name.clone()),
+            name: Some(generated_secret_name(resource)),
             namespace: resource.namespace(),
-            labels: Some(labels.clone()),
+            labels: Some(standard_labels(resource)),
             owner_references: Some(vec![owner_reference(resource)]),
             ..Default::default()
         },
-        spec: Some(k8s_openapi::api::apps::v1::DeploymentSpec {
-            replicas: Some(replicas),
-            selector: k8s_openapi::apimachinery::pkg::apis::meta::v1::LabelSelector {
-                match_labels: Some({
-                    let mut selector = BTreeMap::new();
-                    selector.insert("app.kubernetes.io/name".to_string(), name.clone());
-                    selector
-                }),
+        data: Some(BTreeMap::from([(spec.key.clone(), password)])),
+        type_: Some("Opaque".to_string()),
+        ..Default::default()
+    })
+}
+
+/// Record the hash of the Secrets used by a workload on its pod template
+pub fn annotate_secret_hash(
+    template: &mut k8s_openapi::api::core::v1::PodTemplateSpec,
+    hash: String,
+) {
+    template
+        .metadata
+        .get_or_insert_with(Default::default)
+        .annotations
+        .get_or_insert_with(BTreeMap::new)
+        .insert(SECRET_HASH_ANNOTATION.to_string(), hash);
+}
+
+/// Record the versions of the Secrets hashed into a workload's pod template on
+/// the workload itself, so a metadata-only change doesn't roll the pods
+pub fn annotate_secret_versions(
+    metadata: &mut k8s_openapi::apimachinery::pkg::apis::meta::v1::ObjectMeta,
+    versions: String,
+) {
+    metadata
+        .annotations
+        .get_or_insert_with(BTreeMap::new)
+        .insert(SECRET_VERSIONS_ANNOTATION.to_string(), versions);
+}
+
+/// Environment, volumes and mounts exposing the Secrets of a MyResource
+#[derive(Default)]
+struct SecretProjections {
+    env: Vec<EnvVar>,
+    env_from: Vec<EnvFromSource>,
+    volumes: Vec<Volume>,
+    mounts: Vec<VolumeMount>,
+}
+
+/// Project the referenced and generated Secrets into the container
+fn secret_projections(resource: &MyResource) -> SecretProjections {
+    let mut projections = SecretProjections::default();
+
+    let mut mount = |volume: String, secret_name: String, items: Option<Vec<KeyToPath>>| {
+        projections.mounts.push(VolumeMount {
+            name: volume.clone(),
+            mount_path: format!("{}/{}", SECRET_MOUNT_ROOT, secret_name),
+            read_only: Some(true),
+            ..Default::default()
+        });
+        projections.volumes.push(Volume {
+            name: volume,
+            secret: Some(SecretVolumeSource {
+                secret_name: Some(secret_name),
+                items,
                 ..Default::default()
-            },
-            template: k8s_openapi::api::core::v1::PodTemplateSpec {
-                metadata: Some(k8s_openapi::apimachinery::pkg::apis::meta::v1::ObjectMeta {
-                    labels: Some(labels),
+            }),
+            ..Default::default()
+        });
+    };
+
+    for (index, secret_ref) in resource.spec.secret_refs.iter().enumerate() {
+        if secret_ref.projection != SecretProjection::File {
+            continue;
+        }
+        let items = (!secret_ref.keys.is_empty()).then(|| {
+            secret_ref
+                .keys
+                .iter()
+                .map(|k| KeyToPath {
+                    key: k.key.clone(),
+                    path: k.target.clone().unwrap_or_else(|| k.key.clone()),
+                    ..Default::default()
+                })
+                .collect()
+        });
+        mount(format!("secret-{}", index), secret_ref.name.clone(), items);
+    }
+    if resource.spec.generated_secret.is_some() {
+        mount(
+            "generated-secret".to_string(),
+            generated_secret_name(resource),
+            None,
+        );
+    }
+
+    for secret_ref in &resource.spec.secret_refs {
+        if secret_ref.projection != SecretProjection::Env {
+            continue;
+        }
+        if secret_ref.keys.is_empty() {
+            projections.env_from.push(EnvFromSource {
+                secret_ref: Some(SecretEnvSource {
+                    name: secret_ref.name.clone(),
                     ..Default::default()
                 }),
-                spec: Some(k8s_openapi::api::core::v1::PodSpec {
-                    containers: vec![k8s_openapi::api::core::v1::Container {
-                        name: "main".to_string(),
-                        // Use a simple nginx image as placeholder
-                        image: Some("nginx:alpine".to_string()),
-                        ports: Some(vec![k8s_openapi::api::core::v1::ContainerPort {
-                            container_port: 80,
-                            name: Some("http".to_string()),
-                            ..Default::default()
-                        }]),
-                        env: Some(vec![k8s_openapi::api::core::v1::EnvVar {
-                            name: "MESSAGE".to_string(),
-                            value: Some(resource.spec.message.clone()),
-                            ..Default::default()
-                        }]),
-                        resources: Some(k8s_openapi::api::core::v1::ResourceRequirements {
-                            limits: Some({
-                                let mut limits = BTreeMap::new();
-                                limits.insert(
-                                    "cpu".to_string(),
-                                    k8s_openapi::apimachinery::pkg::api::resource::Quantity(
-                                        "100m".to_string(),
-                                    ),
-                                );
-                                limits.insert(
-                                    "memory".to_string(),
-                                    k8s_openapi::apimachinery::pkg::api::resource::Quantity(
-                                        "128Mi".to_string(),
-                                    ),
-                                );
-                                limits
-                            }),
-                            requests: Some({
-                                let mut requests = BTreeMap::new();
-                                requests.insert(
-                                    "cpu".to_string(),
-                                    k8s_openapi::apimachinery::pkg::api::resource::Quantity(
-                                        "50m".to_string(),
-                                    ),
-                                );
-                                requests.insert(
-                                    "memory".to_string(),
-                                    k8s_openapi::apimachinery::pkg::api::resource::Quantity(
-                                        "64Mi".to_string(),
-                                    ),
-                                );
-                                requests
-                            }),
-                            ..Default::default()
-                        }),
-                        security_context: Some(k8s_openapi::api::core::v1::SecurityContext {
-                            allow_privilege_escalation: Some(false),
-                            read_only_root_filesystem: Some(true),
-                            run_as_non_root: Some(true),
-                            capabilities: Some(k8s_openapi::api::core::v1::Capabilities {
-                                drop: Some(vec!["ALL".to_string()]),
-                                ..Default::default()
-                            }),
-                            ..Default::default()
-                        }),
-                        ..Default::default()
-                    }],
-                    security_context: Some(k8s_openapi::api::core::v1::PodSecurityContext {
-                        run_as_non_root: Some(true),
-                        run_as_user: Some(1000),
-                        fs_group: Some(1000),
+                ..Default::default()
+            });
+            continue;
+        }
+        projections
+            .env
+            .extend(secret_ref.keys.iter().map(|k| EnvVar {
+                name: k.target.clone().unwrap_or_else(|| k.key.clone()),
+                value_from: Some(EnvVarSource {
+                    secret_key_ref: Some(SecretKeySelector {
+                        name: secret_ref.name.clone(),
+                        key: k.key.clone(),
                         ..Default::default()
                     }),
                     ..Default::default()
                 }),
+                ..Default::default()
+            }));
+    }
+
+    projections
+}
+
+/// Pod selector labels shared by the workload, Services and budgets
+fn selector_labels(resource: &MyResource) -> BTreeMap<String, String> {
+    BTreeMap::from([("app.kubernetes.io/name".to_string(), resource.name_any())])
+}
+
+/// Generate a Deployment for a MyResource.
+///
+/// With `spec.autoscaling` the replica count is left out, so the operator
+/// doesn't own `.spec.replicas` and fight the HorizontalPodAutoscaler.
+pub fn generate_deployment(resource: &MyResource) -> Deployment {
+    Deployment {
+        metadata: k8s_openapi::apimachinery::pkg::apis::meta::v1::ObjectMeta {
+            name: Some(resource.name_any()),
+            namespace: resource.namespace(),
+            labels: Some(standard_labels(resource)),
+            owner_references: Some(vec![owner_reference(resource)]),
+            ..Default::default()
+        },
+        spec: Some(k8s_openapi::api::apps::v1::DeploymentSpec {
+            replicas: resource
+                .spec
+                .autoscaling
+                .is_none()
+                .then_some(resource.spec.replicas),
+            selector: LabelSelector {
+                match_labels: Some(selector_labels(resource)),
+                ..Default::default()
             },
+            template: pod_template(resource),
+            strategy: resource.spec.rollout.as_ref().map(deployment_strategy),
+            progress_deadline_seconds: Some(
+                resource
+                    .spec
+                    .rollout
+                    .as_ref()
+                    .and_then(|r| r.progress_deadline_seconds)
+                    .unwrap_or(DEFAULT_PROGRESS_DEADLINE_SECONDS),
+            ),
             ..Default::default()
         }),
         ..Default::default()
     }
 }
 
+/// Deployment strategy for `spec.rollout`
+fn deployment_strategy(rollout: &RolloutSpec) -> DeploymentStrategy {
+    DeploymentStrategy {
+        type_: Some(rollout.strategy.to_string()),
+        rolling_update: (rollout.strategy == RolloutStrategy::RollingUpdate
+            && (rollout.max_surge.is_some() || rollout.max_unavailable.is_some()))
+        .then(|| RollingUpdateDeployment {
+            max_surge: rollout.max_surge.clone(),
+            max_unavailable: rollout.max_unavailable.clone(),
+        }),
+    }
+}
+
+/// Generate a StatefulSet for a MyResource.
+///
+/// The pods get stable network identities through the headless Service from
+/// `generate_headless_service`, and each volume claim template is mounted into
+/// the container at `/data/<name>`. Like the Deployment, the replica count is
+/// left to the HorizontalPodAutoscaler with `spec.autoscaling`.
+pub fn generate_statefulset(resource: &MyResource) -> StatefulSet {
+    let claims = &resource.spec.volume_claim_templates;
+    let mut template = pod_template(resource);
+    if let Some(container) = template
+        .spec
+        .as_mut()
+        .and_then(|spec| spec.containers.first_mut())
+    {
+        container
+            .volume_mounts
+            .get_or_insert_with(Vec::new)
+            .extend(claims.iter().map(|claim| {
+                let name = claim.metadata.name.clone().unwrap_or_default();
+                VolumeMount {
+                    mount_path: format!("{}/{}", DATA_MOUNT_ROOT, name),
+                    name,
+                    ..Default::default()
+                }
+            }));
+    }
+
+    StatefulSet {
+        metadata: k8s_openapi::apimachinery::pkg::apis::meta::v1::ObjectMeta {
+            name: Some(resource.name_any()),
+            namespace: resource.namespace(),
+            labels: Some(standard_labels(resource)),
+            owner_references: Some(vec![owner_reference(resource)]),
+            ..Default::default()
+        },
+        spec: Some(StatefulSetSpec {
+            replicas: resource
+                .spec
+                .autoscaling
+                .is_none()
+                .then_some(resource.spec.replicas),
+            selector: LabelSelector {
+                match_labels: Some(selector_labels(resource)),
+                ..Default::default()
+            },
+            service_name: Some(headless_service_name(resource)),
+            template,
+            volume_claim_templates: if claims.is_empty() {
+                None
+            } else {
+                Some(claims.clone())
+            },
+            ..Default::default()
+        }),
+        ..Default::default()
+    }
+}
+
+/// Name of the PersistentVolumeClaim created for `spec.storage`
+pub fn storage_claim_name(resource: &MyResource) -> String {
+    format!("{}-data", resource.name_any())
+}
+
+/// Generate the PersistentVolumeClaim for `spec.storage`.
+///
+/// Returns `None` without `spec.storage`. Only the requested size can change
+/// once the claim exists.
+pub fn generate_pvc(resource: &MyResource) -> Option<PersistentVolumeClaim> {
+    let storage = resource.spec.storage.as_ref()?;
+
+    Some(PersistentVolumeClaim {
+        metadata: k8s_openapi::apimachinery::pkg::apis::meta::v1::ObjectMeta {
+            name: Some(storage_claim_name(resource)),
+            namespace: resource.namespace(),
+            labels: Some(standard_labels(resource)),
+            annotations: Some(BTreeMap::from([(
+                RECLAIM_POLICY_ANNOTATION.to_string(),
+                storage.reclaim_policy.to_string(),
+            )])),
+            owner_references: Some(vec![owner_reference(resource)]),
+            ..Default::default()
+        },
+        spec: Some(PersistentVolumeClaimSpec {
+            access_modes: Some(
+                storage
+                    .access_modes
+                    .iter()
+                    .map(|mode| mode.to_string())
+                    .collect(),
+            ),
+            storage_class_name: storage.storage_class_name.clone(),
+            resources: Some(VolumeResourceRequirements {
+                requests: Some(BTreeMap::from([(
+                    "storage".to_string(),
+                    storage.size.clone(),
+                )])),
+                ..Default::default()
+            }),
+            ..Default::default()
+        }),
+        ..Default::default()
+    })
+}
+
+/// Name of the headless Service governing the StatefulSet
+pub fn headless_service_name(resource: &MyResource) -> String {
+    format!("{}-headless", resource.name_any())
+}
+
+/// Generate the headless Service giving StatefulSet pods stable DNS names.
+///
+/// Not-ready pods are published too, so replicas can find each other while
+/// they start up.
+pub fn generate_headless_service(resource: &MyResource) -> Service {
+    Service {
+        metadata: k8s_openapi::apimachinery::pkg::apis::meta::v1::ObjectMeta {
+            name: Some(headless_service_name(resource)),
+            namespace: resource.namespace(),
+            labels: Some(standard_labels(resource)),
+            owner_references: Some(vec![owner_reference(resource)]),
+            ..Default::default()
+        },
+        spec: Some(k8s_openapi::api::core::v1::ServiceSpec {
+            cluster_ip: Some("None".to_string()),
+            selector: Some(selector_labels(resource)),
+            publish_not_ready_addresses: Some(true),
+            ports: Some(vec![ServicePort {
+                name: Some("http".to_string()),
+                port: resource.spec.container_port,
+                target_port: Some(IntOrString::String("http".to_string())),
+                ..Default::default()
+            }]),
+            ..Default::default()
+        }),
+        ..Default::default()
+    }
+}
+
+/// Pod template shared by the Deployment and the StatefulSet
+fn pod_template(resource: &MyResource) -> k8s_openapi::api::core::v1::PodTemplateSpec {
+    let name = resource.name_any();
+    let labels = standard_labels(resource);
+    let probes = resource.spec.probes.as_ref();
+    let secrets = secret_projections(resource);
+
+    let mut env = vec![EnvVar {
+        name: "MESSAGE".to_string(),
+        value: Some(resource.spec.message.clone()),
+        ..Default::default()
+    }];
+    env.extend(secrets.env);
+    let mut volume_mounts = vec![VolumeMount {
+        name: "config".to_string(),
+        mount_path: CONFIG_MOUNT_PATH.to_string(),
+        read_only: Some(true),
+        ..Default::default()
+    }];
+    volume_mounts.extend(secrets.mounts);
+    let mut volumes = vec![Volume {
+        name: "config".to_string(),
+        config_map: Some(k8s_openapi::api::core::v1::ConfigMapVolumeSource {
+            name: name.clone(),
+            ..Default::default()
+        }),
+        ..Default::default()
+    }];
+    volumes.extend(secrets.volumes);
+    if let Some(ref storage) = resource.spec.storage {
+        volume_mounts.push(VolumeMount {
+            name: "storage".to_string(),
+            mount_path: storage.mount_path.clone(),
+            ..Default::default()
+        });
+        volumes.push(Volume {
+            name: "storage".to_string(),
+            persistent_volume_claim: Some(PersistentVolumeClaimVolumeSource {
+                claim_name: storage_claim_name(resource),
+                ..Default::default()
+            }),
+            ..Default::default()
+        });
+    }
+
+    k8s_openapi::api::core::v1::PodTemplateSpec {
+        metadata: Some(k8s_openapi::apimachinery::pkg::apis::meta::v1::ObjectMeta {
+            labels: Some(labels),
+            annotations: Some(BTreeMap::from([(
+                CONFIG_HASH_ANNOTATION.to_string(),
+                config_hash(&configmap_data(resource)),
+            )])),
+            ..Default::default()
+        }),
+        spec: Some(k8s_openapi::api::core::v1::PodSpec {
+            containers: [Container {
+                name: MAIN_CONTAINER_NAME.to_string(),
+                image: Some(resource.spec.image.clone()),
+                image_pull_policy: resource
+                    .spec
+                    .image_pull_policy
+                    .map(|policy| policy.to_string()),
+                ports: Some(vec![k8s_openapi::api::core::v1::ContainerPort {
+                    container_port: resource.spec.container_port,
+                    name: Some("http".to_string()),
+                    ..Default::default()
+                }]),
+                env: Some(env),
+                env_from: if secrets.env_from.is_empty() {
+                    None
+                } else {
+                    Some(secrets.env_from)
+                },
+                liveness_probe: Some(container_probe(
+                    probes.and_then(|p| p.liveness.as_ref()),
+                    resource.spec.container_port,
+                )),
+                readiness_probe: Some(container_probe(
+                    probes.and_then(|p| p.readiness.as_ref()),
+                    resource.spec.container_port,
+                )),
+                startup_probe: probes
+                    .and_then(|p| p.startup.as_ref())
+                    .map(|probe| container_probe(Some(probe), resource.spec.container_port)),
+                resources: Some(
+                    resource
+                        .spec
+                        .resources
+                        .clone()
+                        .unwrap_or_else(default_resources),
+                ),
+                volume_mounts: Some(volume_mounts),
+                security_context: Some(default_security_context()),
+                ..Default::default()
+            }]
+            .into_iter()
+            .chain(resource.spec.sidecars.iter().map(with_security_defaults))
+            .collect(),
+            init_containers: if resource.spec.init_containers.is_empty() {
+                None
+            } else {
+                Some(
+                    resource
+                        .spec
+                        .init_containers
+                        .iter()
+                        .map(with_security_defaults)
+                        .collect(),
+                )
+            },
+            volumes: Some(volumes),
+            node_selector: if resource.spec.node_selector.is_empty() {
+                None
+            } else {
+                Some(resource.spec.node_selector.clone())
+            },
+            tolerations: if resource.spec.tolerations.is_empty() {
+                None
+            } else {
+                Some(resource.spec.tolerations.clone())
+            },
+            affinity: resource
+                .spec
+                .affinity
+                .clone()
+                .or_else(|| default_affinity(resource)),
+            topology_spread_constraints: if resource.spec.topology_spread_constraints.is_empty() {
+                None
+            } else {
+                Some(resource.spec.topology_spread_constraints.clone())
+            },
+            priority_class_name: resource.spec.priority_class_name.clone(),
+            image_pull_secrets: if resource.spec.image_pull_secrets.is_empty() {
+                None
+            } else {
+                Some(resource.spec.image_pull_secrets.clone())
+            },
+            security_context: Some(k8s_openapi::api::core::v1::PodSecurityContext {
+                run_as_non_root: Some(true),
+                run_as_user: Some(1000),
+                fs_group: Some(1000),
+                ..Default::default()
+            }),
+            ..Default::default()
+        }),
+    }
+}
+
 /// Generate a Service for a MyResource
 pub fn generate_service(resource: &MyResource) -> Service {
     let name = resource.name_any();
     let labels = standard_labels(resource);
+    let exposure = resource.spec.service.clone().unwrap_or_default();
+
+    let ports = if exposure.ports.is_empty() {
+        vec![ServicePort {
+            port: resource.spec.container_port,
+            target_port: Some(IntOrString::String("http".to_string())),
+            name: Some("http".to_string()),
+            ..Default::default()
+        }]
+    } else {
+        exposure
+            .ports
+            .iter()
+            .map(|port| ServicePort {
+                name: port.name.clone(),
+                port: port.port,
+                target_port: Some(
+                    port.target_port
+                        .map_or_else(|| IntOrString::String("http".to_string()), IntOrString::Int),
+                ),
+                node_port: port.node_port,
+                protocol: port.protocol.clone(),
+                ..Default::default()
+            })
+            .collect()
+    };
+
+    let headless = exposure.type_ == ServiceType::Headless;
 
     Service {
         metadata: k8s_openapi::apimachinery::pkg::apis::meta::v1::ObjectMeta {
             name: Some(name.clone()),
             namespace: resource.namespace(),
             labels: Some(labels),
+            annotations: if exposure.annotations.is_empty() {
+                None
+            } else {
+                Some(exposure.annotations.clone())
+            },
             owner_references: Some(vec![owner_reference(resource)]),
             ..Default::default()
         },
         spec: Some(k8s_openapi::api::core::v1::ServiceSpec {
-            type_: Some("ClusterIP".to_string()),
+            type_: Some(if headless {
+                ServiceType::ClusterIP.to_string()
+            } else {
+                exposure.type_.to_string()
+            }),
+            cluster_ip: headless.then(|| "None".to_string()),
             selector: Some({
                 let mut selector = BTreeMap::new();
                 selector.insert("app.kubernetes.io/name".to_string(), name);
                 selector
             }),
-            ports: Some(vec![k8s_openapi::api::core::v1::ServicePort {
-                port: 80,
-                target_port: Some(
-                    k8s_openapi::apimachinery::pkg::util::intstr::IntOrString::String(
-                        "http".to_string(),
-                    ),
-                ),
-                name: Some("http".to_string()),
+            ports: Some(ports),
+            session_affinity: exposure.session_affinity.map(|a| a.to_string()),
+            external_traffic_policy: exposure.external_traffic_policy.map(|p| p.to_string()),
+            ..Default::default()
+        }),
+        ..Default::default()
+    }
+}
+
+/// Check whether a live Service must be deleted and recreated to reach the
+/// desired state.
+///
+/// `clusterIP` is immutable, so switching between a headless Service and one
+/// with a virtual IP can't be applied in place.
+pub fn service_requires_recreate(desired: &Service, live: &Service) -> bool {
+    let headless = |service: &Service| {
+        service.spec.as_ref().and_then(|s| s.cluster_ip.as_deref()) == Some("None")
+    };
+    headless(desired) != headless(live)
+}
+
+/// Port of the managed Service that routes point at by default
+pub fn service_port(resource: &MyResource) -> i32 {
+    resource
+        .spec
+        .service
+        .as_ref()
+        .and_then(|s| s.ports.first())
+        .map_or(resource.spec.container_port, |p| p.port)
+}
+
+/// Paths routed by `spec.ingress`, defaulting to every path
+fn ingress_paths(paths: &[IngressPath]) -> Vec<IngressPath> {
+    if paths.is_empty() {
+        vec![IngressPath {
+            path: "/".to_string(),
+            path_type: PathType::Prefix,
+        }]
+    } else {
+        paths.to_vec()
+    }
+}
+
+/// Generate an Ingress for a MyResource.
+///
+/// Returns `None` unless `spec.ingress` asks for an Ingress.
+pub fn generate_ingress(resource: &MyResource) -> Option<Ingress> {
+    let spec = resource
+        .spec
+        .ingress
+        .as_ref()
+        .filter(|i| i.kind == RouteKind::Ingress)?;
+    let name = resource.name_any();
+
+    let backend = IngressBackend {
+        service: Some(IngressServiceBackend {
+            name: name.clone(),
+            port: Some(ServiceBackendPort {
+                number: Some(spec.port.unwrap_or_else(|| service_port(resource))),
                 ..Default::default()
-            }]),
+            }),
+        }),
+        ..Default::default()
+    };
+    let http = HTTPIngressRuleValue {
+        paths: ingress_paths(&spec.paths)
+            .into_iter()
+            .map(|p| HTTPIngressPath {
+                path: Some(p.path),
+                path_type: p.path_type.to_string(),
+                backend: backend.clone(),
+            })
+            .collect(),
+    };
+    let rules = if spec.hosts.is_empty() {
+        vec![IngressRule {
+            host: None,
+            http: Some(http),
+        }]
+    } else {
+        spec.hosts
+            .iter()
+            .map(|host| IngressRule {
+                host: Some(host.clone()),
+                http: Some(http.clone()),
+            })
+            .collect()
+    };
+
+    Some(Ingress {
+        metadata: k8s_openapi::apimachinery::pkg::apis::meta::v1::ObjectMeta {
+            name: Some(name),
+            namespace: resource.namespace(),
+            labels: Some(standard_labels(resource)),
+            annotations: if spec.annotations.is_empty() {
+                None
+            } else {
+                Some(spec.annotations.clone())
+            },
+            owner_references: Some(vec![owner_reference(resource)]),
+            ..Default::default()
+        },
+        spec: Some(KubeIngressSpec {
+            ingress_class_name: spec.class_name.clone(),
+            rules: Some(rules),
+            tls: spec.tls_secret_name.as_ref().map(|secret| {
+                vec![IngressTLS {
+                    hosts: if spec.hosts.is_empty() {
+                        None
+                    } else {
+                        Some(spec.hosts.clone())
+                    },
+                    secret_name: Some(secret.clone()),
+                }]
+            }),
             ..Default::default()
         }),
         ..Default::default()
+    })
+}
+
+/// Generate a Gateway API HTTPRoute for a MyResource.
+///
+/// Returns `None` unless `spec.ingress` asks for an HTTPRoute.
+pub fn generate_http_route(resource: &MyResource) -> Option<HTTPRoute> {
+    let spec = resource
+        .spec
+        .ingress
+        .as_ref()
+        .filter(|i| i.kind == RouteKind::HTTPRoute)?;
+    let name = resource.name_any();
+
+    let mut route = HTTPRoute::new(
+        &name,
+        HTTPRouteSpec {
+            parent_refs: spec
+                .gateway
+                .iter()
+                .map(|gateway| ParentReference {
+                    name: gateway.name.clone(),
+                    namespace: gateway.namespace.clone(),
+                    section_name: gateway.section_name.clone(),
+                })
+                .collect(),
+            hostnames: spec.hosts.clone(),
+            rules: vec![HTTPRouteRule {
+                matches: ingress_paths(&spec.paths)
+                    .into_iter()
+                    .map(|p| HTTPRouteMatch {
+                        path: Some(HTTPPathMatch {
+                            type_: match p.path_type {
+                                PathType::Exact => "Exact".to_string(),
+                                _ => "PathPrefix".to_string(),
+                            },
+                            value: p.path,
+                        }),
+                    })
+                    .collect(),
+                backend_refs: vec![HTTPBackendRef {
+                    name: name.clone(),
+                    port: Some(spec.port.unwrap_or_else(|| service_port(resource))),
+                }],
+            }],
+        },
+    );
+    route.metadata.namespace = resource.namespace();
+    route.metadata.labels = Some(standard_labels(resource));
+    route.metadata.owner_references = Some(vec![owner_reference(resource)]);
+    if !spec.annotations.is_empty() {
+        route.metadata.annotations = Some(spec.annotations.clone());
     }
+    Some(route)
+}
+
+/// Generate a PodDisruptionBudget for a MyResource.
+///
+/// Returns `None` with a single replica (and no autoscaling beyond it), where
+/// any budget would either block node drains or allow evicting the only pod.
+/// Unhealthy pods can always be evicted, so a crash-looping workload never
+/// blocks a drain.
+pub fn generate_pdb(resource: &MyResource) -> Option<PodDisruptionBudget> {
+    if resource.spec.max_replicas() <= 1 {
+        return None;
+    }
+    let name = resource.name_any();
+    let budget = resource
+        .spec
+        .pod_disruption_budget
+        .clone()
+        .unwrap_or_default();
+    let max_unavailable = match (&budget.min_available, budget.max_unavailable) {
+        (None, None) => Some(IntOrString::Int(1)),
+        (_, max_unavailable) => max_unavailable,
+    };
+
+    Some(PodDisruptionBudget {
+        metadata: k8s_openapi::apimachinery::pkg::apis::meta::v1::ObjectMeta {
+            name: Some(name.clone()),
+            namespace: resource.namespace(),
+            labels: Some(standard_labels(resource)),
+            owner_references: Some(vec![owner_reference(resource)]),
+            ..Default::default()
+        },
+        spec: Some(PodDisruptionBudgetSpec {
+            selector: Some(LabelSelector {
+                match_labels: Some(BTreeMap::from([(
+                    "app.kubernetes.io/name".to_string(),
+                    name,
+                )])),
+                ..Default::default()
+            }),
+            min_available: budget.min_available,
+            max_unavailable,
+            unhealthy_pod_eviction_policy: Some("AlwaysAllow".to_string()),
+        }),
+        ..Default::default()
+    })
+}
+
+/// Generate a HorizontalPodAutoscaler for a MyResource.
+///
+/// Returns `None` without `spec.autoscaling`. Without a utilization target the
+/// HPA scales on `DEFAULT_CPU_UTILIZATION` of the CPU request.
+pub fn generate_hpa(resource: &MyResource) -> Option<HorizontalPodAutoscaler> {
+    let autoscaling = resource.spec.autoscaling.as_ref()?;
+    let name = resource.name_any();
+
+    let utilization_metric = |resource_name: &str, utilization: i32| MetricSpec {
+        type_: "Resource".to_string(),
+        resource: Some(ResourceMetricSource {
+            name: resource_name.to_string(),
+            target: MetricTarget {
+                type_: "Utilization".to_string(),
+                average_utilization: Some(utilization),
+                ..Default::default()
+            },
+        }),
+        ..Default::default()
+    };
+    let mut metrics = Vec::new();
+    if let Some(cpu) = autoscaling.cpu_target() {
+        metrics.push(utilization_metric("cpu", cpu));
+    }
+    if let Some(memory) = autoscaling.target_memory_utilization {
+        metrics.push(utilization_metric("memory", memory));
+    }
+
+    Some(HorizontalPodAutoscaler {
+        metadata: k8s_openapi::apimachinery::pkg::apis::meta::v1::ObjectMeta {
+            name: Some(name.clone()),
+            namespace: resource.namespace(),
+            labels: Some(standard_labels(resource)),
+            owner_references: Some(vec![owner_reference(resource)]),
+            ..Default::default()
+        },
+        spec: Some(HorizontalPodAutoscalerSpec {
+            scale_target_ref: CrossVersionObjectReference {
+                api_version: Some("apps/v1".to_string()),
+                kind: resource.spec.workload_type.to_string(),
+                name,
+            },
+            min_replicas: Some(resource.spec.min_replicas()),
+            max_replicas: autoscaling.max_replicas,
+            metrics: Some(metrics),
+            ..Default::default()
+        }),
+        ..Default::default()
+    })
 }
@@ -0,0 +1,98 @@
+//! Gateway API types.
+//!
+//! Only the subset of `gateway.networking.k8s.io/v1` HTTPRoute used by the
+//! operator is modelled. Fields not listed here are ignored when reading live
+//! objects, and never set when applying.
+
+use kube::CustomResource;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// API group of the Gateway API
+pub const GATEWAY_API_GROUP: &str = "gateway.networking.k8s.io";
+
+/// Routes HTTP traffic from a Gateway listener to Services
+#[derive(CustomResource, Clone, Debug, Default, PartialEq, Deserialize, Serialize, JsonSchema)]
+#[kube(
+    group = "gateway.networking.k8s.io",
+    version = "v1",
+    kind = "HTTPRoute",
+    plural = "httproutes",
+    namespaced
+)]
+#[serde(rename_all = "camelCase")]
+pub struct HTTPRouteSpec {
+    /// Gateways (or listeners) the route attaches to
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub parent_refs: Vec<ParentReference>,
+
+    /// Host names matched against the Host header
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub hostnames: Vec<String>,
+
+    /// Matching rules and the backends they forward to
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub rules: Vec<HTTPRouteRule>,
+}
+
+/// Reference to a parent Gateway
+#[derive(Clone, Debug, Default, PartialEq, Deserialize, Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ParentReference {
+    /// Name of the Gateway
+    pub name: String,
+
+    /// Namespace of the Gateway (defaults to the route namespace)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub namespace: Option<String>,
+
+    /// Listener of the Gateway
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub section_name: Option<String>,
+}
+
+/// A set of matches forwarding to backends
+#[derive(Clone, Debug, Default, PartialEq, Deserialize, Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct HTTPRouteRule {
+    /// Requests matching any of these are forwarded
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub matches: Vec<HTTPRouteMatch>,
+
+    /// Services requests are forwarded to
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub backend_refs: Vec<HTTPBackendRef>,
+}
+
+/// Request match of a rule
+#[derive(Clone, Debug, Default, PartialEq, Deserialize, Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct HTTPRouteMatch {
+    /// Path match
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub path: Option<HTTPPathMatch>,
+}
+
+/// Path match of a request
+#[derive(Clone, Debug, Default, PartialEq, Deserialize, Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct HTTPPathMatch {
+    /// Match type: `PathPrefix` or `Exact`
+    #[serde(rename = "type")]
+    pub type_: String,
+
+    /// Path to match
+    pub value: String,
+}
+
+/// Service a rule forwards to
+#[derive(Clone, Debug, Default, PartialEq, Deserialize, Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct HTTPBackendRef {
+    /// Name of the Service
+    pub name: String,
+
+    /// Port of the Service
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub port: Option<i32>,
+}
@@ -3,3 +3,4 @@
 //! Contains utilities for generating Kubernetes resources owned by MyResource.
 
 pub mod common;
+pub mod gateway;
@@ -0,0 +1,17 @@
+//! Autoscaling validation policy.
+//!
+//! Validates:
+//! - maxReplicas is within MIN_REPLICAS and MAX_REPLICAS
+//! - minReplicas (defaulting to spec.replicas) does not exceed maxReplicas
+//! - Utilization targets are positive and backed by a resource request
+
+use super::{ValidationContext, ValidationResult, from_validation};
+use crate::controller::validation::validate_autoscaling;
+
+/// Validate the autoscaling settings
+pub fn validate(ctx: &ValidationContext<'_>) -> ValidationResult {
+    from_validation(
+        "InvalidAutoscaling",
+        validate_autoscaling(&ctx.resource.spec),
+    )
+}
@@ -0,0 +1,13 @@
+//! ConfigMap files validation policy.
+//!
+//! Validates:
+//! - `spec.config` keys are valid file names and don't shadow `message`
+//! - The ConfigMap data fits within the Kubernetes size limit
+
+use super::{ValidationContext, ValidationResult, from_validation};
+use crate::controller::validation::validate_config;
+
+/// Validate the ConfigMap files
+pub fn validate(ctx: &ValidationContext<'_>) -> ValidationResult {
+    from_validation("InvalidConfig", validate_config(&ctx.resource.spec))
+}
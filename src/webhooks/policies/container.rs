@@ -0,0 +1,18 @@
+//! Container settings validation policy.
+//!
+//! Validates:
+//! - Image is a plausible image reference
+//! - Container port is within the valid port range
+//! - Image pull secrets are named and not repeated
+//! - Resource quantities parse and requests do not exceed limits
+//! - Probes set at most one handler with valid ports and thresholds
+//! - Secret references and the generated Secret are well formed
+//! - Init and sidecar containers have unique names and non-conflicting ports
+
+use super::{ValidationContext, ValidationResult, from_validation};
+use crate::controller::validation::validate_container;
+
+/// Validate container settings
+pub fn validate(ctx: &ValidationContext<'_>) -> ValidationResult {
+    from_validation("InvalidContainer", validate_container(&ctx.resource.spec))
+}
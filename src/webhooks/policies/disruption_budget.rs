@@ -0,0 +1,17 @@
+//! PodDisruptionBudget validation policy.
+//!
+//! Validates:
+//! - At most one of minAvailable and maxUnavailable is set
+//! - Values are non-negative counts or percentages up to 100%
+//! - The budget still allows at least one eviction for multi-replica resources
+
+use super::{ValidationContext, ValidationResult, from_validation};
+use crate::controller::validation::validate_disruption_budget;
+
+/// Validate the PodDisruptionBudget settings
+pub fn validate(ctx: &ValidationContext<'_>) -> ValidationResult {
+    from_validation(
+        "InvalidDisruptionBudget",
+        validate_disruption_budget(&ctx.resource.spec),
+    )
+}
@@ -5,8 +5,16 @@
 //! Validates:
 //! - Certain fields cannot be changed after creation
 //! - Prevents scaling to zero from a running state
+//! - `spec.workloadType` only changes with the `WORKLOAD_MIGRATION_ANNOTATION`
+//! - StatefulSet volume claim templates are not changed
+//! - `spec.storage` is never shrunk and keeps its storage class and access modes
+
+use kube::ResourceExt;
 
 use super::{ValidationContext, ValidationResult};
+use crate::controller::reconciler::WORKLOAD_MIGRATION_ANNOTATION;
+use crate::controller::validation::parse_quantity;
+use crate::crd::WorkloadType;
 
 /// Validate immutability constraints on UPDATE operations
 pub fn validate(ctx: &ValidationContext<'_>) -> ValidationResult {
@@ -25,8 +33,62 @@ pub fn validate(ctx: &ValidationContext<'_>) -> ValidationResult {
         );
     }
 
-    // Example: Add more immutability checks here
-    // For instance, preventing changes to certain labels or annotations
+    // Switching workloads replaces every pod (and leaves StatefulSet volumes
+    // behind), so it has to be asked for explicitly
+    let migration_allowed = new
+        .annotations()
+        .get(WORKLOAD_MIGRATION_ANNOTATION)
+        .is_some_and(|v| v == "true");
+    if old.spec.workload_type != new.spec.workload_type && !migration_allowed {
+        return ValidationResult::denied(
+            "WorkloadMigrationNotAllowed",
+            &format!(
+                "spec.workloadType cannot change from {} to {} without the {}: \"true\" annotation",
+                old.spec.workload_type, new.spec.workload_type, WORKLOAD_MIGRATION_ANNOTATION
+            ),
+        );
+    }
+
+    // The API server rejects any update to a StatefulSet's volumeClaimTemplates
+    if old.spec.workload_type == WorkloadType::StatefulSet
+        && new.spec.workload_type == WorkloadType::StatefulSet
+        && old.spec.volume_claim_templates != new.spec.volume_claim_templates
+    {
+        return ValidationResult::denied(
+            "ImmutableField",
+            "spec.volumeClaimTemplates cannot be changed on a StatefulSet",
+        );
+    }
+
+    // A bound PersistentVolumeClaim can only grow, and its class and access
+    // modes are fixed
+    if let (Some(old_storage), Some(new_storage)) = (&old.spec.storage, &new.spec.storage) {
+        if let (Some(old_size), Some(new_size)) = (
+            parse_quantity(&old_storage.size.0),
+            parse_quantity(&new_storage.size.0),
+        ) && new_size < old_size
+        {
+            return ValidationResult::denied(
+                "StorageShrinkNotAllowed",
+                &format!(
+                    "spec.storage.size cannot shrink from {} to {}",
+                    old_storage.size.0, new_storage.size.0
+                ),
+            );
+        }
+        if old_storage.storage_class_name != new_storage.storage_class_name {
+            return ValidationResult::denied(
+                "ImmutableField",
+                "spec.storage.storageClassName cannot be changed",
+            );
+        }
+        if old_storage.access_modes != new_storage.access_modes {
+            return ValidationResult::denied(
+                "ImmutableField",
+                "spec.storage.accessModes cannot be changed",
+            );
+        }
+    }
 
     ValidationResult::allowed()
 }
@@ -35,7 +97,8 @@ pub fn validate(ctx: &ValidationContext<'_>) -> ValidationResult {
 #[allow(clippy::unwrap_used)]
 mod tests {
     use super::*;
-    use crate::crd::{MyResource, MyResourceSpec};
+    use crate::crd::{MyResource, MyResourceSpec, StorageSpec};
+    use k8s_openapi::apimachinery::pkg::api::resource::Quantity;
     use k8s_openapi::apimachinery::pkg::apis::meta::v1::ObjectMeta;
     use std::collections::BTreeMap;
 
@@ -50,6 +113,7 @@ mod tests {
                 replicas,
                 message: "test".to_string(),
                 labels: BTreeMap::new(),
+                ..Default::default()
             },
             status: None,
         }
@@ -88,6 +152,87 @@ mod tests {
         assert!(result.reason.unwrap().contains("InvalidScaleDown"));
     }
 
+    #[test]
+    fn test_workload_migration_requires_annotation() {
+        let old = create_resource(2);
+        let mut new = create_resource(2);
+        new.spec.workload_type = WorkloadType::StatefulSet;
+
+        let ctx = ValidationContext {
+            resource: &new,
+            old_resource: Some(&old),
+            dry_run: false,
+            namespace: Some("default"),
+        };
+        let result = validate(&ctx);
+        assert!(!result.allowed);
+        assert!(
+            result
+                .reason
+                .unwrap()
+                .contains("WorkloadMigrationNotAllowed")
+        );
+
+        new.annotations_mut().insert(
+            WORKLOAD_MIGRATION_ANNOTATION.to_string(),
+            "true".to_string(),
+        );
+        let ctx = ValidationContext {
+            resource: &new,
+            old_resource: Some(&old),
+            dry_run: false,
+            namespace: Some("default"),
+        };
+        assert!(validate(&ctx).allowed);
+    }
+
+    #[test]
+    fn test_storage_can_grow_but_not_shrink() {
+        let with_size = |size: &str| {
+            let mut resource = create_resource(1);
+            resource.spec.storage = Some(StorageSpec {
+                size: Quantity(size.to_string()),
+                ..Default::default()
+            });
+            resource
+        };
+        let old = with_size("10Gi");
+
+        let grown = with_size("20Gi");
+        let ctx = ValidationContext {
+            resource: &grown,
+            old_resource: Some(&old),
+            dry_run: false,
+            namespace: Some("default"),
+        };
+        assert!(validate(&ctx).allowed);
+
+        let shrunk = with_size("5Gi");
+        let ctx = ValidationContext {
+            resource: &shrunk,
+            old_resource: Some(&old),
+            dry_run: false,
+            namespace: Some("default"),
+        };
+        let result = validate(&ctx);
+        assert!(!result.allowed);
+        assert!(result.reason.unwrap().contains("StorageShrinkNotAllowed"));
+
+        let mut reclassed = with_size("10Gi");
+        if let Some(storage) = reclassed.spec.storage.as_mut() {
+            storage.storage_class_name = Some("fast".to_string());
+        }
+        let ctx = ValidationContext {
+            resource: &reclassed,
+            old_resource: Some(&old),
+            dry_run: false,
+            namespace: Some("default"),
+        };
+        let result = validate(&ctx);
+        assert!(!result.allowed);
+        assert!(result.reason.unwrap().contains("ImmutableField"));
+    }
+
     #[test]
     fn test_create_allows_zero() {
         // On CREATE, there's no old_resource
@@ -0,0 +1,14 @@
+//! Ingress / HTTPRoute validation policy.
+//!
+//! Validates:
+//! - Hosts are valid (optionally wildcard) host names and not repeated
+//! - Paths are absolute and the routed port is a port of the Service
+//! - HTTPRoutes reference a Gateway and leave TLS and class to it
+
+use super::{ValidationContext, ValidationResult, from_validation};
+use crate::controller::validation::validate_ingress;
+
+/// Validate the Ingress / HTTPRoute settings
+pub fn validate(ctx: &ValidationContext<'_>) -> ValidationResult {
+    from_validation("InvalidIngress", validate_ingress(&ctx.resource.spec))
+}
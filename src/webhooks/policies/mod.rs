@@ -1,12 +1,25 @@
 //! Validation policies for MyResource admission webhooks.
 //!
 //! Policies are organized into tiers:
-//! - Tier 1 (Critical): Always enforced (replica validation)
+//! - Tier 1 (Critical): Always enforced (replica, container, scheduling, config,
+//!   service, ingress, autoscaling, rollout, disruption budget, workload and
+//!   storage validation)
 //! - Tier 2 (Update): Only enforced on UPDATE operations (immutability)
 
+pub mod autoscaling;
+pub mod config;
+pub mod container;
+pub mod disruption_budget;
 pub mod immutability;
+pub mod ingress;
 pub mod replicas;
+pub mod rollout;
+pub mod scheduling;
+pub mod service;
+pub mod storage;
+pub mod workload;
 
+use crate::controller::error::{Error, Result};
 use crate::crd::MyResource;
 
 /// Result of a validation check
@@ -40,6 +53,16 @@ impl ValidationResult {
     }
 }
 
+/// Convert the outcome of a spec validator into a validation result, denying
+/// with `reason` and the validator's message
+pub fn from_validation(reason: &str, result: Result<()>) -> ValidationResult {
+    match result {
+        Ok(()) => ValidationResult::allowed(),
+        Err(Error::Validation(message)) => ValidationResult::denied(reason, &message),
+        Err(e) => ValidationResult::denied(reason, &e.to_string()),
+    }
+}
+
 /// Context for validation
 pub struct ValidationContext<'a> {
     /// The resource being validated
@@ -67,6 +90,56 @@ pub fn validate_all(ctx: &ValidationContext<'_>) -> ValidationResult {
         return result;
     }
 
+    let result = container::validate(ctx);
+    if !result.allowed {
+        return result;
+    }
+
+    let result = scheduling::validate(ctx);
+    if !result.allowed {
+        return result;
+    }
+
+    let result = config::validate(ctx);
+    if !result.allowed {
+        return result;
+    }
+
+    let result = service::validate(ctx);
+    if !result.allowed {
+        return result;
+    }
+
+    let result = ingress::validate(ctx);
+    if !result.allowed {
+        return result;
+    }
+
+    let result = autoscaling::validate(ctx);
+    if !result.allowed {
+        return result;
+    }
+
+    let result = rollout::validate(ctx);
+    if !result.allowed {
+        return result;
+    }
+
+    let result = disruption_budget::validate(ctx);
+    if !result.allowed {
+        return result;
+    }
+
+    let result = workload::validate(ctx);
+    if !result.allowed {
+        return result;
+    }
+
+    let result = storage::validate(ctx);
+    if !result.allowed {
+        return result;
+    }
+
     // Tier 2: Update validations (only for UPDATE operations)
     if ctx.is_update() {
         let result = immutability::validate(ctx);
@@ -77,3 +150,220 @@ pub fn validate_all(ctx: &ValidationContext<'_>) -> ValidationResult {
 
     ValidationResult::allowed()
 }
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use crate::crd::{
+        AutoscalingSpec, DisruptionBudgetSpec, GatewayRef, IngressSpec, MyResourceSpec,
+        RolloutSpec, RolloutStrategy, ServiceExposureSpec, ServicePortSpec, ServiceType,
+        StorageSpec, VolumeAccessMode, WorkloadType,
+    };
+    use k8s_openapi::api::core::v1::{
+        PersistentVolumeClaim, PersistentVolumeClaimSpec, Toleration, VolumeResourceRequirements,
+    };
+    use k8s_openapi::apimachinery::pkg::api::resource::Quantity;
+    use k8s_openapi::apimachinery::pkg::apis::meta::v1::ObjectMeta;
+    use k8s_openapi::apimachinery::pkg::util::intstr::IntOrString;
+
+    type Policy = fn(&ValidationContext<'_>) -> ValidationResult;
+
+    /// Run a single policy against a new resource with the given spec
+    fn check(policy: Policy, spec: MyResourceSpec) -> ValidationResult {
+        let resource = MyResource {
+            metadata: ObjectMeta {
+                name: Some("test".to_string()),
+                namespace: Some("default".to_string()),
+                ..Default::default()
+            },
+            spec,
+            status: None,
+        };
+        policy(&ValidationContext {
+            resource: &resource,
+            old_resource: None,
+            dry_run: false,
+            namespace: Some("default"),
+        })
+    }
+
+    #[test]
+    fn test_spec_policies_deny_with_their_reason() {
+        // Each spec policy wraps a validator tested in controller::validation;
+        // this only checks that it is wired to the right reason
+        let cases: Vec<(Policy, &str, MyResourceSpec, &str)> = vec![
+            (
+                container::validate,
+                "InvalidContainer",
+                MyResourceSpec {
+                    image: String::new(),
+                    ..Default::default()
+                },
+                "spec.image",
+            ),
+            (
+                scheduling::validate,
+                "InvalidScheduling",
+                MyResourceSpec {
+                    tolerations: vec![Toleration {
+                        operator: Some("Maybe".to_string()),
+                        ..Default::default()
+                    }],
+                    ..Default::default()
+                },
+                "spec.tolerations[0].operator",
+            ),
+            (
+                config::validate,
+                "InvalidConfig",
+                MyResourceSpec {
+                    config: [("conf.d/default.conf".to_string(), String::new())].into(),
+                    ..Default::default()
+                },
+                "conf.d/default.conf",
+            ),
+            (
+                service::validate,
+                "InvalidService",
+                MyResourceSpec {
+                    service: Some(ServiceExposureSpec {
+                        type_: ServiceType::Headless,
+                        ports: vec![ServicePortSpec {
+                            port: 80,
+                            node_port: Some(30080),
+                            ..Default::default()
+                        }],
+                        ..Default::default()
+                    }),
+                    ..Default::default()
+                },
+                "spec.service.ports[0].nodePort",
+            ),
+            (
+                ingress::validate,
+                "InvalidIngress",
+                MyResourceSpec {
+                    ingress: Some(IngressSpec {
+                        gateway: Some(GatewayRef {
+                            name: "public".to_string(),
+                            ..Default::default()
+                        }),
+                        ..Default::default()
+                    }),
+                    ..Default::default()
+                },
+                "spec.ingress.gateway",
+            ),
+            (
+                autoscaling::validate,
+                "InvalidAutoscaling",
+                MyResourceSpec {
+                    autoscaling: Some(AutoscalingSpec {
+                        max_replicas: crate::controller::validation::MAX_REPLICAS + 1,
+                        ..Default::default()
+                    }),
+                    ..Default::default()
+                },
+                "maxReplicas",
+            ),
+            (
+                rollout::validate,
+                "InvalidRollout",
+                MyResourceSpec {
+                    rollout: Some(RolloutSpec {
+                        strategy: RolloutStrategy::Recreate,
+                        max_surge: Some(IntOrString::Int(1)),
+                        ..Default::default()
+                    }),
+                    ..Default::default()
+                },
+                "RollingUpdate",
+            ),
+            (
+                disruption_budget::validate,
+                "InvalidDisruptionBudget",
+                MyResourceSpec {
+                    replicas: 3,
+                    pod_disruption_budget: Some(DisruptionBudgetSpec {
+                        min_available: Some(IntOrString::Int(3)),
+                        max_unavailable: None,
+                    }),
+                    ..Default::default()
+                },
+                "block all evictions",
+            ),
+            (
+                workload::validate,
+                "InvalidWorkload",
+                MyResourceSpec {
+                    workload_type: WorkloadType::Deployment,
+                    volume_claim_templates: vec![PersistentVolumeClaim {
+                        metadata: ObjectMeta {
+                            name: Some("data".to_string()),
+                            ..Default::default()
+                        },
+                        spec: Some(PersistentVolumeClaimSpec {
+                            resources: Some(VolumeResourceRequirements {
+                                requests: Some(
+                                    [("storage".to_string(), Quantity("1Gi".to_string()))].into(),
+                                ),
+                                ..Default::default()
+                            }),
+                            ..Default::default()
+                        }),
+                        ..Default::default()
+                    }],
+                    ..Default::default()
+                },
+                "StatefulSet",
+            ),
+            (
+                storage::validate,
+                "InvalidStorage",
+                MyResourceSpec {
+                    storage: Some(StorageSpec {
+                        mount_path: "/etc/config".to_string(),
+                        ..Default::default()
+                    }),
+                    ..Default::default()
+                },
+                "mountPath",
+            ),
+            (
+                storage::validate,
+                "InvalidStorage",
+                MyResourceSpec {
+                    replicas: 2,
+                    storage: Some(StorageSpec::default()),
+                    ..Default::default()
+                },
+                "ReadWriteOnce",
+            ),
+        ];
+
+        for (policy, reason, spec, message) in cases {
+            assert!(
+                check(policy, MyResourceSpec::default()).allowed,
+                "{}",
+                reason
+            );
+            let result = check(policy, spec);
+            assert!(!result.allowed, "{}", reason);
+            assert_eq!(result.reason.as_deref(), Some(reason));
+            let denial = result.message.unwrap();
+            assert!(denial.contains(message), "{}: {}", reason, denial);
+        }
+
+        // Shared storage can be mounted by every replica
+        let shared = MyResourceSpec {
+            replicas: 2,
+            storage: Some(StorageSpec {
+                access_modes: vec![VolumeAccessMode::ReadWriteMany],
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+        assert!(check(storage::validate, shared).allowed);
+    }
+}
@@ -55,6 +55,7 @@ mod tests {
                 replicas,
                 message: "test".to_string(),
                 labels: BTreeMap::new(),
+                ..Default::default()
             },
             status: None,
         }
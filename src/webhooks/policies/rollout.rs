@@ -0,0 +1,14 @@
+//! Rollout validation policy.
+//!
+//! Validates:
+//! - Rollout settings are only used with the Deployment workload type
+//! - maxSurge / maxUnavailable are valid, RollingUpdate only and not both 0
+//! - progressDeadlineSeconds is positive
+
+use super::{ValidationContext, ValidationResult, from_validation};
+use crate::controller::validation::validate_rollout;
+
+/// Validate the rollout settings
+pub fn validate(ctx: &ValidationContext<'_>) -> ValidationResult {
+    from_validation("InvalidRollout", validate_rollout(&ctx.resource.spec))
+}
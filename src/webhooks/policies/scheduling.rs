@@ -0,0 +1,15 @@
+//! Pod scheduling validation policy.
+//!
+//! Validates:
+//! - Tolerations use a known operator and effect
+//! - Topology spread constraints have a positive skew, a topology key and a
+//!   known `whenUnsatisfiable` action
+//! - Node selector keys and the priority class name are not empty
+
+use super::{ValidationContext, ValidationResult, from_validation};
+use crate::controller::validation::validate_scheduling;
+
+/// Validate pod scheduling settings
+pub fn validate(ctx: &ValidationContext<'_>) -> ValidationResult {
+    from_validation("InvalidScheduling", validate_scheduling(&ctx.resource.spec))
+}
@@ -0,0 +1,14 @@
+//! Service exposure validation policy.
+//!
+//! Validates:
+//! - Service ports are in range, uniquely named and use a known protocol
+//! - Node ports and `externalTrafficPolicy` are only set for NodePort and
+//!   LoadBalancer Services
+
+use super::{ValidationContext, ValidationResult, from_validation};
+use crate::controller::validation::validate_service;
+
+/// Validate the Service exposure settings
+pub fn validate(ctx: &ValidationContext<'_>) -> ValidationResult {
+    from_validation("InvalidService", validate_service(&ctx.resource.spec))
+}
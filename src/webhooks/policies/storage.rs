@@ -0,0 +1,16 @@
+//! Storage validation policy.
+//!
+//! Validates:
+//! - The requested size is a positive quantity
+//! - Access modes are set and fit the replica count: ReadWriteOncePod only
+//!   with one replica, and ReadWriteOnce (the default) only when at most one
+//!   replica can run, unless a multi-node mode is also listed
+//! - The mount path is absolute and doesn't overlap operator-managed mounts
+
+use super::{ValidationContext, ValidationResult, from_validation};
+use crate::controller::validation::validate_storage;
+
+/// Validate the persistent storage settings
+pub fn validate(ctx: &ValidationContext<'_>) -> ValidationResult {
+    from_validation("InvalidStorage", validate_storage(&ctx.resource.spec))
+}
@@ -0,0 +1,14 @@
+//! Workload validation policy.
+//!
+//! Validates:
+//! - Volume claim templates are only used with the StatefulSet workload type
+//! - Claim names are unique DNS labels that don't collide with other volumes
+//! - Each claim requests storage
+
+use super::{ValidationContext, ValidationResult, from_validation};
+use crate::controller::validation::validate_workload;
+
+/// Validate the workload type and volume claim templates
+pub fn validate(ctx: &ValidationContext<'_>) -> ValidationResult {
+    from_validation("InvalidWorkload", validate_workload(&ctx.resource.spec))
+}
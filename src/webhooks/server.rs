@@ -4,20 +4,23 @@
 //!
 //! To enable webhooks:
 //! 1. Deploy cert-manager for TLS certificates
-//! 2. Create a ValidatingWebhookConfiguration
+//! 2. Create a ValidatingWebhookConfiguration with a rule for `myresources`
+//!    (path `/validate-myresource`) and one for `myresources/scale` (path
+//!    `/validate-myresource-scale`)
 //! 3. Mount the TLS certificate secret to the operator pod at /etc/webhook/certs/
 //!
 //! The webhook server starts automatically when certificates are present.
 
 use axum::{Json, Router, extract::State, http::StatusCode, response::IntoResponse, routing::post};
-use kube::Client;
-use kube::Resource;
+use k8s_openapi::api::autoscaling::v1::Scale;
 use kube::core::admission::{AdmissionRequest, AdmissionResponse, AdmissionReview, Operation};
+use kube::{Api, Client, Resource};
 use std::sync::Arc;
 use tracing::{debug, error, info, warn};
 
+use crate::controller::error::Error;
 use crate::crd::MyResource;
-use crate::webhooks::policies::{ValidationContext, validate_all};
+use crate::webhooks::policies::{ValidationContext, ValidationResult, validate_all};
 
 /// Default path to webhook TLS certificate
 pub const WEBHOOK_CERT_PATH: &str = "/etc/webhook/certs/tls.crt";
@@ -28,7 +31,6 @@ pub const WEBHOOK_PORT: u16 = 9443;
 
 /// Shared state for webhook handlers
 pub struct WebhookState {
-    #[allow(dead_code)]
     pub client: Client,
 }
 
@@ -55,6 +57,10 @@ fn deny_with_reason<T: Resource<DynamicType = ()>>(
 pub fn create_webhook_router(state: Arc<WebhookState>) -> Router {
     Router::new()
         .route("/validate-myresource", post(validate_myresource))
+        .route(
+            "/validate-myresource-scale",
+            post(validate_myresource_scale),
+        )
         .with_state(state)
 }
 
@@ -124,7 +130,123 @@ async fn validate_myresource(
 
     // Run tiered validation policies
     let result = validate_all(&ctx);
+    (StatusCode::OK, Json(review_response(&request, result)))
+}
 
+/// Validate a scale subresource update of a MyResource.
+///
+/// Scale requests only carry the replica counts, so the old and new counts of
+/// the request are applied to the stored MyResource and validated as an
+/// update, running the same policies as a change to `spec.replicas`. If the
+/// MyResource can't be read because of a transient API error, the request is
+/// allowed with a warning rather than blocking every scale until the API
+/// server recovers; the controller still validates the spec on its next pass.
+async fn validate_myresource_scale(
+    State(state): State<Arc<WebhookState>>,
+    Json(review): Json<AdmissionReview<Scale>>,
+) -> impl IntoResponse {
+    let request: AdmissionRequest<Scale> = match review.try_into() {
+        Ok(req) => req,
+        Err(e) => {
+            error!(error = %e, "Failed to extract admission request");
+            return (
+                StatusCode::BAD_REQUEST,
+                Json(
+                    AdmissionResponse::invalid(format!("Invalid AdmissionReview: {}", e))
+                        .into_review(),
+                ),
+            );
+        }
+    };
+
+    let uid = &request.uid;
+    debug!(
+        uid = %uid,
+        operation = ?request.operation,
+        namespace = ?request.namespace,
+        name = ?request.name,
+        "Processing scale admission request"
+    );
+
+    let Some(replicas) = request
+        .object
+        .as_ref()
+        .and_then(|scale| scale.spec.as_ref())
+        .and_then(|spec| spec.replicas)
+    else {
+        error!(uid = %uid, "Missing replicas in scale request");
+        return (
+            StatusCode::OK,
+            Json(deny_with_reason(
+                &request,
+                "Missing replicas in scale request",
+                "InvalidRequest",
+            )),
+        );
+    };
+
+    let old_replicas = request
+        .old_object
+        .as_ref()
+        .and_then(|scale| scale.spec.as_ref())
+        .and_then(|spec| spec.replicas);
+
+    // The rest of the spec isn't part of a Scale, so it is read from the API server
+    let namespace = request.namespace.as_deref().unwrap_or("default");
+    let api: Api<MyResource> = Api::namespaced(state.client.clone(), namespace);
+    let stored = match api.get(&request.name).await {
+        Ok(resource) => resource,
+        Err(e) => {
+            let e = Error::from(e);
+            if e.is_retryable() {
+                warn!(uid = %uid, error = %e, "Failed to get scaled MyResource, allowing scale request");
+                let mut response = AdmissionResponse::from(&request);
+                response.warnings = Some(vec![format!(
+                    "MyResource {} could not be read to validate the replica count: {}",
+                    request.name, e
+                )]);
+                return (StatusCode::OK, Json(response.into_review()));
+            }
+            error!(uid = %uid, error = %e, "Failed to get scaled MyResource");
+            return (
+                StatusCode::OK,
+                Json(deny_with_reason(
+                    &request,
+                    &format!("Failed to get MyResource {}: {}", request.name, e),
+                    "InternalError",
+                )),
+            );
+        }
+    };
+    let current = match old_replicas {
+        Some(old_replicas) => scaled_resource(&stored, old_replicas),
+        None => stored,
+    };
+    let scaled = scaled_resource(&current, replicas);
+
+    let ctx = ValidationContext {
+        resource: &scaled,
+        old_resource: Some(&current),
+        dry_run: request.dry_run,
+        namespace: request.namespace.as_deref(),
+    };
+    let result = validate_all(&ctx);
+    (StatusCode::OK, Json(review_response(&request, result)))
+}
+
+/// A MyResource with the replica count of a scale request applied
+fn scaled_resource(current: &MyResource, replicas: i32) -> MyResource {
+    let mut scaled = current.clone();
+    scaled.spec.replicas = replicas;
+    scaled
+}
+
+/// Turn a validation result into an admission response
+fn review_response<T: Resource<DynamicType = ()>>(
+    request: &AdmissionRequest<T>,
+    result: ValidationResult,
+) -> AdmissionReview<kube::core::DynamicObject> {
+    let uid = &request.uid;
     if !result.allowed {
         let reason = result
             .reason
@@ -133,17 +255,11 @@ async fn validate_myresource(
             .message
             .unwrap_or_else(|| "Validation failed".to_string());
         warn!(uid = %uid, reason = %reason, message = %message, "Admission request denied");
-        return (
-            StatusCode::OK,
-            Json(deny_with_reason(&request, &message, &reason)),
-        );
+        return deny_with_reason(request, &message, &reason);
     }
 
     info!(uid = %uid, "Admission request allowed");
-    (
-        StatusCode::OK,
-        Json(AdmissionResponse::from(&request).into_review()),
-    )
+    AdmissionResponse::from(request).into_review()
 }
 
 /// Errors that can occur when running the webhook server
@@ -168,7 +284,8 @@ impl std::error::Error for WebhookError {}
 
 /// Run the webhook server with TLS
 ///
-/// Binds to 0.0.0.0:9443 and serves the /validate-myresource endpoint.
+/// Binds to 0.0.0.0:9443 and serves the /validate-myresource and
+/// /validate-myresource-scale endpoints.
 /// TLS certificates are loaded from the paths specified.
 ///
 /// # Arguments
@@ -223,6 +340,7 @@ mod tests {
                 replicas,
                 message: message.to_string(),
                 labels: BTreeMap::new(),
+                ..Default::default()
             },
             status: None,
         }
@@ -271,6 +389,161 @@ mod tests {
         assert!(result.allowed);
     }
 
+    #[test]
+    fn test_scale_request_runs_update_policies() {
+        let current = create_resource(3, "test");
+
+        let scaled = scaled_resource(&current, 5);
+        assert_eq!(scaled.spec.replicas, 5);
+        assert_eq!(scaled.spec.message, current.spec.message);
+        let ctx = ValidationContext {
+            resource: &scaled,
+            old_resource: Some(&current),
+            dry_run: false,
+            namespace: Some("default"),
+        };
+        assert!(validate_all(&ctx).allowed);
+
+        let scaled = scaled_resource(&current, 0);
+        let ctx = ValidationContext {
+            resource: &scaled,
+            old_resource: Some(&current),
+            dry_run: false,
+            namespace: Some("default"),
+        };
+        let result = validate_all(&ctx);
+        assert!(!result.allowed);
+        assert!(result.reason.unwrap().contains("InvalidReplicas"));
+    }
+
+    /// Send a scale request from `old` to `new` replicas to the handler, with
+    /// the API server answering the read of the MyResource with `stored`
+    async fn review_scale(
+        stored: http::Response<kube::client::Body>,
+        old: i32,
+        new: i32,
+    ) -> serde_json::Value {
+        use axum::response::IntoResponse;
+        use http::{Request, Response};
+        use kube::client::Body;
+
+        let (service, mut handle) = tower_test::mock::pair::<Request<Body>, Response<Body>>();
+        let server = tokio::spawn(async move {
+            let (request, send) = handle.next_request().await.unwrap();
+            assert_eq!(request.method(), http::Method::GET);
+            assert_eq!(
+                request.uri().path(),
+                "/apis/myoperator.example.com/v1alpha1/namespaces/default/myresources/test"
+            );
+            send.send_response(stored);
+        });
+        let state = Arc::new(WebhookState::new(Client::new(service, "default")));
+
+        let scale = |replicas: i32| {
+            serde_json::json!({
+                "apiVersion": "autoscaling/v1",
+                "kind": "Scale",
+                "metadata": { "name": "test", "namespace": "default" },
+                "spec": { "replicas": replicas },
+            })
+        };
+        let review: AdmissionReview<Scale> = serde_json::from_value(serde_json::json!({
+            "apiVersion": "admission.k8s.io/v1",
+            "kind": "AdmissionReview",
+            "request": {
+                "uid": "scale-uid",
+                "kind": { "group": "autoscaling", "version": "v1", "kind": "Scale" },
+                "resource": {
+                    "group": "myoperator.example.com",
+                    "version": "v1alpha1",
+                    "resource": "myresources",
+                },
+                "subResource": "scale",
+                "name": "test",
+                "namespace": "default",
+                "operation": "UPDATE",
+                "userInfo": {},
+                "object": scale(new),
+                "oldObject": scale(old),
+                "dryRun": false,
+            },
+        }))
+        .unwrap();
+
+        let response = validate_myresource_scale(State(state), Json(review))
+            .await
+            .into_response();
+        server.await.unwrap();
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let review: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        review.get("response").cloned().unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_scale_handler_validates_stored_resource() {
+        let stored = || {
+            http::Response::builder()
+                .body(kube::client::Body::from(
+                    serde_json::to_vec(&create_resource(3, "test")).unwrap(),
+                ))
+                .unwrap()
+        };
+
+        let response = review_scale(stored(), 3, 5).await;
+        assert_eq!(response.pointer("/allowed"), Some(&serde_json::json!(true)));
+
+        let response = review_scale(stored(), 3, 0).await;
+        assert_eq!(
+            response.pointer("/allowed"),
+            Some(&serde_json::json!(false))
+        );
+        let message = response
+            .pointer("/status/message")
+            .unwrap()
+            .as_str()
+            .unwrap();
+        assert!(message.contains("InvalidReplicas"));
+    }
+
+    #[tokio::test]
+    async fn test_scale_handler_allows_on_transient_error() {
+        let unavailable = |code: u16, reason: &str| {
+            http::Response::builder()
+                .status(code)
+                .body(kube::client::Body::from(
+                    serde_json::to_vec(&serde_json::json!({
+                        "kind": "Status",
+                        "apiVersion": "v1",
+                        "status": "Failure",
+                        "reason": reason,
+                        "code": code,
+                    }))
+                    .unwrap(),
+                ))
+                .unwrap()
+        };
+
+        let response = review_scale(unavailable(503, "ServiceUnavailable"), 3, 5).await;
+        assert_eq!(response.pointer("/allowed"), Some(&serde_json::json!(true)));
+        assert!(
+            response
+                .pointer("/warnings/0")
+                .unwrap()
+                .as_str()
+                .unwrap()
+                .contains("test")
+        );
+
+        // A permanent error still fails closed
+        let response = review_scale(unavailable(403, "Forbidden"), 3, 5).await;
+        assert_eq!(
+            response.pointer("/allowed"),
+            Some(&serde_json::json!(false))
+        );
+    }
+
     #[test]
     fn test_scale_to_zero_on_update() {
         let old = create_resource(2, "old");
@@ -113,6 +113,7 @@ impl MyResourceBuilder {
                 replicas: self.replicas,
                 message: self.message,
                 labels: BTreeMap::new(),
+                ..Default::default()
             },
             status: None,
         }
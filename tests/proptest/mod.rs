@@ -50,6 +50,7 @@ fn any_event() -> impl Strategy<Value = ResourceEvent> {
         Just(ResourceEvent::DeletionRequested),
         Just(ResourceEvent::RecoveryInitiated),
         Just(ResourceEvent::FullyRecovered),
+        Just(ResourceEvent::ProgressDeadlineExceeded),
     ]
 }
 
@@ -115,6 +116,7 @@ mod crd_property_tests {
             replicas,
             message,
             labels: std::collections::BTreeMap::new(),
+            ..Default::default()
         })
     }
 
@@ -176,6 +178,7 @@ mod webhook_property_tests {
             replicas,
             message,
             labels: std::collections::BTreeMap::new(),
+            ..Default::default()
         })
     }
 
@@ -185,6 +188,7 @@ mod webhook_property_tests {
             replicas: 1,
             message: "test".to_string(),
             labels: std::collections::BTreeMap::new(),
+            ..Default::default()
         }
     }
 
@@ -4,7 +4,9 @@
 //! components in isolation.
 
 mod crd_tests {
-    use my_operator::crd::{Condition, Phase};
+    use my_operator::crd::{
+        Condition, DEFAULT_CONTAINER_PORT, DEFAULT_IMAGE, MyResourceSpec, Phase,
+    };
 
     #[test]
     fn test_phase_display() {
@@ -45,6 +47,18 @@ mod crd_tests {
         assert_eq!(condition.status, "True");
     }
 
+    #[test]
+    fn test_spec_defaults() {
+        let parsed = serde_json::from_str::<MyResourceSpec>("{}");
+        assert!(parsed.is_ok());
+        let spec = parsed.unwrap_or_default();
+        assert_eq!(spec.replicas, 1);
+        assert_eq!(spec.image, DEFAULT_IMAGE);
+        assert_eq!(spec.container_port, DEFAULT_CONTAINER_PORT);
+        assert!(spec.image_pull_policy.is_none());
+        assert!(spec.resources.is_none());
+    }
+
     #[test]
     fn test_condition_degraded() {
         let condition = Condition::degraded(true, "PodFailed", "Pod in crash loop", Some(3));
@@ -53,6 +67,918 @@ mod crd_tests {
     }
 }
 
+mod resources_tests {
+    use k8s_openapi::ByteString;
+    use k8s_openapi::api::core::v1::Secret;
+    use k8s_openapi::api::core::v1::{Affinity, LocalObjectReference, NodeAffinity};
+    use k8s_openapi::apimachinery::pkg::apis::meta::v1::ObjectMeta;
+    use k8s_openapi::apimachinery::pkg::util::intstr::IntOrString;
+    use my_operator::crd::{
+        AutoscalingSpec, DisruptionBudgetSpec, GatewayRef, GeneratedSecretSpec, HttpGetProbe,
+        ImagePullPolicy, IngressPath, IngressSpec, MyResource, MyResourceSpec, PasswordCharset,
+        PathType, ProbeSpec, ProbesSpec, RolloutSpec, RolloutStrategy, RouteKind,
+        SecretKeyProjection, SecretProjection, SecretRef, ServiceExposureSpec, ServicePortSpec,
+        ServiceType, SessionAffinity, StorageReclaimPolicy, StorageSpec, VolumeAccessMode,
+        WorkloadType,
+    };
+    use my_operator::resources::common::{
+        CONFIG_HASH_ANNOTATION, CONFIG_MOUNT_PATH, MAIN_CONTAINER_NAME, MANAGED_BY,
+        MANAGED_BY_LABEL, RECLAIM_POLICY_ANNOTATION, SECRET_HASH_ANNOTATION, annotate_secret_hash,
+        container_probe, default_resources, generate_configmap, generate_deployment,
+        generate_headless_service, generate_hpa, generate_http_route, generate_ingress,
+        generate_password, generate_pdb, generate_pvc, generate_secret, generate_service,
+        generate_statefulset, secret_hash, service_requires_recreate, uses_secret,
+    };
+
+    fn create_resource(spec: MyResourceSpec) -> MyResource {
+        MyResource {
+            metadata: ObjectMeta {
+                name: Some("app".to_string()),
+                namespace: Some("default".to_string()),
+                ..Default::default()
+            },
+            spec,
+            status: None,
+        }
+    }
+
+    #[test]
+    fn test_managed_by_label_cannot_be_overridden() {
+        let resource = create_resource(MyResourceSpec {
+            labels: [
+                (MANAGED_BY_LABEL.to_string(), "someone-else".to_string()),
+                ("team".to_string(), "payments".to_string()),
+            ]
+            .into(),
+            ..Default::default()
+        });
+
+        let labels = generate_configmap(&resource)
+            .metadata
+            .labels
+            .unwrap_or_default();
+        assert_eq!(
+            labels.get(MANAGED_BY_LABEL).map(String::as_str),
+            Some(MANAGED_BY)
+        );
+        assert_eq!(labels.get("team").map(String::as_str), Some("payments"));
+    }
+
+    #[test]
+    fn test_deployment_uses_container_settings() {
+        let resource = create_resource(MyResourceSpec {
+            image: "ghcr.io/example/app:1.2.3".to_string(),
+            image_pull_policy: Some(ImagePullPolicy::IfNotPresent),
+            image_pull_secrets: vec![LocalObjectReference {
+                name: "registry".to_string(),
+            }],
+            container_port: 8080,
+            ..Default::default()
+        });
+
+        let pod = generate_deployment(&resource)
+            .spec
+            .and_then(|s| s.template.spec)
+            .unwrap_or_default();
+        let container = pod.containers.first().cloned().unwrap_or_default();
+
+        assert_eq!(
+            container.image.as_deref(),
+            Some("ghcr.io/example/app:1.2.3")
+        );
+        assert_eq!(container.image_pull_policy.as_deref(), Some("IfNotPresent"));
+        assert_eq!(
+            container
+                .ports
+                .unwrap_or_default()
+                .first()
+                .map(|p| p.container_port),
+            Some(8080)
+        );
+        assert_eq!(container.resources, Some(default_resources()));
+        assert_eq!(
+            pod.image_pull_secrets,
+            Some(vec![LocalObjectReference {
+                name: "registry".to_string()
+            }])
+        );
+    }
+
+    #[test]
+    fn test_default_anti_affinity_only_with_multiple_replicas() {
+        let pod_affinity = |replicas| {
+            generate_deployment(&create_resource(MyResourceSpec {
+                replicas,
+                ..Default::default()
+            }))
+            .spec
+            .and_then(|s| s.template.spec)
+            .and_then(|p| p.affinity)
+        };
+
+        assert!(pod_affinity(1).is_none());
+
+        let terms = pod_affinity(3)
+            .and_then(|a| a.pod_anti_affinity)
+            .and_then(|a| a.preferred_during_scheduling_ignored_during_execution)
+            .unwrap_or_default();
+        assert_eq!(terms.len(), 1);
+        assert_eq!(
+            terms
+                .first()
+                .map(|t| t.pod_affinity_term.topology_key.as_str()),
+            Some("kubernetes.io/hostname")
+        );
+    }
+
+    #[test]
+    fn test_explicit_affinity_replaces_default() {
+        let affinity = Affinity {
+            node_affinity: Some(NodeAffinity::default()),
+            ..Default::default()
+        };
+        let resource = create_resource(MyResourceSpec {
+            replicas: 3,
+            affinity: Some(affinity.clone()),
+            priority_class_name: Some("high-priority".to_string()),
+            ..Default::default()
+        });
+
+        let pod = generate_deployment(&resource)
+            .spec
+            .and_then(|s| s.template.spec)
+            .unwrap_or_default();
+        assert_eq!(pod.affinity, Some(affinity));
+        assert_eq!(pod.priority_class_name.as_deref(), Some("high-priority"));
+    }
+
+    #[test]
+    fn test_default_probes_check_container_port() {
+        let resource = create_resource(MyResourceSpec {
+            container_port: 8080,
+            ..Default::default()
+        });
+
+        let container = generate_deployment(&resource)
+            .spec
+            .and_then(|s| s.template.spec)
+            .and_then(|p| p.containers.first().cloned())
+            .unwrap_or_default();
+        for probe in [container.liveness_probe, container.readiness_probe] {
+            assert_eq!(
+                probe.and_then(|p| p.tcp_socket).map(|t| t.port),
+                Some(IntOrString::Int(8080))
+            );
+        }
+        assert!(container.startup_probe.is_none());
+    }
+
+    #[test]
+    fn test_configured_probes() {
+        let readiness = ProbeSpec {
+            http_get: Some(HttpGetProbe {
+                path: "/healthz".to_string(),
+                ..Default::default()
+            }),
+            period_seconds: Some(5),
+            ..Default::default()
+        };
+        let probe = container_probe(Some(&readiness), 8080);
+        let http = probe.http_get.unwrap_or_default();
+        assert_eq!(http.path.as_deref(), Some("/healthz"));
+        assert_eq!(http.port, IntOrString::Int(8080));
+        assert_eq!(probe.period_seconds, Some(5));
+        assert!(probe.tcp_socket.is_none());
+
+        let resource = create_resource(MyResourceSpec {
+            probes: Some(ProbesSpec {
+                startup: Some(ProbeSpec {
+                    failure_threshold: Some(30),
+                    ..Default::default()
+                }),
+                ..Default::default()
+            }),
+            ..Default::default()
+        });
+        let startup = generate_deployment(&resource)
+            .spec
+            .and_then(|s| s.template.spec)
+            .and_then(|p| p.containers.first().cloned())
+            .and_then(|c| c.startup_probe);
+        assert_eq!(startup.and_then(|p| p.failure_threshold), Some(30));
+    }
+
+    #[test]
+    fn test_configmap_is_mounted() {
+        let resource = create_resource(MyResourceSpec {
+            message: "hello".to_string(),
+            config: [("app.yaml".to_string(), "port: 8080".to_string())].into(),
+            ..Default::default()
+        });
+
+        let data = generate_configmap(&resource).data.unwrap_or_default();
+        assert_eq!(data.get("message").map(String::as_str), Some("hello"));
+        assert_eq!(data.get("app.yaml").map(String::as_str), Some("port: 8080"));
+
+        let pod = generate_deployment(&resource)
+            .spec
+            .and_then(|s| s.template.spec)
+            .unwrap_or_default();
+        let volume = pod.volumes.unwrap_or_default().first().cloned();
+        assert_eq!(
+            volume.and_then(|v| v.config_map).map(|c| c.name),
+            Some("app".to_string())
+        );
+        let mount = pod
+            .containers
+            .first()
+            .and_then(|c| c.volume_mounts.clone())
+            .unwrap_or_default()
+            .first()
+            .cloned()
+            .unwrap_or_default();
+        assert_eq!(mount.mount_path, CONFIG_MOUNT_PATH);
+        assert_eq!(mount.read_only, Some(true));
+    }
+
+    #[test]
+    fn test_config_hash_rolls_pods_on_config_change() {
+        let config_hash = |spec: MyResourceSpec| {
+            generate_deployment(&create_resource(spec))
+                .spec
+                .and_then(|s| s.template.metadata)
+                .and_then(|m| m.annotations)
+                .and_then(|a| a.get(CONFIG_HASH_ANNOTATION).cloned())
+        };
+        let base = config_hash(MyResourceSpec {
+            message: "hello".to_string(),
+            ..Default::default()
+        });
+        assert!(base.is_some());
+
+        // Scaling doesn't restart pods
+        assert_eq!(
+            config_hash(MyResourceSpec {
+                message: "hello".to_string(),
+                replicas: 3,
+                ..Default::default()
+            }),
+            base
+        );
+        assert_ne!(
+            config_hash(MyResourceSpec {
+                message: "goodbye".to_string(),
+                ..Default::default()
+            }),
+            base
+        );
+        assert_ne!(
+            config_hash(MyResourceSpec {
+                message: "hello".to_string(),
+                config: [("app.yaml".to_string(), String::new())].into(),
+                ..Default::default()
+            }),
+            base
+        );
+    }
+
+    #[test]
+    fn test_generate_password() {
+        let password = generate_password(40, PasswordCharset::Hex);
+        assert_eq!(password.len(), 40);
+        assert!(password.chars().all(|c| c.is_ascii_hexdigit()));
+        assert_ne!(password, generate_password(40, PasswordCharset::Hex));
+    }
+
+    #[test]
+    fn test_generated_secret_keeps_existing_password() {
+        let resource = create_resource(MyResourceSpec {
+            generated_secret: Some(GeneratedSecretSpec {
+                length: 16,
+                ..Default::default()
+            }),
+            ..Default::default()
+        });
+        assert!(uses_secret(&resource, "app-generated"));
+
+        let secret = generate_secret(&resource, None).unwrap_or_default();
+        assert_eq!(secret.metadata.name.as_deref(), Some("app-generated"));
+        let password = secret
+            .data
+            .as_ref()
+            .and_then(|d| d.get("password"))
+            .cloned()
+            .unwrap_or_default();
+        assert_eq!(password.0.len(), 16);
+
+        let regenerated = generate_secret(&resource, Some(&secret)).unwrap_or_default();
+        assert_eq!(regenerated.data, secret.data);
+
+        assert!(generate_secret(&create_resource(MyResourceSpec::default()), None).is_none());
+    }
+
+    #[test]
+    fn test_deployment_projects_secrets() {
+        let resource = create_resource(MyResourceSpec {
+            secret_refs: vec![
+                SecretRef {
+                    name: "db".to_string(),
+                    projection: SecretProjection::Env,
+                    keys: vec![SecretKeyProjection {
+                        key: "password".to_string(),
+                        target: Some("DB_PASSWORD".to_string()),
+                    }],
+                },
+                SecretRef {
+                    name: "api".to_string(),
+                    ..Default::default()
+                },
+                SecretRef {
+                    name: "tls".to_string(),
+                    projection: SecretProjection::File,
+                    keys: Vec::new(),
+                },
+            ],
+            ..Default::default()
+        });
+        assert!(uses_secret(&resource, "db"));
+        assert!(!uses_secret(&resource, "app-generated"));
+
+        let pod = generate_deployment(&resource)
+            .spec
+            .and_then(|s| s.template.spec)
+            .unwrap_or_default();
+        let container = pod.containers.first().cloned().unwrap_or_default();
+
+        let db_password = container
+            .env
+            .unwrap_or_default()
+            .into_iter()
+            .find(|e| e.name == "DB_PASSWORD")
+            .and_then(|e| e.value_from)
+            .and_then(|v| v.secret_key_ref);
+        assert_eq!(
+            db_password.map(|r| (r.name, r.key)),
+            Some(("db".to_string(), "password".to_string()))
+        );
+        assert_eq!(
+            container
+                .env_from
+                .unwrap_or_default()
+                .first()
+                .and_then(|e| e.secret_ref.as_ref())
+                .map(|r| r.name.as_str()),
+            Some("api")
+        );
+        let tls_mount = container
+            .volume_mounts
+            .unwrap_or_default()
+            .into_iter()
+            .find(|m| m.mount_path == "/etc/secrets/tls");
+        assert_eq!(tls_mount.and_then(|m| m.read_only), Some(true));
+        assert!(
+            pod.volumes.unwrap_or_default().iter().any(|v| v
+                .secret
+                .as_ref()
+                .and_then(|s| s.secret_name.as_deref())
+                == Some("tls"))
+        );
+    }
+
+    #[test]
+    fn test_secret_hash_changes_with_data() {
+        let secret = |value: &str| Secret {
+            metadata: ObjectMeta {
+                name: Some("db".to_string()),
+                ..Default::default()
+            },
+            data: Some(
+                [(
+                    "password".to_string(),
+                    ByteString(value.as_bytes().to_vec()),
+                )]
+                .into(),
+            ),
+            ..Default::default()
+        };
+        let hash = secret_hash(&[secret("one")]);
+        assert_eq!(hash, secret_hash(&[secret("one")]));
+        assert_ne!(hash, secret_hash(&[secret("two")]));
+
+        let mut template = generate_deployment(&create_resource(MyResourceSpec::default()))
+            .spec
+            .map(|s| s.template)
+            .unwrap_or_default();
+        annotate_secret_hash(&mut template, hash.clone());
+        let annotations = template
+            .metadata
+            .and_then(|m| m.annotations)
+            .unwrap_or_default();
+        assert_eq!(annotations.get(SECRET_HASH_ANNOTATION), Some(&hash));
+        assert!(annotations.contains_key(CONFIG_HASH_ANNOTATION));
+    }
+
+    #[test]
+    fn test_service_exposes_container_port() {
+        let resource = create_resource(MyResourceSpec {
+            container_port: 8080,
+            ..Default::default()
+        });
+
+        let ports = generate_service(&resource)
+            .spec
+            .and_then(|s| s.ports)
+            .unwrap_or_default();
+        assert_eq!(ports.first().map(|p| p.port), Some(8080));
+    }
+
+    #[test]
+    fn test_service_exposure_options() {
+        let resource = create_resource(MyResourceSpec {
+            container_port: 8080,
+            service: Some(ServiceExposureSpec {
+                type_: ServiceType::LoadBalancer,
+                ports: vec![
+                    ServicePortSpec {
+                        name: Some("http".to_string()),
+                        port: 80,
+                        ..Default::default()
+                    },
+                    ServicePortSpec {
+                        name: Some("metrics".to_string()),
+                        port: 9090,
+                        target_port: Some(9090),
+                        ..Default::default()
+                    },
+                ],
+                annotations: [(
+                    "service.beta.kubernetes.io/aws-load-balancer-type".to_string(),
+                    "nlb".to_string(),
+                )]
+                .into(),
+                session_affinity: Some(SessionAffinity::ClientIP),
+                ..Default::default()
+            }),
+            ..Default::default()
+        });
+
+        let service = generate_service(&resource);
+        assert!(
+            service
+                .metadata
+                .annotations
+                .unwrap_or_default()
+                .contains_key("service.beta.kubernetes.io/aws-load-balancer-type")
+        );
+        let spec = service.spec.unwrap_or_default();
+        assert_eq!(spec.type_.as_deref(), Some("LoadBalancer"));
+        assert_eq!(spec.session_affinity.as_deref(), Some("ClientIP"));
+        assert!(spec.cluster_ip.is_none());
+        let ports = spec.ports.unwrap_or_default();
+        assert_eq!(
+            ports
+                .iter()
+                .map(|p| (p.port, p.target_port.clone()))
+                .collect::<Vec<_>>(),
+            vec![
+                (80, Some(IntOrString::String("http".to_string()))),
+                (9090, Some(IntOrString::Int(9090))),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_headless_service_requires_recreate() {
+        let service = |type_| {
+            generate_service(&create_resource(MyResourceSpec {
+                service: Some(ServiceExposureSpec {
+                    type_,
+                    ..Default::default()
+                }),
+                ..Default::default()
+            }))
+        };
+        let headless = service(ServiceType::Headless);
+        let spec = headless.spec.clone().unwrap_or_default();
+        assert_eq!(spec.type_.as_deref(), Some("ClusterIP"));
+        assert_eq!(spec.cluster_ip.as_deref(), Some("None"));
+
+        let mut live = service(ServiceType::ClusterIP);
+        if let Some(spec) = live.spec.as_mut() {
+            spec.cluster_ip = Some("10.96.0.12".to_string());
+        }
+        assert!(service_requires_recreate(&headless, &live));
+        assert!(service_requires_recreate(&live, &headless));
+        assert!(!service_requires_recreate(
+            &service(ServiceType::NodePort),
+            &live
+        ));
+    }
+
+    #[test]
+    fn test_generate_ingress() {
+        let resource = create_resource(MyResourceSpec {
+            container_port: 8080,
+            ingress: Some(IngressSpec {
+                hosts: vec!["a.example.com".to_string(), "b.example.com".to_string()],
+                tls_secret_name: Some("app-tls".to_string()),
+                class_name: Some("nginx".to_string()),
+                ..Default::default()
+            }),
+            ..Default::default()
+        });
+        assert!(generate_http_route(&resource).is_none());
+
+        let spec = generate_ingress(&resource)
+            .and_then(|i| i.spec)
+            .unwrap_or_default();
+        assert_eq!(spec.ingress_class_name.as_deref(), Some("nginx"));
+        let rules = spec.rules.unwrap_or_default();
+        assert_eq!(rules.len(), 2);
+        let path = rules
+            .first()
+            .and_then(|r| r.http.as_ref())
+            .and_then(|h| h.paths.first())
+            .cloned()
+            .unwrap_or_default();
+        assert_eq!(path.path.as_deref(), Some("/"));
+        assert_eq!(path.path_type, "Prefix");
+        let backend = path.backend.service.unwrap_or_default();
+        assert_eq!(backend.name, "app");
+        assert_eq!(backend.port.and_then(|p| p.number), Some(8080));
+        assert_eq!(
+            spec.tls
+                .unwrap_or_default()
+                .first()
+                .and_then(|t| t.secret_name.as_deref()),
+            Some("app-tls")
+        );
+
+        assert!(generate_ingress(&create_resource(MyResourceSpec::default())).is_none());
+    }
+
+    #[test]
+    fn test_generate_http_route() {
+        let resource = create_resource(MyResourceSpec {
+            ingress: Some(IngressSpec {
+                kind: RouteKind::HTTPRoute,
+                hosts: vec!["app.example.com".to_string()],
+                paths: vec![IngressPath {
+                    path: "/api".to_string(),
+                    path_type: PathType::Exact,
+                }],
+                gateway: Some(GatewayRef {
+                    name: "public".to_string(),
+                    namespace: Some("gateways".to_string()),
+                    section_name: Some("https".to_string()),
+                }),
+                ..Default::default()
+            }),
+            ..Default::default()
+        });
+        assert!(generate_ingress(&resource).is_none());
+
+        let route = generate_http_route(&resource);
+        assert_eq!(
+            route.as_ref().and_then(|r| r.metadata.name.as_deref()),
+            Some("app")
+        );
+        let spec = route.map(|r| r.spec).unwrap_or_default();
+        assert_eq!(spec.hostnames, vec!["app.example.com".to_string()]);
+        assert_eq!(
+            spec.parent_refs
+                .first()
+                .map(|p| (p.name.as_str(), p.namespace.as_deref())),
+            Some(("public", Some("gateways")))
+        );
+        let rule = spec.rules.first().cloned().unwrap_or_default();
+        let path = rule
+            .matches
+            .first()
+            .and_then(|m| m.path.clone())
+            .unwrap_or_default();
+        assert_eq!(
+            (path.type_.as_str(), path.value.as_str()),
+            ("Exact", "/api")
+        );
+        assert_eq!(
+            rule.backend_refs.first().map(|b| (b.name.as_str(), b.port)),
+            Some(("app", Some(80)))
+        );
+    }
+
+    #[test]
+    fn test_pdb_only_for_multiple_replicas() {
+        let resource = create_resource(MyResourceSpec {
+            replicas: 1,
+            ..Default::default()
+        });
+        assert!(generate_pdb(&resource).is_none());
+
+        let resource = create_resource(MyResourceSpec {
+            replicas: 3,
+            ..Default::default()
+        });
+        let spec = generate_pdb(&resource)
+            .and_then(|p| p.spec)
+            .unwrap_or_default();
+        assert_eq!(spec.max_unavailable, Some(IntOrString::Int(1)));
+        assert_eq!(spec.min_available, None);
+        assert_eq!(
+            spec.selector
+                .and_then(|s| s.match_labels)
+                .and_then(|l| l.get("app.kubernetes.io/name").cloned()),
+            Some("app".to_string())
+        );
+    }
+
+    #[test]
+    fn test_pdb_uses_configured_budget() {
+        let resource = create_resource(MyResourceSpec {
+            replicas: 4,
+            pod_disruption_budget: Some(DisruptionBudgetSpec {
+                min_available: Some(IntOrString::String("50%".to_string())),
+                max_unavailable: None,
+            }),
+            ..Default::default()
+        });
+        let spec = generate_pdb(&resource)
+            .and_then(|p| p.spec)
+            .unwrap_or_default();
+        assert_eq!(
+            spec.min_available,
+            Some(IntOrString::String("50%".to_string()))
+        );
+        assert_eq!(spec.max_unavailable, None);
+    }
+
+    #[test]
+    fn test_statefulset_mounts_volume_claims() {
+        use k8s_openapi::api::core::v1::PersistentVolumeClaim;
+
+        let resource = create_resource(MyResourceSpec {
+            replicas: 3,
+            workload_type: WorkloadType::StatefulSet,
+            volume_claim_templates: vec![PersistentVolumeClaim {
+                metadata: ObjectMeta {
+                    name: Some("data".to_string()),
+                    ..Default::default()
+                },
+                ..Default::default()
+            }],
+            ..Default::default()
+        });
+
+        let spec = generate_statefulset(&resource).spec.unwrap_or_default();
+        assert_eq!(spec.replicas, Some(3));
+        assert_eq!(spec.service_name.as_deref(), Some("app-headless"));
+        assert_eq!(spec.volume_claim_templates.map(|c| c.len()), Some(1));
+        let mounts = spec
+            .template
+            .spec
+            .and_then(|p| p.containers.into_iter().next())
+            .and_then(|c| c.volume_mounts)
+            .unwrap_or_default();
+        assert!(
+            mounts
+                .iter()
+                .any(|m| m.name == "data" && m.mount_path == "/data/data")
+        );
+        // The ConfigMap is still mounted like in the Deployment
+        assert!(mounts.iter().any(|m| m.mount_path == CONFIG_MOUNT_PATH));
+
+        let headless = generate_headless_service(&resource);
+        assert_eq!(headless.metadata.name.as_deref(), Some("app-headless"));
+        let spec = headless.spec.unwrap_or_default();
+        assert_eq!(spec.cluster_ip.as_deref(), Some("None"));
+        assert_eq!(spec.publish_not_ready_addresses, Some(true));
+        assert_eq!(
+            spec.selector
+                .and_then(|s| s.get("app.kubernetes.io/name").cloned()),
+            Some("app".to_string())
+        );
+    }
+
+    #[test]
+    fn test_storage_claim_is_mounted() {
+        use k8s_openapi::apimachinery::pkg::api::resource::Quantity;
+
+        assert!(generate_pvc(&create_resource(MyResourceSpec::default())).is_none());
+
+        let resource = create_resource(MyResourceSpec {
+            storage: Some(StorageSpec {
+                size: Quantity("5Gi".to_string()),
+                storage_class_name: Some("fast".to_string()),
+                access_modes: vec![VolumeAccessMode::ReadWriteOncePod],
+                reclaim_policy: StorageReclaimPolicy::Retain,
+                ..Default::default()
+            }),
+            ..Default::default()
+        });
+
+        let claim = generate_pvc(&resource).unwrap_or_default();
+        assert_eq!(claim.metadata.name.as_deref(), Some("app-data"));
+        assert_eq!(
+            claim
+                .metadata
+                .annotations
+                .and_then(|a| a.get(RECLAIM_POLICY_ANNOTATION).cloned()),
+            Some("Retain".to_string())
+        );
+        let spec = claim.spec.unwrap_or_default();
+        assert_eq!(spec.storage_class_name.as_deref(), Some("fast"));
+        assert_eq!(
+            spec.access_modes,
+            Some(vec!["ReadWriteOncePod".to_string()])
+        );
+        assert_eq!(
+            spec.resources
+                .and_then(|r| r.requests)
+                .and_then(|r| r.get("storage").cloned()),
+            Some(Quantity("5Gi".to_string()))
+        );
+
+        let pod = generate_deployment(&resource)
+            .spec
+            .and_then(|s| s.template.spec)
+            .unwrap_or_default();
+        assert!(pod.volumes.unwrap_or_default().iter().any(|v| {
+            v.persistent_volume_claim
+                .as_ref()
+                .is_some_and(|c| c.claim_name == "app-data")
+        }));
+        let mounts = pod
+            .containers
+            .into_iter()
+            .next()
+            .and_then(|c| c.volume_mounts)
+            .unwrap_or_default();
+        assert!(mounts.iter().any(|m| m.mount_path == "/var/lib/data"));
+    }
+
+    #[test]
+    fn test_extra_containers_get_hardened_defaults() {
+        use k8s_openapi::api::core::v1::{Container, SecurityContext};
+
+        let resource = create_resource(MyResourceSpec {
+            init_containers: vec![Container {
+                name: "migrate".to_string(),
+                image: Some("migrate:1".to_string()),
+                ..Default::default()
+            }],
+            sidecars: vec![Container {
+                name: "logs".to_string(),
+                image: Some("fluent-bit:3".to_string()),
+                security_context: Some(SecurityContext {
+                    read_only_root_filesystem: Some(false),
+                    ..Default::default()
+                }),
+                ..Default::default()
+            }],
+            ..Default::default()
+        });
+
+        let pod = generate_deployment(&resource)
+            .spec
+            .and_then(|s| s.template.spec)
+            .unwrap_or_default();
+        let names: Vec<_> = pod.containers.iter().map(|c| c.name.as_str()).collect();
+        assert_eq!(names, vec![MAIN_CONTAINER_NAME, "logs"]);
+
+        let init = pod
+            .init_containers
+            .and_then(|c| c.into_iter().next())
+            .and_then(|c| c.security_context)
+            .unwrap_or_default();
+        assert_eq!(init.allow_privilege_escalation, Some(false));
+        assert_eq!(init.read_only_root_filesystem, Some(true));
+        assert_eq!(init.run_as_non_root, Some(true));
+        assert_eq!(
+            init.capabilities.and_then(|c| c.drop),
+            Some(vec!["ALL".to_string()])
+        );
+
+        // Fields set on the sidecar win over the defaults
+        let sidecar = pod
+            .containers
+            .get(1)
+            .and_then(|c| c.security_context.clone())
+            .unwrap_or_default();
+        assert_eq!(sidecar.read_only_root_filesystem, Some(false));
+        assert_eq!(sidecar.allow_privilege_escalation, Some(false));
+    }
+
+    #[test]
+    fn test_deployment_rollout_strategy() {
+        let spec = generate_deployment(&create_resource(MyResourceSpec::default()))
+            .spec
+            .unwrap_or_default();
+        assert_eq!(spec.strategy, None);
+        assert_eq!(spec.progress_deadline_seconds, Some(600));
+
+        let resource = create_resource(MyResourceSpec {
+            rollout: Some(RolloutSpec {
+                max_surge: Some(IntOrString::Int(0)),
+                max_unavailable: Some(IntOrString::String("50%".to_string())),
+                progress_deadline_seconds: Some(120),
+                ..Default::default()
+            }),
+            ..Default::default()
+        });
+        let spec = generate_deployment(&resource).spec.unwrap_or_default();
+        assert_eq!(spec.progress_deadline_seconds, Some(120));
+        let strategy = spec.strategy.unwrap_or_default();
+        assert_eq!(strategy.type_.as_deref(), Some("RollingUpdate"));
+        let rolling = strategy.rolling_update.unwrap_or_default();
+        assert_eq!(rolling.max_surge, Some(IntOrString::Int(0)));
+        assert_eq!(
+            rolling.max_unavailable,
+            Some(IntOrString::String("50%".to_string()))
+        );
+
+        let resource = create_resource(MyResourceSpec {
+            rollout: Some(RolloutSpec {
+                strategy: RolloutStrategy::Recreate,
+                ..Default::default()
+            }),
+            ..Default::default()
+        });
+        let strategy = generate_deployment(&resource)
+            .spec
+            .and_then(|s| s.strategy)
+            .unwrap_or_default();
+        assert_eq!(strategy.type_.as_deref(), Some("Recreate"));
+        assert_eq!(strategy.rolling_update, None);
+    }
+
+    #[test]
+    fn test_autoscaling_leaves_replicas_to_hpa() {
+        let fixed = create_resource(MyResourceSpec {
+            replicas: 2,
+            ..Default::default()
+        });
+        assert!(generate_hpa(&fixed).is_none());
+        assert_eq!(
+            generate_deployment(&fixed).spec.and_then(|s| s.replicas),
+            Some(2)
+        );
+
+        let autoscaled = create_resource(MyResourceSpec {
+            replicas: 2,
+            autoscaling: Some(AutoscalingSpec {
+                max_replicas: 6,
+                target_memory_utilization: Some(75),
+                ..Default::default()
+            }),
+            ..Default::default()
+        });
+        assert_eq!(
+            generate_deployment(&autoscaled)
+                .spec
+                .and_then(|s| s.replicas),
+            None
+        );
+
+        let spec = generate_hpa(&autoscaled)
+            .and_then(|h| h.spec)
+            .unwrap_or_default();
+        assert_eq!(spec.scale_target_ref.kind, "Deployment");
+        assert_eq!(spec.scale_target_ref.name, "app");
+        assert_eq!((spec.min_replicas, spec.max_replicas), (Some(2), 6));
+        let targets: Vec<_> = spec
+            .metrics
+            .unwrap_or_default()
+            .into_iter()
+            .filter_map(|m| m.resource)
+            .map(|r| (r.name, r.target.average_utilization))
+            .collect();
+        assert_eq!(targets, vec![("memory".to_string(), Some(75))]);
+    }
+
+    #[test]
+    fn test_hpa_defaults_to_cpu_target() {
+        let resource = create_resource(MyResourceSpec {
+            autoscaling: Some(AutoscalingSpec {
+                min_replicas: Some(1),
+                max_replicas: 3,
+                ..Default::default()
+            }),
+            ..Default::default()
+        });
+        let metric = generate_hpa(&resource)
+            .and_then(|h| h.spec)
+            .and_then(|s| s.metrics)
+            .and_then(|m| m.into_iter().next())
+            .and_then(|m| m.resource);
+        assert_eq!(
+            metric.map(|r| (r.name, r.target.average_utilization)),
+            Some(("cpu".to_string(), Some(80)))
+        );
+        // The budget follows maxReplicas, not the single starting replica
+        assert!(generate_pdb(&resource).is_some());
+    }
+}
+
 mod state_machine_tests {
     use my_operator::controller::state_machine::{ResourceEvent, ResourceStateMachine};
     use my_operator::crd::Phase;
@@ -190,8 +1116,14 @@ mod error_tests {
 }
 
 mod status_tests {
-    use my_operator::controller::status::{ConditionBuilder, is_condition_true};
-    use my_operator::crd::Condition;
+    use k8s_openapi::api::core::v1::{ContainerStatus, Pod, PodStatus};
+    use k8s_openapi::apimachinery::pkg::apis::meta::v1::ObjectMeta;
+    use my_operator::controller::status::{
+        ConditionBuilder, is_condition_true, probe_failure_message, push_transition, status_changed,
+    };
+    use my_operator::crd::{
+        Condition, MAX_TRANSITION_HISTORY, MyResourceStatus, Phase, PhaseTransition,
+    };
 
     #[test]
     fn test_condition_builder() {
@@ -217,9 +1149,136 @@ mod status_tests {
         assert!(is_condition_true(&conditions, "Ready"));
     }
 
+    #[test]
+    fn test_condition_builder_keeps_transition_time_when_unchanged() {
+        let mut existing = Condition::ready(true, "AllReady", "All ready", Some(1));
+        existing.last_transition_time = "2024-01-01T00:00:00Z".to_string();
+
+        let mut builder = ConditionBuilder::from_conditions(vec![existing]);
+        builder.ready(true, "AllReady", "Still ready", Some(2));
+        let conditions = builder.build();
+
+        let ready = conditions.first();
+        assert_eq!(conditions.len(), 1);
+        assert_eq!(
+            ready.map(|c| c.last_transition_time.as_str()),
+            Some("2024-01-01T00:00:00Z")
+        );
+        assert_eq!(ready.map(|c| c.message.as_str()), Some("Still ready"));
+        assert_eq!(ready.and_then(|c| c.observed_generation), Some(2));
+    }
+
+    #[test]
+    fn test_condition_builder_updates_transition_time_on_flip() {
+        let mut existing = Condition::ready(true, "AllReady", "All ready", Some(1));
+        existing.last_transition_time = "2024-01-01T00:00:00Z".to_string();
+
+        let mut builder = ConditionBuilder::from_conditions(vec![existing]);
+        builder.ready(false, "ReplicasNotReady", "1/3 replicas ready", Some(1));
+        let conditions = builder.build();
+
+        assert!(!is_condition_true(&conditions, "Ready"));
+        assert_ne!(
+            conditions.first().map(|c| c.last_transition_time.as_str()),
+            Some("2024-01-01T00:00:00Z")
+        );
+    }
+
+    #[test]
+    fn test_status_changed() {
+        let current = MyResourceStatus {
+            phase: Phase::Running,
+            ready_replicas: 3,
+            observed_generation: Some(1),
+            conditions: vec![Condition::ready(true, "AllReady", "All ready", Some(1))],
+            ..Default::default()
+        };
+
+        assert!(status_changed(None, &current));
+        assert!(!status_changed(Some(&current), &current.clone()));
+
+        let desired = MyResourceStatus {
+            ready_replicas: 2,
+            ..current.clone()
+        };
+        assert!(status_changed(Some(&current), &desired));
+    }
+
     #[test]
     fn test_is_condition_true_missing() {
         let conditions: Vec<Condition> = vec![];
         assert!(!is_condition_true(&conditions, "Ready"));
     }
+
+    #[test]
+    fn test_push_transition_is_bounded() {
+        let mut history = Vec::new();
+        for generation in 0..(MAX_TRANSITION_HISTORY as i64 + 5) {
+            push_transition(
+                &mut history,
+                PhaseTransition::new(
+                    Phase::Running,
+                    Phase::Updating,
+                    "SpecChanged",
+                    "Resource spec changed, starting update",
+                    Some(generation),
+                ),
+            );
+        }
+
+        assert_eq!(history.len(), MAX_TRANSITION_HISTORY);
+        // Oldest entries are dropped first
+        assert_eq!(history.first().and_then(|t| t.observed_generation), Some(5));
+        assert_eq!(
+            history.last().and_then(|t| t.observed_generation),
+            Some(MAX_TRANSITION_HISTORY as i64 + 4)
+        );
+    }
+
+    fn pod(name: &str, phase: &str, containers: Vec<ContainerStatus>) -> Pod {
+        Pod {
+            metadata: ObjectMeta {
+                name: Some(name.to_string()),
+                ..Default::default()
+            },
+            spec: None,
+            status: Some(PodStatus {
+                phase: Some(phase.to_string()),
+                container_statuses: Some(containers),
+                ..Default::default()
+            }),
+        }
+    }
+
+    fn container(ready: bool, started: bool, restart_count: i32) -> ContainerStatus {
+        ContainerStatus {
+            name: "main".to_string(),
+            ready,
+            started: Some(started),
+            restart_count,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_probe_failure_message() {
+        // Healthy and still-starting pods are not probe failures
+        assert_eq!(
+            probe_failure_message(&[
+                pod("app-1", "Running", vec![container(true, true, 0)]),
+                pod("app-2", "Running", vec![container(false, false, 0)]),
+                pod("app-3", "Pending", vec![container(false, true, 2)]),
+            ]),
+            None
+        );
+
+        assert_eq!(
+            probe_failure_message(&[
+                pod("app-1", "Running", vec![container(false, true, 0)]),
+                pod("app-2", "Running", vec![container(false, false, 3)]),
+            ])
+            .as_deref(),
+            Some("Containers failing probes in 2 pod(s): app-1: main; app-2: main (restarts: 3)")
+        );
+    }
 }